@@ -0,0 +1,312 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use carbide_parser::nodes::{Attribute, LiteralValue, Statement};
+use carbide_parser::visit::{Visitor, walk_statement};
+
+use crate::errors::CarbideCoreError;
+
+/// A kind of declaration that attributes can be placed on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AttributeTarget {
+    Function,
+    Let,
+}
+
+impl AttributeTarget {
+    /// Get the target of `statement`, if attributes can be placed on it
+    #[must_use]
+    pub fn of(statement: &Statement) -> Option<Self> {
+        match statement {
+            Statement::FunctionDeclaration { .. } => Some(Self::Function),
+            Statement::LetDeclaration { .. } => Some(Self::Let),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for AttributeTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Function => write!(f, "function"),
+            Self::Let => write!(f, "`let` declaration"),
+        }
+    }
+}
+
+/// The type of literal an attribute argument must be
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArgumentKind {
+    Int,
+    Float,
+    String,
+    Bool,
+}
+
+impl ArgumentKind {
+    /// Check if `literal` is of this kind
+    #[must_use]
+    pub fn matches(self, literal: &LiteralValue) -> bool {
+        matches!(
+            (self, literal),
+            (Self::Int, LiteralValue::Int(_))
+                | (Self::Float, LiteralValue::Float(_))
+                | (Self::String, LiteralValue::String(_))
+                | (Self::Bool, LiteralValue::Bool(_))
+        )
+    }
+}
+
+impl fmt::Display for ArgumentKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Int => write!(f, "int"),
+            Self::Float => write!(f, "float"),
+            Self::String => write!(f, "string"),
+            Self::Bool => write!(f, "bool"),
+        }
+    }
+}
+
+/// Describes a known attribute, where it can go and what arguments it takes
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeSpec {
+    pub name: String,
+    pub targets: Vec<AttributeTarget>,
+    /// Kinds of the arguments, in order
+    pub arguments: Vec<ArgumentKind>,
+    /// How many of the `arguments` have to be given, the rest are optional
+    pub required: usize,
+}
+
+impl AttributeSpec {
+    /// Create an [`AttributeSpec`] for an attribute without arguments
+    #[must_use]
+    pub fn new(name: &str, targets: &[AttributeTarget]) -> Self {
+        Self {
+            name: name.to_string(),
+            targets: targets.to_vec(),
+            arguments: Vec::new(),
+            required: 0,
+        }
+    }
+
+    /// Check if `arguments` are accepted by this attribute
+    #[must_use]
+    pub fn accepts(&self, arguments: &[LiteralValue]) -> bool {
+        (self.required..=self.arguments.len()).contains(&arguments.len())
+            && self
+                .arguments
+                .iter()
+                .zip(arguments)
+                .all(|(kind, argument)| kind.matches(argument))
+    }
+
+    /// Describe the accepted arguments, like `` no arguments `` or `` an optional string ``
+    #[must_use]
+    pub fn describe_arguments(&self) -> String {
+        let described = self
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(i, kind)| {
+                if i < self.required {
+                    let article = if *kind == ArgumentKind::Int {
+                        "an"
+                    } else {
+                        "a"
+                    };
+                    format!("{article} {kind}")
+                } else {
+                    format!("an optional {kind}")
+                }
+            })
+            .collect::<Vec<_>>();
+
+        match described.split_last() {
+            None => "no arguments".to_string(),
+            Some((last, [])) => last.clone(),
+            Some((last, rest)) => format!("{} and {last}", rest.join(", ")),
+        }
+    }
+}
+
+/// The attributes known to the compiler
+///
+/// Attributes that aren't registered are rejected by [`check_attributes`]
+#[derive(Debug, Clone)]
+pub struct AttributeRegistry {
+    specs: HashMap<String, AttributeSpec>,
+}
+
+impl Default for AttributeRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AttributeRegistry {
+    /// Create an [`AttributeRegistry`] with the builtin attributes:
+    ///
+    /// - `#[inline]` on functions
+    /// - `#[deprecated]` or `#[deprecated("note")]` on functions and `let` declarations
+    /// - `#[test]` on functions
+    #[must_use]
+    pub fn new() -> Self {
+        let mut registry = Self::empty();
+        registry.register(AttributeSpec::new("inline", &[AttributeTarget::Function]));
+        registry.register(AttributeSpec {
+            arguments: vec![ArgumentKind::String],
+            ..AttributeSpec::new(
+                "deprecated",
+                &[AttributeTarget::Function, AttributeTarget::Let],
+            )
+        });
+        registry.register(AttributeSpec::new("test", &[AttributeTarget::Function]));
+        registry
+    }
+
+    /// Create an [`AttributeRegistry`] without any attributes
+    #[must_use]
+    pub fn empty() -> Self {
+        Self {
+            specs: HashMap::new(),
+        }
+    }
+
+    /// Add `spec` to the registry, returning the spec it replaced if there was one
+    pub fn register(&mut self, spec: AttributeSpec) -> Option<AttributeSpec> {
+        self.specs.insert(spec.name.clone(), spec)
+    }
+
+    /// Get the [`AttributeSpec`] for the attribute called `name`
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&AttributeSpec> {
+        self.specs.get(name)
+    }
+
+    /// Check if an attribute called `name` is registered
+    #[must_use]
+    pub fn is_known(&self, name: &str) -> bool {
+        self.specs.contains_key(name)
+    }
+}
+
+/// Get the attributes of `statement`, which is empty for statements that can't have any
+#[must_use]
+pub fn attributes_of(statement: &Statement) -> &[Attribute] {
+    match statement {
+        Statement::FunctionDeclaration { attributes, .. }
+        | Statement::LetDeclaration { attributes, .. } => attributes,
+        _ => &[],
+    }
+}
+
+/// Find the first attribute called `name` in `attributes`
+#[must_use]
+pub fn find_attribute<'a>(attributes: &'a [Attribute], name: &str) -> Option<&'a Attribute> {
+    attributes.iter().find(|attribute| attribute.name == name)
+}
+
+/// Find every declaration in `program` that has an attribute called `name`, including ones
+/// nested in bodies
+#[must_use]
+pub fn declarations_with<'a>(program: &'a [Statement], name: &str) -> Vec<&'a Statement> {
+    let mut collector = AttributeCollector {
+        name,
+        found: Vec::new(),
+    };
+    for statement in program {
+        collector.visit_statement(statement);
+    }
+    collector.found
+}
+
+struct AttributeCollector<'a, 'n> {
+    name: &'n str,
+    found: Vec<&'a Statement>,
+}
+
+impl<'a> Visitor<'a> for AttributeCollector<'a, '_> {
+    fn visit_statement(&mut self, statement: &'a Statement) {
+        if find_attribute(attributes_of(statement), self.name).is_some() {
+            self.found.push(statement);
+        }
+        walk_statement(self, statement);
+    }
+}
+
+/// Checks that every attribute is known, placed on a declaration it applies to, given valid
+/// arguments and not repeated
+pub struct AttributeChecker<'r> {
+    registry: &'r AttributeRegistry,
+    errors: Vec<CarbideCoreError>,
+}
+
+/// Check every attribute in `program` against `registry`
+#[must_use]
+pub fn check_attributes(
+    program: &[Statement],
+    registry: &AttributeRegistry,
+) -> Vec<CarbideCoreError> {
+    let mut checker = AttributeChecker::new(registry);
+    for statement in program {
+        checker.visit_statement(statement);
+    }
+    checker.errors
+}
+
+impl<'r> AttributeChecker<'r> {
+    #[must_use]
+    pub fn new(registry: &'r AttributeRegistry) -> Self {
+        Self {
+            registry,
+            errors: Vec::new(),
+        }
+    }
+
+    fn check(&mut self, attributes: &[Attribute], target: AttributeTarget) {
+        for (i, attribute) in attributes.iter().enumerate() {
+            if let Some(first) = find_attribute(&attributes[..i], &attribute.name) {
+                self.errors.push(CarbideCoreError::DuplicateAttribute {
+                    name: attribute.name.clone(),
+                    span: attribute.span.clone(),
+                    first: first.span.clone(),
+                });
+                continue;
+            }
+
+            let Some(spec) = self.registry.get(&attribute.name) else {
+                self.errors.push(CarbideCoreError::UnknownAttribute {
+                    name: attribute.name.clone(),
+                    span: attribute.span.clone(),
+                });
+                continue;
+            };
+
+            if !spec.targets.contains(&target) {
+                self.errors.push(CarbideCoreError::MisplacedAttribute {
+                    name: attribute.name.clone(),
+                    target,
+                    span: attribute.span.clone(),
+                });
+            } else if !spec.accepts(&attribute.arguments) {
+                self.errors
+                    .push(CarbideCoreError::InvalidAttributeArguments {
+                        name: attribute.name.clone(),
+                        expected: spec.describe_arguments(),
+                        span: attribute.span.clone(),
+                    });
+            }
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for AttributeChecker<'_> {
+    fn visit_statement(&mut self, statement: &'ast Statement) {
+        if let Some(target) = AttributeTarget::of(statement) {
+            self.check(attributes_of(statement), target);
+        }
+        walk_statement(self, statement);
+    }
+}
@@ -0,0 +1,804 @@
+use ariadne::{Color, Label, Report, ReportKind};
+use carbide_errors::{
+    codes::{
+        E2001, E2002, E2003, E2010, E2011, E2012, E2013, E2020, E2021, E2022, E2030, E2031, E2032,
+        E2033, E2040, E2041, E2042, E2043, E2044, E2045, E2046, E2050, E2051, E2052, E2053, E2054,
+        E2055, E2056, E2057, E2058, E2100, ErrCode,
+    },
+    error::CarbideError,
+};
+use carbide_lexer::errors::ErrorSpan;
+use carbide_lexer::tokens::Span;
+use thiserror::Error;
+
+use crate::attributes::AttributeTarget;
+
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum CarbideCoreError {
+    #[error("Non-exhaustive match, {} not covered", format_patterns(.missing))]
+    NonExhaustiveMatch { missing: Vec<String>, span: Span },
+
+    #[error("Unreachable match arm")]
+    UnreachablePattern(Span),
+
+    #[error("Refutable pattern, {} not covered", format_patterns(.missing))]
+    RefutablePattern { missing: Vec<String>, span: Span },
+
+    #[error("Unresolved import `{path}`")]
+    UnresolvedImport { path: String, span: Span },
+
+    #[error("Import cycle: {}", .cycle.join(" -> "))]
+    ImportCycle { cycle: Vec<String>, span: Span },
+
+    #[error("Cannot read module `{path}`: {reason}")]
+    UnreadableImport {
+        path: String,
+        reason: String,
+        span: Span,
+    },
+
+    #[error("`{item}` is private to module `{module}`")]
+    PrivateImport {
+        item: String,
+        module: String,
+        span: Span,
+    },
+
+    #[error("Cannot assign to immutable binding `{name}`")]
+    AssignToImmutable {
+        name: String,
+        span: Span,
+        /// Span of the binding's declaration
+        declaration: Span,
+    },
+
+    #[error("Cannot assign to constant `{name}`")]
+    AssignToConstant {
+        name: String,
+        span: Span,
+        /// Span of the constant's declaration
+        declaration: Span,
+    },
+
+    #[error("Initializer of constant `{name}` is not a constant expression")]
+    NonConstantInitializer { name: String, span: Span },
+
+    #[error("Unknown attribute `#[{name}]`")]
+    UnknownAttribute { name: String, span: Span },
+
+    #[error("`#[{name}]` can't be placed on a {target}")]
+    MisplacedAttribute {
+        name: String,
+        target: AttributeTarget,
+        span: Span,
+    },
+
+    #[error("`#[{name}]` takes {expected}")]
+    InvalidAttributeArguments {
+        name: String,
+        /// Description of the accepted arguments
+        expected: String,
+        span: Span,
+    },
+
+    #[error("Duplicate attribute `#[{name}]`")]
+    DuplicateAttribute {
+        name: String,
+        span: Span,
+        /// Span of the first use of the attribute
+        first: Span,
+    },
+
+    #[error("Duplicate type parameter `{name}`")]
+    DuplicateTypeParameter {
+        name: String,
+        span: Span,
+        /// Span of the first type parameter with the name
+        first: Span,
+    },
+
+    #[error("`{name}` takes {expected} type arguments, but {found} were given")]
+    WrongTypeArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+
+    #[error("Argument to `{function}` should be `{expected}`, but found `{found}`")]
+    MismatchedArgumentType {
+        function: String,
+        expected: String,
+        found: String,
+        span: Span,
+    },
+
+    #[error("Generic function `{function}` is used without type arguments")]
+    MissingTypeArguments { function: String, span: Span },
+
+    #[error("Too many instances of generic functions, starting from `{function}`")]
+    TooManyInstances { function: String, span: Span },
+
+    #[error("Unknown trait `{name}` in bound")]
+    UnknownTraitBound {
+        name: String,
+        /// Span of the bounded type parameter
+        span: Span,
+    },
+
+    #[error("`{ty}` doesn't implement `{trait_name}`")]
+    UnsatisfiedBound {
+        /// Name of the generic function whose bound isn't satisfied
+        function: String,
+        trait_name: String,
+        ty: String,
+        span: Span,
+    },
+
+    #[error(
+        "Impl of `{trait_name}` for `{target}` is missing {}",
+        format_patterns(.missing)
+    )]
+    MissingTraitMethod {
+        trait_name: String,
+        target: String,
+        missing: Vec<String>,
+        /// Span of the impl header
+        span: Span,
+    },
+
+    #[error("`{name}` is not a method of trait `{trait_name}`")]
+    UnknownTraitMethod {
+        trait_name: String,
+        name: String,
+        span: Span,
+    },
+
+    #[error("Method `{name}` should be `{expected}`, but found `{found}`")]
+    MismatchedMethodSignature {
+        name: String,
+        expected: String,
+        found: String,
+        span: Span,
+        /// Span of the method in the trait
+        declaration: Span,
+    },
+
+    #[error("Duplicate method `{name}`")]
+    DuplicateMethod {
+        name: String,
+        span: Span,
+        /// Span of the first method with the name
+        first: Span,
+    },
+
+    #[error("Duplicate impl of `{trait_name}` for `{target}`")]
+    DuplicateImpl {
+        trait_name: String,
+        target: String,
+        span: Span,
+        /// Span of the first impl
+        first: Span,
+    },
+
+    #[error("No method `{name}` on `{target}`")]
+    UnknownMethod {
+        /// Name of the type the method was looked up on
+        target: String,
+        name: String,
+        span: Span,
+    },
+
+    #[error(
+        "Method `{name}` on `{target}` is ambiguous, it's in {}",
+        format_patterns(.traits)
+    )]
+    AmbiguousMethod {
+        target: String,
+        name: String,
+        /// Names of the traits with a method called `name`
+        traits: Vec<String>,
+        span: Span,
+    },
+
+    #[error("`{target}::{name}` doesn't take `self`, so it can't be called on a value")]
+    NotAMethod {
+        target: String,
+        name: String,
+        span: Span,
+    },
+
+    #[error("`{name}` takes {expected} arguments, but {found} were given")]
+    WrongMethodArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        span: Span,
+    },
+
+    #[error("Cast `{0}` as `{1}` failed!")]
+    CastFailed(String, String),
+}
+
+/// Format a list of patterns like `` `a`, `b` and `c` ``
+fn format_patterns(patterns: &[String]) -> String {
+    let quoted = patterns
+        .iter()
+        .map(|p| format!("`{p}`"))
+        .collect::<Vec<_>>();
+
+    match quoted.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => quoted.join(""),
+    }
+}
+
+impl CarbideCoreError {
+    /// Convert a [`Span`] into an [`ErrorSpan`] in `file`
+    ///
+    /// # Errors
+    /// Returns `Err` if the span doesn't fit in a `usize`
+    fn error_span(file: &str, span: &Span) -> Result<ErrorSpan, Self> {
+        let cast = |v: u64| {
+            usize::try_from(v)
+                .map_err(|_| CarbideCoreError::CastFailed(v.to_string(), "usize".to_string()))
+        };
+
+        Ok(ErrorSpan::new(file, cast(span.start)?, cast(span.end)?))
+    }
+}
+
+impl CarbideError for CarbideCoreError {
+    type Span = ErrorSpan;
+
+    fn code(&self) -> ErrCode {
+        match self {
+            Self::NonExhaustiveMatch { .. } => E2001,
+            Self::UnreachablePattern(_) => E2002,
+            Self::RefutablePattern { .. } => E2003,
+            Self::UnresolvedImport { .. } => E2010,
+            Self::ImportCycle { .. } => E2011,
+            Self::PrivateImport { .. } => E2012,
+            Self::UnreadableImport { .. } => E2013,
+            Self::AssignToImmutable { .. } => E2020,
+            Self::AssignToConstant { .. } => E2021,
+            Self::NonConstantInitializer { .. } => E2022,
+            Self::UnknownAttribute { .. } => E2030,
+            Self::MisplacedAttribute { .. } => E2031,
+            Self::InvalidAttributeArguments { .. } => E2032,
+            Self::DuplicateAttribute { .. } => E2033,
+            Self::DuplicateTypeParameter { .. } => E2040,
+            Self::WrongTypeArgumentCount { .. } => E2041,
+            Self::MismatchedArgumentType { .. } => E2042,
+            Self::MissingTypeArguments { .. } => E2043,
+            Self::TooManyInstances { .. } => E2044,
+            Self::UnknownTraitBound { .. } => E2045,
+            Self::UnsatisfiedBound { .. } => E2046,
+            Self::MissingTraitMethod { .. } => E2050,
+            Self::UnknownTraitMethod { .. } => E2051,
+            Self::MismatchedMethodSignature { .. } => E2052,
+            Self::DuplicateMethod { .. } => E2053,
+            Self::DuplicateImpl { .. } => E2054,
+            Self::UnknownMethod { .. } => E2055,
+            Self::AmbiguousMethod { .. } => E2056,
+            Self::NotAMethod { .. } => E2057,
+            Self::WrongMethodArgumentCount { .. } => E2058,
+            Self::CastFailed(_, _) => E2100,
+        }
+    }
+
+    fn help(&self) -> Option<&'static str> {
+        match self {
+            Self::NonExhaustiveMatch { .. } => {
+                Some("Add arms for the missing patterns, or a wildcard `_` arm.")
+            }
+            Self::UnreachablePattern(_) => {
+                Some("Earlier arms already match every value this arm could.")
+            }
+            Self::RefutablePattern { .. } => Some(
+                "`let` and parameter patterns must match every value, use a `match` to handle the others.",
+            ),
+            Self::UnresolvedImport { .. } => Some(
+                "Import paths are resolved relative to the root, like `util.strings` to `util/strings.cb`.",
+            ),
+            Self::ImportCycle { .. } => {
+                Some("Move the shared items into a separate module that both can import.")
+            }
+            Self::PrivateImport { .. } => Some("Mark the item with `pub` to export it."),
+            Self::UnreadableImport { .. } => {
+                Some("The module's file exists but couldn't be read, check its permissions.")
+            }
+            Self::AssignToImmutable { .. } => {
+                Some("Declare the binding with `let mut` to allow assigning to it.")
+            }
+            Self::AssignToConstant { .. } => {
+                Some("Constants can't change, use a `let mut` binding instead.")
+            }
+            Self::NonConstantInitializer { .. } => Some(
+                "Constants can only be initialized with literals, operators and other constants.",
+            ),
+            Self::UnknownAttribute { .. } => {
+                Some("Check the spelling, only attributes known to the compiler can be used.")
+            }
+            Self::MisplacedAttribute { .. } => {
+                Some("Remove the attribute, or move it to a declaration it applies to.")
+            }
+            Self::InvalidAttributeArguments { .. } => {
+                Some("Attribute arguments are literals, like `#[deprecated(\"use g\")]`.")
+            }
+            Self::DuplicateAttribute { .. } => Some("Remove the repeated attribute."),
+            Self::DuplicateTypeParameter { .. } => Some("Rename one of the type parameters."),
+            Self::WrongTypeArgumentCount { .. } => {
+                Some("Give one type argument for each type parameter of the function.")
+            }
+            Self::MismatchedArgumentType { .. } => Some(
+                "Parameter types are instantiated with the type arguments, check that they match.",
+            ),
+            Self::MissingTypeArguments { .. } => {
+                Some("Give the type arguments explicitly, like `first::<int>(xs)`.")
+            }
+            Self::TooManyInstances { .. } => Some(
+                "A generic function can't instantiate itself with ever larger types, like `f::<[T]>` inside `f<T>`.",
+            ),
+            Self::UnknownTraitBound { .. } => {
+                Some("Bounds must name a trait, check the spelling or import the trait.")
+            }
+            Self::UnsatisfiedBound { .. } => {
+                Some("Add an impl of the trait for the type, like `impl Show for Point { ... }`.")
+            }
+            Self::MissingTraitMethod { .. } => {
+                Some("Add the missing methods, only methods with a default body can be left out.")
+            }
+            Self::UnknownTraitMethod { .. } => {
+                Some("Move the method to an inherent impl, like `impl Circle { ... }`.")
+            }
+            Self::MismatchedMethodSignature { .. } => {
+                Some("Methods in an impl must take and return the same types as in the trait.")
+            }
+            Self::DuplicateMethod { .. } => Some("Rename or remove one of the methods."),
+            Self::DuplicateImpl { .. } => {
+                Some("A type can implement each trait once, merge the impls.")
+            }
+            Self::UnknownMethod { .. } => Some(
+                "Add the method to an impl of the type, or to a trait the type parameter is bound by.",
+            ),
+            Self::AmbiguousMethod { .. } => {
+                Some("Call it through the trait instead, like `Shape::area(c)`.")
+            }
+            Self::NotAMethod { .. } => Some(
+                "Call it on the type, like `Circle::new(1.0)`, or make `self` its first parameter.",
+            ),
+            Self::WrongMethodArgumentCount { .. } => {
+                Some("Give one argument for each parameter after `self`.")
+            }
+            Self::CastFailed(_, _) => {
+                Some("This is a compiler bug, please open an issue on github")
+            }
+        }
+    }
+
+    fn message(&self) -> String {
+        format!("{self}")
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn report(&'_ self, file: &str, _src: &str) -> Result<Report<'_, Self::Span>, Box<Self>>
+    where
+        Self: Sized,
+    {
+        let mut report = match self {
+            Self::NonExhaustiveMatch { missing, span } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Non-exhaustive match")
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!(
+                                "Patterns {} not covered",
+                                format_patterns(missing)
+                            ))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnreachablePattern(span) => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Warning, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Unreachable match arm")
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This pattern can never match")
+                            .with_color(Color::BrightYellow),
+                    )
+            }
+
+            Self::RefutablePattern { missing, span } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Refutable pattern in a binding")
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!(
+                                "Patterns {} not covered",
+                                format_patterns(missing)
+                            ))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnresolvedImport { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("No module or item found for this import")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::ImportCycle { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This import leads back to the current module")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnreadableImport { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This module's file couldn't be read")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::PrivateImport { item, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("`{item}` is not marked `pub`"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::AssignToImmutable {
+                name,
+                span,
+                declaration,
+            }
+            | Self::AssignToConstant {
+                name,
+                span,
+                declaration,
+            } => {
+                let span = Self::error_span(file, span)?;
+                let declaration = Self::error_span(file, declaration)?;
+                let declared_as = if matches!(self, Self::AssignToConstant { .. }) {
+                    "constant"
+                } else {
+                    "immutable binding"
+                };
+
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("`{name}` is assigned here"))
+                            .with_color(Color::BrightRed),
+                    )
+                    .with_label(
+                        Label::new(declaration)
+                            .with_message(format!("`{name}` is declared as a {declared_as} here"))
+                            .with_color(Color::BrightBlue),
+                    )
+            }
+
+            Self::NonConstantInitializer { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This constant needs a value known at compile time")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnknownAttribute { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This attribute isn't known to the compiler")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MisplacedAttribute { target, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("This attribute doesn't apply to a {target}"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::InvalidAttributeArguments { expected, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("Expected {expected}"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::DuplicateAttribute { name, span, first } => {
+                let span = Self::error_span(file, span)?;
+                let first = Self::error_span(file, first)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("`#[{name}]` is repeated here"))
+                            .with_color(Color::BrightRed),
+                    )
+                    .with_label(
+                        Label::new(first)
+                            .with_message("First used here")
+                            .with_color(Color::BrightBlue),
+                    )
+            }
+
+            Self::DuplicateTypeParameter { name, span, first }
+            | Self::DuplicateMethod { name, span, first } => {
+                let span = Self::error_span(file, span)?;
+                let first = Self::error_span(file, first)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("`{name}` is declared again here"))
+                            .with_color(Color::BrightRed),
+                    )
+                    .with_label(
+                        Label::new(first)
+                            .with_message("First declared here")
+                            .with_color(Color::BrightBlue),
+                    )
+            }
+
+            Self::WrongTypeArgumentCount { expected, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("Expected {expected} type arguments"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MismatchedArgumentType { expected, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("This instance takes `{expected}`"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MissingTypeArguments { function, span } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("`{function}` needs type arguments here"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::TooManyInstances { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("Instantiated here")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnknownTraitBound { name, span } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("`{name}` isn't a declared trait"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnsatisfiedBound { function, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("Required by a bound on `{function}`"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MissingTraitMethod { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This impl doesn't provide every required method")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::UnknownTraitMethod {
+                trait_name, span, ..
+            } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("Not declared in `{trait_name}`"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MismatchedMethodSignature {
+                expected,
+                span,
+                declaration,
+                ..
+            } => {
+                let span = Self::error_span(file, span)?;
+                let declaration = Self::error_span(file, declaration)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("This signature differs from the trait")
+                            .with_color(Color::BrightRed),
+                    )
+                    .with_label(
+                        Label::new(declaration)
+                            .with_message(format!("Declared as `{expected}` here"))
+                            .with_color(Color::BrightBlue),
+                    )
+            }
+
+            Self::DuplicateImpl { span, first, .. } => {
+                let span = Self::error_span(file, span)?;
+                let first = Self::error_span(file, first)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("Implemented again here")
+                            .with_color(Color::BrightRed),
+                    )
+                    .with_label(
+                        Label::new(first)
+                            .with_message("First implemented here")
+                            .with_color(Color::BrightBlue),
+                    )
+            }
+
+            Self::UnknownMethod { target, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("Not found in any impl of `{target}`"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::AmbiguousMethod { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("More than one trait has a method with this name")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::NotAMethod { span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("Called on a value here")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::WrongMethodArgumentCount { expected, span, .. } => {
+                let span = Self::error_span(file, span)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message(format!("Expected {expected} arguments"))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::CastFailed(_, _) => {
+                let span = ErrorSpan::new(file, 0, 1);
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message(self.message())
+                    .with_label(
+                        Label::new(span)
+                            .with_message("Error occurred here")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+        };
+
+        if let Some(help) = self.help() {
+            report = report.with_help(help);
+        }
+
+        Ok(report.finish())
+    }
+}
@@ -0,0 +1,688 @@
+use std::collections::HashMap;
+
+use carbide_lexer::tokens::Span;
+use carbide_parser::nodes::{
+    EnumVariant, Expression, LiteralValue, MatchArm, Method, Parameter, Pattern, Statement,
+    StructField, VariantPayload,
+};
+use carbide_parser::visit::{Visitor, walk_expression, walk_method, walk_statement};
+
+use crate::errors::CarbideCoreError;
+
+/// Upper bound on the number of missing patterns reported for a single `match`
+const MAX_WITNESSES: usize = 8;
+
+/// A value constructor that patterns can test for
+#[derive(Debug, Clone, PartialEq)]
+enum Constructor {
+    /// The `index`th variant of `enum_name`
+    Variant {
+        enum_name: String,
+        index: usize,
+    },
+    Bool(bool),
+    /// A tuple with the given number of elements
+    Tuple(usize),
+    /// An array with exactly the given number of elements
+    Array(usize),
+    /// An array with at least `prefix` and `suffix` elements, whose sub-patterns are the
+    /// first `prefix` and last `suffix` elements
+    LongArray {
+        prefix: usize,
+        suffix: usize,
+    },
+    Struct(String),
+    /// A literal from a type with too many values to list, like `int` or `string`
+    Literal(String),
+}
+
+/// A [`Pattern`] reduced to constructors and wildcards
+#[derive(Debug, Clone)]
+enum Pat {
+    Wild,
+    Ctor(Constructor, Vec<Pat>),
+    /// An array pattern with a `..`, which matches arrays with at least as many elements as
+    /// `prefix` and `suffix` together
+    Slice {
+        prefix: Vec<Pat>,
+        suffix: Vec<Pat>,
+    },
+}
+
+/// Checks that every `match` covers all possible values, that each arm can be reached, and
+/// that the patterns of `let` declarations and parameters can't fail to match
+///
+/// This is an implementation of the usefulness algorithm from
+/// "Warnings for pattern matching" (Maranget, 2007)
+pub struct MatchChecker<'a> {
+    enums: HashMap<&'a str, &'a [EnumVariant]>,
+    structs: HashMap<&'a str, &'a [StructField]>,
+    /// Whether array patterns with a `..` match arrays of any length. A `let` or parameter
+    /// with a `..` checks the length of its array when it runs instead, while array
+    /// patterns without one can fail to match since array types have no known length
+    unchecked_rest: bool,
+    errors: Vec<CarbideCoreError>,
+}
+
+/// Check every `match` and destructuring pattern in `program`
+#[must_use]
+pub fn check_matches(program: &[Statement]) -> Vec<CarbideCoreError> {
+    let mut checker = MatchChecker::new(program);
+    for statement in program {
+        checker.visit_statement(statement);
+    }
+    checker.errors
+}
+
+impl<'a> MatchChecker<'a> {
+    /// Create a [`MatchChecker`] aware of every struct and enum declared in `program`
+    #[must_use]
+    pub fn new(program: &'a [Statement]) -> Self {
+        let mut checker = Self {
+            enums: HashMap::new(),
+            structs: HashMap::new(),
+            unchecked_rest: false,
+            errors: Vec::new(),
+        };
+        checker.collect_declarations(program);
+        checker
+    }
+
+    /// Record all struct and enum declarations, including ones nested in bodies
+    fn collect_declarations(&mut self, statements: &'a [Statement]) {
+        for statement in statements {
+            match statement {
+                Statement::StructDeclaration { name, fields, .. } => {
+                    self.structs.insert(name, fields);
+                }
+                Statement::EnumDeclaration { name, variants, .. } => {
+                    self.enums.insert(name, variants);
+                }
+                Statement::FunctionDeclaration { body, .. } => {
+                    self.collect_declarations(&body.statements);
+                }
+                Statement::While { body, .. }
+                | Statement::For { body, .. }
+                | Statement::Block(body) => self.collect_declarations(body),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for MatchChecker<'_> {
+    fn visit_statement(&mut self, statement: &'ast Statement) {
+        match statement {
+            Statement::LetDeclaration { pattern, span, .. } => {
+                self.check_irrefutable(pattern, span);
+            }
+            Statement::FunctionDeclaration { parameters, .. } => {
+                self.check_parameters(parameters);
+            }
+            _ => {}
+        }
+        walk_statement(self, statement);
+    }
+
+    fn visit_method(&mut self, method: &'ast Method) {
+        self.check_parameters(&method.parameters);
+        walk_method(self, method);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression) {
+        walk_expression(self, expression);
+
+        match expression {
+            Expression::Match { arms, span, .. } => self.check_match(arms, span),
+            Expression::Lambda { parameters, .. } => self.check_parameters(parameters),
+            _ => {}
+        }
+    }
+}
+
+impl MatchChecker<'_> {
+    /// Report unreachable `arms`, and values that none of them match
+    fn check_match(&mut self, arms: &[MatchArm], span: &Span) {
+        // Matches that refer to unknown types can't be checked
+        let Some(patterns) = arms
+            .iter()
+            .map(|arm| self.lower(&arm.pattern))
+            .collect::<Option<Vec<_>>>()
+        else {
+            return;
+        };
+
+        let mut rows: Vec<Vec<Pat>> = Vec::new();
+        for (arm, pattern) in arms.iter().zip(patterns) {
+            let row = vec![pattern];
+
+            if !self.is_useful(&rows, &row) {
+                self.errors
+                    .push(CarbideCoreError::UnreachablePattern(arm.span.clone()));
+            }
+
+            // A guarded arm might not match, so it can't cover anything
+            if arm.guard.is_none() {
+                rows.push(row);
+            }
+        }
+
+        let missing = self.witnesses(&rows, 1);
+        if !missing.is_empty() {
+            self.errors.push(CarbideCoreError::NonExhaustiveMatch {
+                missing: missing.iter().map(|w| self.render(&w[0])).collect(),
+                span: span.clone(),
+            });
+        }
+    }
+
+    fn check_parameters(&mut self, parameters: &[Parameter]) {
+        for parameter in parameters {
+            self.check_irrefutable(&parameter.pattern, &parameter.span);
+        }
+    }
+
+    /// Report values that `pattern` doesn't match, since a `let` or parameter has nowhere
+    /// else to go
+    fn check_irrefutable(&mut self, pattern: &Pattern, span: &Span) {
+        self.unchecked_rest = true;
+        let pattern = self.lower(pattern);
+        self.unchecked_rest = false;
+
+        // Patterns that refer to unknown types can't be checked
+        let Some(pattern) = pattern else {
+            return;
+        };
+
+        let missing = self.witnesses(&[vec![pattern]], 1);
+        if !missing.is_empty() {
+            self.errors.push(CarbideCoreError::RefutablePattern {
+                missing: missing.iter().map(|w| self.render(&w[0])).collect(),
+                span: span.clone(),
+            });
+        }
+    }
+}
+
+impl MatchChecker<'_> {
+    /// Lower a [`Pattern`] to a [`Pat`], returning `None` if it refers to unknown types
+    fn lower(&self, pattern: &Pattern) -> Option<Pat> {
+        Some(match pattern {
+            Pattern::Wildcard | Pattern::Binding { .. } => Pat::Wild,
+            Pattern::Literal(LiteralValue::Bool(value)) => {
+                Pat::Ctor(Constructor::Bool(*value), Vec::new())
+            }
+            Pattern::Literal(literal) => {
+                let key = match literal {
+                    LiteralValue::Int(v) => v.to_string(),
+                    LiteralValue::Float(v) => format!("{v:?}"),
+                    LiteralValue::String(v) => format!("{v:?}"),
+                    LiteralValue::Bool(v) => v.to_string(),
+                };
+                Pat::Ctor(Constructor::Literal(key), Vec::new())
+            }
+            Pattern::Tuple(elements) => Pat::Ctor(
+                Constructor::Tuple(elements.len()),
+                elements
+                    .iter()
+                    .map(|p| self.lower(p))
+                    .collect::<Option<_>>()?,
+            ),
+            Pattern::Array(elements) => {
+                let rest = elements
+                    .iter()
+                    .position(|p| matches!(p, Pattern::Rest { .. }));
+                let (prefix, suffix) = match rest {
+                    Some(rest) => (&elements[..rest], &elements[rest + 1..]),
+                    None => (elements.as_slice(), [].as_slice()),
+                };
+                let prefix = prefix
+                    .iter()
+                    .map(|p| self.lower(p))
+                    .collect::<Option<Vec<_>>>()?;
+                let suffix = suffix
+                    .iter()
+                    .map(|p| self.lower(p))
+                    .collect::<Option<Vec<_>>>()?;
+
+                if rest.is_none() {
+                    Pat::Ctor(Constructor::Array(prefix.len()), prefix)
+                } else if self.unchecked_rest {
+                    let ctor = Constructor::LongArray {
+                        prefix: prefix.len(),
+                        suffix: suffix.len(),
+                    };
+                    Pat::Ctor(ctor, [prefix, suffix].concat())
+                } else if prefix.is_empty() && suffix.is_empty() {
+                    Pat::Wild
+                } else {
+                    Pat::Slice { prefix, suffix }
+                }
+            }
+            // Only valid inside an array pattern
+            Pattern::Rest { .. } => return None,
+            Pattern::Path(name) => {
+                let (ctor, payload) = self.variant(name)?;
+                if *payload != VariantPayload::Unit {
+                    return None;
+                }
+                Pat::Ctor(ctor, Vec::new())
+            }
+            Pattern::TupleStruct { name, elements } => {
+                let (ctor, payload) = self.variant(name)?;
+                match payload {
+                    VariantPayload::Tuple(types) if types.len() == elements.len() => Pat::Ctor(
+                        ctor,
+                        elements
+                            .iter()
+                            .map(|p| self.lower(p))
+                            .collect::<Option<_>>()?,
+                    ),
+                    _ => return None,
+                }
+            }
+            Pattern::Struct { name, fields, .. } => {
+                let (ctor, declared) = if name.contains("::") {
+                    match self.variant(name)? {
+                        (ctor, VariantPayload::Struct(declared)) => (ctor, declared.as_slice()),
+                        _ => return None,
+                    }
+                } else {
+                    let declared = self.structs.get(name.as_str())?;
+                    (Constructor::Struct(name.clone()), *declared)
+                };
+
+                if fields
+                    .iter()
+                    .any(|f| !declared.iter().any(|d| d.name == f.name))
+                {
+                    return None;
+                }
+
+                // Fields are matched in declaration order, with omitted fields matching anything
+                let args = declared
+                    .iter()
+                    .map(|d| {
+                        fields
+                            .iter()
+                            .find(|f| f.name == d.name)
+                            .map_or(Some(Pat::Wild), |f| self.lower(&f.pattern))
+                    })
+                    .collect::<Option<_>>()?;
+
+                Pat::Ctor(ctor, args)
+            }
+        })
+    }
+
+    /// Look up an enum variant by its qualified name, like `Shape::Circle`
+    fn variant(&self, name: &str) -> Option<(Constructor, &VariantPayload)> {
+        let (enum_name, variant) = name.split_once("::")?;
+        let variants = self.enums.get(enum_name)?;
+        let index = variants.iter().position(|v| v.name == variant)?;
+
+        Some((
+            Constructor::Variant {
+                enum_name: enum_name.to_string(),
+                index,
+            },
+            &variants[index].payload,
+        ))
+    }
+
+    /// Get every constructor of the type `ctor` belongs to, or `None` if there are too many
+    fn all_constructors(&self, ctor: &Constructor) -> Option<Vec<Constructor>> {
+        match ctor {
+            Constructor::Variant { enum_name, .. } => {
+                let variants = self.enums.get(enum_name.as_str())?;
+                Some(
+                    (0..variants.len())
+                        .map(|index| Constructor::Variant {
+                            enum_name: enum_name.clone(),
+                            index,
+                        })
+                        .collect(),
+                )
+            }
+            Constructor::Bool(_) => Some(vec![Constructor::Bool(false), Constructor::Bool(true)]),
+            // Arrays of any length only appear when their length isn't checked
+            Constructor::Tuple(_) | Constructor::Struct(_) | Constructor::LongArray { .. } => {
+                Some(vec![ctor.clone()])
+            }
+            // Array lengths are split by `array_constructors` instead
+            Constructor::Array(_) | Constructor::Literal(_) => None,
+        }
+    }
+
+    /// Get the number of sub-patterns `ctor` takes
+    fn arity(&self, ctor: &Constructor) -> usize {
+        match ctor {
+            Constructor::Variant { enum_name, index } => self
+                .enums
+                .get(enum_name.as_str())
+                .and_then(|variants| variants.get(*index))
+                .map_or(0, |variant| match &variant.payload {
+                    VariantPayload::Unit => 0,
+                    VariantPayload::Tuple(types) => types.len(),
+                    VariantPayload::Struct(fields) => fields.len(),
+                }),
+            Constructor::Tuple(arity) | Constructor::Array(arity) => *arity,
+            Constructor::LongArray { prefix, suffix } => prefix + suffix,
+            Constructor::Struct(name) => self.structs.get(name.as_str()).map_or(0, |f| f.len()),
+            Constructor::Bool(_) | Constructor::Literal(_) => 0,
+        }
+    }
+
+    /// Keep the rows whose first pattern can match `ctor`, replacing it with its sub-patterns
+    fn specialize(&self, rows: &[Vec<Pat>], ctor: &Constructor) -> Vec<Vec<Pat>> {
+        rows.iter()
+            .filter_map(|row| {
+                let (head, tail) = row.split_first()?;
+                let mut specialized = match head {
+                    Pat::Ctor(c, args) if c == ctor => args.clone(),
+                    Pat::Ctor(..) => return None,
+                    Pat::Wild => vec![Pat::Wild; self.arity(ctor)],
+                    // The `..` matches the elements between `prefix` and `suffix`
+                    Pat::Slice { prefix, suffix } => {
+                        let (before, after) = match ctor {
+                            Constructor::Array(len) => {
+                                (len.checked_sub(prefix.len() + suffix.len())?, 0)
+                            }
+                            Constructor::LongArray {
+                                prefix: long_prefix,
+                                suffix: long_suffix,
+                            } => (
+                                long_prefix.checked_sub(prefix.len())?,
+                                long_suffix.checked_sub(suffix.len())?,
+                            ),
+                            _ => return None,
+                        };
+                        let mut elements = prefix.clone();
+                        elements.extend(vec![Pat::Wild; before + after]);
+                        elements.extend_from_slice(suffix);
+                        elements
+                    }
+                };
+                specialized.extend_from_slice(tail);
+                Some(specialized)
+            })
+            .collect()
+    }
+
+    /// Keep the rows whose first pattern is a wildcard, removing it
+    fn default_rows(rows: &[Vec<Pat>]) -> Vec<Vec<Pat>> {
+        rows.iter()
+            .filter_map(|row| match row.split_first()? {
+                (Pat::Wild, tail) => Some(tail.to_vec()),
+                (Pat::Ctor(..) | Pat::Slice { .. }, _) => None,
+            })
+            .collect()
+    }
+
+    /// Get the constructors at the head of `rows`, and whether they cover their whole type
+    fn head_constructors(&self, rows: &[Vec<Pat>]) -> (Vec<Constructor>, Option<Vec<Constructor>>) {
+        let patterns = rows
+            .iter()
+            .filter_map(|row| row.first())
+            .collect::<Vec<_>>();
+        if let Some(all) = Self::array_constructors(&patterns) {
+            let heads = all
+                .iter()
+                .filter(|c| patterns.iter().any(|pattern| Self::covers(pattern, c)))
+                .cloned()
+                .collect();
+            return (heads, Some(all));
+        }
+
+        let heads = patterns
+            .iter()
+            .filter_map(|pattern| match pattern {
+                Pat::Ctor(c, _) => Some(c.clone()),
+                Pat::Wild | Pat::Slice { .. } => None,
+            })
+            .collect::<Vec<_>>();
+
+        let all = heads.first().and_then(|c| self.all_constructors(c));
+        (heads, all)
+    }
+
+    /// Get the array constructors needed to tell apart the array patterns in `patterns`:
+    /// every length that some pattern without a `..` could match, and one for all the
+    /// longer arrays
+    ///
+    /// Returns `None` if none of `patterns` are array patterns
+    fn array_constructors(patterns: &[&Pat]) -> Option<Vec<Constructor>> {
+        let mut found = false;
+        // Length of the longest pattern without a `..`
+        let mut longest = None;
+        let (mut prefix, mut suffix) = (0, 0);
+        for pattern in patterns {
+            match pattern {
+                Pat::Ctor(Constructor::Array(len), _) => {
+                    found = true;
+                    longest = longest.max(Some(*len));
+                }
+                Pat::Slice {
+                    prefix: p,
+                    suffix: s,
+                } => {
+                    found = true;
+                    prefix = prefix.max(p.len());
+                    suffix = suffix.max(s.len());
+                }
+                _ => {}
+            }
+        }
+        if !found {
+            return None;
+        }
+
+        // Arrays long enough for the `..` constructor can't match any pattern without one
+        let shortest_long = longest.map_or(0, |len| len + 1);
+        if prefix + suffix < shortest_long {
+            prefix = shortest_long - suffix;
+        }
+
+        Some(
+            (0..prefix + suffix)
+                .map(Constructor::Array)
+                .chain([Constructor::LongArray { prefix, suffix }])
+                .collect(),
+        )
+    }
+
+    /// Check whether `pattern` tests for `ctor` and matches every value it builds, which
+    /// wildcards don't count as
+    fn covers(pattern: &Pat, ctor: &Constructor) -> bool {
+        match (pattern, ctor) {
+            (Pat::Ctor(c, _), ctor) => c == ctor,
+            (Pat::Slice { prefix, suffix }, Constructor::Array(len)) => {
+                *len >= prefix.len() + suffix.len()
+            }
+            (
+                Pat::Slice { prefix, suffix },
+                Constructor::LongArray {
+                    prefix: long_prefix,
+                    suffix: long_suffix,
+                },
+            ) => *long_prefix >= prefix.len() && *long_suffix >= suffix.len(),
+            (Pat::Wild | Pat::Slice { .. }, _) => false,
+        }
+    }
+
+    /// Check whether some value matched by `row` isn't matched by any of `rows`
+    fn is_useful(&self, rows: &[Vec<Pat>], row: &[Pat]) -> bool {
+        let Some((head, tail)) = row.split_first() else {
+            return rows.is_empty();
+        };
+
+        match head {
+            Pat::Ctor(ctor, args) => {
+                let mut specialized = args.clone();
+                specialized.extend_from_slice(tail);
+                self.is_useful(&self.specialize(rows, ctor), &specialized)
+            }
+            Pat::Wild => {
+                let (heads, all) = self.head_constructors(rows);
+
+                match all {
+                    Some(all) if all.iter().all(|c| heads.contains(c)) => all.iter().any(|ctor| {
+                        let mut specialized = vec![Pat::Wild; self.arity(ctor)];
+                        specialized.extend_from_slice(tail);
+                        self.is_useful(&self.specialize(rows, ctor), &specialized)
+                    }),
+                    _ => self.is_useful(&Self::default_rows(rows), tail),
+                }
+            }
+            Pat::Slice { .. } => {
+                let mut patterns = rows
+                    .iter()
+                    .filter_map(|row| row.first())
+                    .collect::<Vec<_>>();
+                patterns.push(head);
+                let all = Self::array_constructors(&patterns).unwrap_or_default();
+
+                all.iter()
+                    .filter(|ctor| Self::covers(head, ctor))
+                    .any(|ctor| {
+                        self.specialize(&[row.to_vec()], ctor)
+                            .first()
+                            .is_some_and(|specialized| {
+                                self.is_useful(&self.specialize(rows, ctor), specialized)
+                            })
+                    })
+            }
+        }
+    }
+
+    /// Find rows of `width` patterns matching values that none of `rows` match
+    fn witnesses(&self, rows: &[Vec<Pat>], width: usize) -> Vec<Vec<Pat>> {
+        if width == 0 {
+            return if rows.is_empty() {
+                vec![Vec::new()]
+            } else {
+                Vec::new()
+            };
+        }
+
+        let (heads, all) = self.head_constructors(rows);
+
+        if let Some(all) = &all
+            && all.iter().all(|c| heads.contains(c))
+        {
+            let mut witnesses = Vec::new();
+            for ctor in all {
+                let arity = self.arity(ctor);
+                for witness in self.witnesses(&self.specialize(rows, ctor), arity + width - 1) {
+                    let (args, tail) = witness.split_at(arity);
+                    let mut row = vec![Pat::Ctor(ctor.clone(), args.to_vec())];
+                    row.extend_from_slice(tail);
+                    witnesses.push(row);
+                }
+                witnesses.truncate(MAX_WITNESSES);
+            }
+            return witnesses;
+        }
+
+        let tails = self.witnesses(&Self::default_rows(rows), width - 1);
+        if tails.is_empty() {
+            return Vec::new();
+        }
+
+        // Name the missing constructors when there are few enough to list
+        let missing_heads = match all {
+            Some(all) if !heads.is_empty() => all
+                .into_iter()
+                .filter(|c| !heads.contains(c))
+                .map(|c| {
+                    let arity = self.arity(&c);
+                    Pat::Ctor(c, vec![Pat::Wild; arity])
+                })
+                .collect(),
+            _ => vec![Pat::Wild],
+        };
+
+        missing_heads
+            .iter()
+            .flat_map(|head| {
+                tails.iter().map(move |tail| {
+                    let mut row = vec![head.clone()];
+                    row.extend_from_slice(tail);
+                    row
+                })
+            })
+            .take(MAX_WITNESSES)
+            .collect()
+    }
+
+    /// Render an array pattern with a `..` between `prefix` and `suffix`
+    fn render_slice(&self, prefix: &[Pat], suffix: &[Pat]) -> String {
+        let elements = prefix
+            .iter()
+            .map(|p| self.render(p))
+            .chain(["..".to_string()])
+            .chain(suffix.iter().map(|p| self.render(p)))
+            .collect::<Vec<_>>();
+        format!("[{}]", elements.join(", "))
+    }
+
+    /// Render a [`Pat`] as Carbide source
+    fn render(&self, pat: &Pat) -> String {
+        let render_all = |args: &[Pat]| {
+            args.iter()
+                .map(|arg| self.render(arg))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let render_fields = |fields: &[StructField], args: &[Pat]| {
+            if args.iter().all(|arg| matches!(arg, Pat::Wild)) {
+                return "{ .. }".to_string();
+            }
+            let fields = fields
+                .iter()
+                .zip(args)
+                .map(|(field, arg)| format!("{}: {}", field.name, self.render(arg)))
+                .collect::<Vec<_>>()
+                .join(", ");
+            format!("{{ {fields} }}")
+        };
+
+        match pat {
+            Pat::Wild => "_".to_string(),
+            Pat::Ctor(Constructor::Bool(value), _) => value.to_string(),
+            Pat::Ctor(Constructor::Literal(key), _) => key.clone(),
+            Pat::Ctor(Constructor::Tuple(_), args) => format!("({})", render_all(args)),
+            Pat::Ctor(Constructor::Array(_), args) => format!("[{}]", render_all(args)),
+            Pat::Ctor(Constructor::LongArray { prefix, .. }, args) => {
+                let (prefix, suffix) = args.split_at((*prefix).min(args.len()));
+                self.render_slice(prefix, suffix)
+            }
+            Pat::Slice { prefix, suffix } => self.render_slice(prefix, suffix),
+            Pat::Ctor(Constructor::Struct(name), args) => {
+                let fields = self.structs.get(name.as_str()).copied().unwrap_or_default();
+                format!("{name} {}", render_fields(fields, args))
+            }
+            Pat::Ctor(Constructor::Variant { enum_name, index }, args) => {
+                let Some(variant) = self
+                    .enums
+                    .get(enum_name.as_str())
+                    .and_then(|variants| variants.get(*index))
+                else {
+                    return "_".to_string();
+                };
+
+                match &variant.payload {
+                    VariantPayload::Unit => format!("{enum_name}::{}", variant.name),
+                    VariantPayload::Tuple(_) => {
+                        format!("{enum_name}::{}({})", variant.name, render_all(args))
+                    }
+                    VariantPayload::Struct(fields) => format!(
+                        "{enum_name}::{} {}",
+                        variant.name,
+                        render_fields(fields, args)
+                    ),
+                }
+            }
+        }
+    }
+}
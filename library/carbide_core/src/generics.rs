@@ -0,0 +1,448 @@
+use std::collections::{HashMap, HashSet};
+
+use carbide_lexer::operators::UnaryOperators;
+use carbide_lexer::tokens::Span;
+use carbide_parser::fold::{Folder, walk_type};
+use carbide_parser::nodes::{
+    Block, Expression, LiteralValue, MatchArm, Method, Parameter, Pattern, Statement, Type,
+    TypeParameter,
+};
+use carbide_parser::print::print_type;
+use carbide_parser::visit::{
+    self, Visitor, walk_block, walk_expression, walk_match_arm, walk_method, walk_statement,
+};
+
+use crate::errors::CarbideCoreError;
+use crate::methods::MethodTable;
+
+/// The parts of a function declaration needed to check its instantiations
+#[derive(Debug, Clone, Copy)]
+pub struct Signature<'a> {
+    pub type_parameters: &'a [TypeParameter],
+    pub parameters: &'a [Parameter],
+}
+
+impl<'a> Signature<'a> {
+    /// Get the signature of `statement`, if it's a function declaration
+    #[must_use]
+    pub fn of(statement: &'a Statement) -> Option<Self> {
+        match statement {
+            Statement::FunctionDeclaration {
+                type_parameters,
+                parameters,
+                ..
+            } => Some(Self {
+                type_parameters,
+                parameters,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Check if the function has type parameters
+    #[must_use]
+    pub fn is_generic(&self) -> bool {
+        !self.type_parameters.is_empty()
+    }
+
+    /// Pair each type parameter name with the matching type in `arguments`
+    #[must_use]
+    pub fn bindings<'t>(&self, arguments: &'t [Type]) -> HashMap<String, &'t Type> {
+        self.type_parameters
+            .iter()
+            .map(|parameter| parameter.name.clone())
+            .zip(arguments)
+            .collect()
+    }
+
+    /// Check that each type in `arguments` implements the traits its type parameter is
+    /// bound by, reporting the instantiation of `function` at `span` for each that doesn't
+    ///
+    /// Only traits declared in the program can be checked, since impls of other traits
+    /// can't be seen
+    #[must_use]
+    pub fn check_bounds(
+        &self,
+        function: &str,
+        arguments: &[Type],
+        table: &MethodTable,
+        span: &Span,
+    ) -> Vec<CarbideCoreError> {
+        self.type_parameters
+            .iter()
+            .zip(arguments)
+            .flat_map(|(parameter, argument)| {
+                parameter.bounds.iter().map(move |bound| (bound, argument))
+            })
+            .filter(|(bound, argument)| {
+                table.trait_methods(bound).is_some() && !table.implements(argument, bound)
+            })
+            .map(|(bound, argument)| CarbideCoreError::UnsatisfiedBound {
+                function: function.to_string(),
+                trait_name: bound.clone(),
+                ty: print_type(argument),
+                span: span.clone(),
+            })
+            .collect()
+    }
+}
+
+/// Replace every type parameter in `ty` with the type it's bound to in `bindings`
+fn substitute(ty: &Type, bindings: &HashMap<String, &Type>) -> Type {
+    Substitution { bindings }.fold_type(ty.clone())
+}
+
+/// Replaces type parameters with the types they're bound to, in every type it folds
+pub struct Substitution<'a, 't> {
+    pub bindings: &'a HashMap<String, &'t Type>,
+}
+
+impl Folder for Substitution<'_, '_> {
+    fn fold_type(&mut self, ty: Type) -> Type {
+        match ty {
+            Type::Named(name) => match self.bindings.get(&name) {
+                Some(bound) => (*bound).clone(),
+                None => Type::Named(name),
+            },
+            ty => walk_type(self, ty),
+        }
+    }
+}
+
+/// Get the type of `expression` if it can be seen without knowing the type of any name,
+/// like `int` for `1` or `[string]` for `["a"]`
+#[must_use]
+pub fn evident_type(expression: &Expression) -> Option<Type> {
+    match expression {
+        Expression::Literal(literal) => Some(Type::named(match literal {
+            LiteralValue::Int(_) => "int",
+            LiteralValue::Float(_) => "float",
+            LiteralValue::String(_) => "string",
+            LiteralValue::Bool(_) => "bool",
+        })),
+        Expression::InterpolatedString { .. } => Some(Type::named("string")),
+        Expression::Grouped(inner) => evident_type(inner),
+        Expression::UnaryOp {
+            operator: UnaryOperators::Not,
+            operand,
+        } => evident_type(operand),
+        Expression::Array(elements) => {
+            let (first, rest) = elements.split_first()?;
+            let element = evident_type(first)?;
+            rest.iter()
+                .all(|e| evident_type(e).as_ref() == Some(&element))
+                .then(|| Type::Array(Box::new(element)))
+        }
+        Expression::Tuple(elements) => elements
+            .iter()
+            .map(evident_type)
+            .collect::<Option<Vec<_>>>()
+            .map(Type::Tuple),
+        _ => None,
+    }
+}
+
+/// Check if a value of type `found` can be passed where `expected` is wanted
+fn accepts(expected: &Type, found: &Type) -> bool {
+    match (expected, found) {
+        (Type::Optional(expected), Type::Optional(found))
+        | (Type::Array(expected), Type::Array(found)) => accepts(expected, found),
+        (Type::Optional(expected), found) => accepts(expected, found),
+        (Type::Tuple(expected), Type::Tuple(found)) => {
+            expected.len() == found.len() && expected.iter().zip(found).all(|(e, f)| accepts(e, f))
+        }
+        (expected, found) => expected == found,
+    }
+}
+
+/// Collects the names of every named type in the types it visits
+struct TypeNames<'a>(Vec<&'a str>);
+
+impl<'a> Visitor<'a> for TypeNames<'a> {
+    fn visit_type(&mut self, ty: &'a Type) {
+        if let Type::Named(name) | Type::Generic { name, .. } = ty {
+            self.0.push(name);
+        }
+        visit::walk_type(self, ty);
+    }
+}
+
+/// Checks the type parameters of generic functions, and that every use of one gives type
+/// arguments that fit its signature
+///
+/// Only arguments whose type is [evident][`evident_type`] can be compared to the
+/// instantiated parameter types, since names aren't resolved to types yet
+pub struct GenericChecker<'a> {
+    functions: HashMap<&'a str, Signature<'a>>,
+    table: MethodTable<'a>,
+    /// Items imported from other modules, whose declarations can't be seen
+    imported: HashSet<&'a str>,
+    /// Names bound in each scope, which hide functions with the same name
+    scopes: Vec<HashSet<&'a str>>,
+    /// Type parameters of each enclosing generic function
+    type_parameters: Vec<&'a [TypeParameter]>,
+    errors: Vec<CarbideCoreError>,
+}
+
+/// Check every generic function and instantiation in `program`
+#[must_use]
+pub fn check_generics(program: &[Statement]) -> Vec<CarbideCoreError> {
+    let mut checker = GenericChecker::new(program);
+    for statement in program {
+        checker.visit_statement(statement);
+    }
+    checker.errors
+}
+
+impl<'a> GenericChecker<'a> {
+    /// Create a [`GenericChecker`] aware of every function declared in `program`
+    #[must_use]
+    pub fn new(program: &'a [Statement]) -> Self {
+        let mut checker = Self {
+            functions: HashMap::new(),
+            table: MethodTable::new(program),
+            imported: HashSet::new(),
+            scopes: vec![HashSet::new()],
+            type_parameters: Vec::new(),
+            errors: Vec::new(),
+        };
+        checker.collect_functions(program);
+        checker
+    }
+
+    /// Record all function declarations, including ones nested in bodies, and imported items
+    fn collect_functions(&mut self, statements: &'a [Statement]) {
+        for statement in statements {
+            match statement {
+                Statement::FunctionDeclaration { name, body, .. } => {
+                    if let Some(signature) = Signature::of(statement) {
+                        self.functions.insert(name, signature);
+                    }
+                    self.collect_functions(&body.statements);
+                }
+                Statement::Import {
+                    items: Some(items), ..
+                } => self.imported.extend(items.iter().map(String::as_str)),
+                Statement::While { body, .. }
+                | Statement::For { body, .. }
+                | Statement::Block(body) => self.collect_functions(body),
+                _ => {}
+            }
+        }
+    }
+
+    /// Run `check` in a new scope
+    fn scoped(&mut self, check: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashSet::new());
+        check(self);
+        self.scopes.pop();
+    }
+
+    fn declare_pattern(&mut self, pattern: &'a Pattern) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.extend(pattern.bindings());
+        }
+    }
+
+    fn declare_parameters(&mut self, parameters: &'a [Parameter]) {
+        for parameter in parameters {
+            self.declare_pattern(&parameter.pattern);
+        }
+    }
+
+    /// Check if `name` refers to a local binding rather than a function
+    fn is_local(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    /// Check if any of `types` mentions a type parameter of an enclosing generic function
+    fn mentions_type_parameters(&self, types: &[Type]) -> bool {
+        let mut names = TypeNames(Vec::new());
+        for ty in types {
+            names.visit_type(ty);
+        }
+        names.0.iter().any(|&name| {
+            self.type_parameters
+                .iter()
+                .flat_map(|parameters| parameters.iter())
+                .any(|parameter| parameter.name == name)
+        })
+    }
+
+    fn check_type_parameters(&mut self, type_parameters: &[TypeParameter]) {
+        for (i, parameter) in type_parameters.iter().enumerate() {
+            for bound in &parameter.bounds {
+                let declared = self.table.trait_methods(bound).is_some()
+                    || self.imported.contains(bound.as_str());
+                if !declared {
+                    self.errors.push(CarbideCoreError::UnknownTraitBound {
+                        name: bound.clone(),
+                        span: parameter.span.clone(),
+                    });
+                }
+            }
+
+            if let Some(first) = type_parameters[..i]
+                .iter()
+                .find(|p| p.name == parameter.name)
+            {
+                self.errors.push(CarbideCoreError::DuplicateTypeParameter {
+                    name: parameter.name.clone(),
+                    span: parameter.span.clone(),
+                    first: first.span.clone(),
+                });
+            }
+        }
+    }
+
+    /// Check that `name::<arguments>` gives as many type arguments as `name` has
+    /// parameters, and that they satisfy the bounds of the parameters
+    fn check_instantiation(&mut self, name: &str, arguments: &[Type], span: &Span) {
+        // Functions from other modules can't be checked
+        let Some(signature) = self.functions.get(name).copied() else {
+            return;
+        };
+
+        if signature.type_parameters.len() != arguments.len() {
+            self.errors.push(CarbideCoreError::WrongTypeArgumentCount {
+                name: name.to_string(),
+                expected: signature.type_parameters.len(),
+                found: arguments.len(),
+                span: span.clone(),
+            });
+        } else if !self.mentions_type_parameters(arguments) {
+            // Instantiations with the type parameters of an enclosing function are checked
+            // by `monomorphize`, once those are replaced by types
+            let errors = signature.check_bounds(name, arguments, &self.table, span);
+            self.errors.extend(errors);
+        }
+    }
+
+    /// Check the evident types of `arguments` against the parameters of `name::<types>`
+    fn check_arguments(
+        &mut self,
+        name: &str,
+        types: &[Type],
+        arguments: &[Expression],
+        span: &Span,
+    ) {
+        let Some(signature) = self.functions.get(name).copied() else {
+            return;
+        };
+        if signature.type_parameters.len() != types.len() {
+            return;
+        }
+
+        let bindings = signature.bindings(types);
+        for (parameter, argument) in signature.parameters.iter().zip(arguments) {
+            let Some((expected, found)) = parameter
+                .type_annotation
+                .as_ref()
+                .zip(evident_type(argument))
+            else {
+                continue;
+            };
+
+            let expected = substitute(expected, &bindings);
+            if !accepts(&expected, &found) {
+                self.errors.push(CarbideCoreError::MismatchedArgumentType {
+                    function: name.to_string(),
+                    expected: print_type(&expected),
+                    found: print_type(&found),
+                    span: span.clone(),
+                });
+            }
+        }
+    }
+
+    /// Report a use of the generic function `name` without type arguments
+    fn check_missing_arguments(&mut self, name: &str, span: &Span) {
+        if self.is_local(name) {
+            return;
+        }
+
+        if self.functions.get(name).is_some_and(Signature::is_generic) {
+            self.errors.push(CarbideCoreError::MissingTypeArguments {
+                function: name.to_string(),
+                span: span.clone(),
+            });
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for GenericChecker<'a> {
+    fn visit_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::LetDeclaration { pattern, .. } => {
+                walk_statement(self, statement);
+                self.declare_pattern(pattern);
+            }
+            Statement::FunctionDeclaration {
+                type_parameters,
+                parameters,
+                ..
+            } => {
+                self.check_type_parameters(type_parameters);
+                self.type_parameters.push(type_parameters);
+                self.scoped(|checker| {
+                    checker.declare_parameters(parameters);
+                    walk_statement(checker, statement);
+                });
+                self.type_parameters.pop();
+            }
+            Statement::While { .. } | Statement::For { .. } | Statement::Block(_) => {
+                self.scoped(|checker| walk_statement(checker, statement));
+            }
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_method(&mut self, method: &'a Method) {
+        self.scoped(|checker| {
+            checker.declare_parameters(&method.parameters);
+            walk_method(checker, method);
+        });
+    }
+
+    fn visit_block(&mut self, block: &'a Block) {
+        self.scoped(|checker| walk_block(checker, block));
+    }
+
+    fn visit_match_arm(&mut self, arm: &'a MatchArm) {
+        self.scoped(|checker| {
+            checker.declare_pattern(&arm.pattern);
+            walk_match_arm(checker, arm);
+        });
+    }
+
+    fn visit_expression(&mut self, expression: &'a Expression) {
+        if let Expression::Lambda { parameters, .. } = expression {
+            self.scoped(|checker| {
+                checker.declare_parameters(parameters);
+                walk_expression(checker, expression);
+            });
+            return;
+        }
+
+        match expression {
+            Expression::Generic {
+                name,
+                arguments,
+                span,
+            } => self.check_instantiation(name, arguments, span),
+            Expression::Call { callee, arguments } => {
+                if let Expression::Generic {
+                    name,
+                    arguments: types,
+                    span,
+                } = callee.as_ref()
+                {
+                    self.check_arguments(name, types, arguments, span);
+                }
+            }
+            Expression::Identifier { name, span } => self.check_missing_arguments(name, span),
+            _ => {}
+        }
+        walk_expression(self, expression);
+    }
+}
@@ -1 +1,8 @@
-
+pub mod attributes;
+pub mod errors;
+pub mod exhaustiveness;
+pub mod generics;
+pub mod methods;
+pub mod modules;
+pub mod monomorphize;
+pub mod mutability;
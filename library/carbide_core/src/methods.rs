@@ -0,0 +1,645 @@
+use std::collections::HashMap;
+
+use carbide_lexer::tokens::Span;
+use carbide_parser::fold::Folder;
+use carbide_parser::nodes::{
+    Block, Expression, MatchArm, Method, Parameter, Pattern, Statement, StructField, Type,
+};
+use carbide_parser::print::print_type;
+use carbide_parser::visit::{
+    Visitor, walk_block, walk_expression, walk_match_arm, walk_method, walk_statement,
+};
+
+use crate::errors::CarbideCoreError;
+use crate::generics::{Substitution, evident_type};
+
+/// A method a call resolved to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Resolved<'a> {
+    /// The trait the method comes from, or `None` for a method of an inherent impl
+    pub trait_name: Option<&'a str>,
+    pub method: &'a Method,
+}
+
+/// An `impl Trait for Type` block
+#[derive(Debug, Clone, Copy)]
+struct TraitImpl<'a> {
+    trait_name: &'a str,
+    methods: &'a [Method],
+}
+
+/// The traits, impls and types declared in a program, used to look up methods by type
+#[derive(Debug, Default)]
+pub struct MethodTable<'a> {
+    traits: HashMap<&'a str, &'a [Method]>,
+    /// Methods from `impl Type { ... }` blocks of each type
+    inherent: HashMap<&'a str, Vec<&'a Method>>,
+    /// Traits implemented by each type
+    impls: HashMap<&'a str, Vec<TraitImpl<'a>>>,
+    structs: HashMap<&'a str, &'a [StructField]>,
+    enums: Vec<&'a str>,
+}
+
+impl<'a> MethodTable<'a> {
+    /// Create a [`MethodTable`] of every trait, impl and type declared in `program`
+    #[must_use]
+    pub fn new(program: &'a [Statement]) -> Self {
+        let mut table = Self::default();
+        table.collect_declarations(program);
+        table
+    }
+
+    /// Record all declarations, including ones nested in bodies
+    fn collect_declarations(&mut self, statements: &'a [Statement]) {
+        for statement in statements {
+            match statement {
+                Statement::StructDeclaration { name, fields, .. } => {
+                    self.structs.insert(name, fields);
+                }
+                Statement::EnumDeclaration { name, .. } => self.enums.push(name),
+                Statement::TraitDeclaration { name, methods, .. } => {
+                    self.traits.insert(name, methods);
+                    self.collect_method_bodies(methods);
+                }
+                Statement::ImplDeclaration {
+                    trait_name,
+                    target,
+                    methods,
+                    ..
+                } => {
+                    match trait_name {
+                        Some(trait_name) => {
+                            self.impls.entry(target).or_default().push(TraitImpl {
+                                trait_name,
+                                methods,
+                            });
+                        }
+                        None => self.inherent.entry(target).or_default().extend(methods),
+                    }
+                    self.collect_method_bodies(methods);
+                }
+                Statement::FunctionDeclaration { body, .. } => {
+                    self.collect_declarations(&body.statements);
+                }
+                Statement::While { body, .. }
+                | Statement::For { body, .. }
+                | Statement::Block(body) => self.collect_declarations(body),
+                _ => {}
+            }
+        }
+    }
+
+    fn collect_method_bodies(&mut self, methods: &'a [Method]) {
+        for method in methods {
+            if let Some(body) = &method.body {
+                self.collect_declarations(&body.statements);
+            }
+        }
+    }
+
+    /// Get the methods of the trait called `name`, if it's declared in the program
+    #[must_use]
+    pub fn trait_methods(&self, name: &str) -> Option<&'a [Method]> {
+        self.traits.get(name).copied()
+    }
+
+    /// Check if `ty` is a struct or enum declared in the program, or has an impl
+    #[must_use]
+    pub fn is_known_type(&self, ty: &str) -> bool {
+        self.structs.contains_key(ty)
+            || self.enums.contains(&ty)
+            || self.inherent.contains_key(ty)
+            || self.impls.contains_key(ty)
+    }
+
+    /// Check if `ty` has an impl of the trait called `trait_name`
+    #[must_use]
+    pub fn implements(&self, ty: &Type, trait_name: &str) -> bool {
+        type_name(ty)
+            .and_then(|ty| self.impls.get(ty))
+            .is_some_and(|impls| {
+                impls
+                    .iter()
+                    .any(|implementation| implementation.trait_name == trait_name)
+            })
+    }
+
+    /// Check if `ty` is a struct with a field called `name`
+    #[must_use]
+    pub fn has_field(&self, ty: &str, name: &str) -> bool {
+        self.structs
+            .get(ty)
+            .is_some_and(|fields| fields.iter().any(|field| field.name == name))
+    }
+
+    /// Find the methods called `name` that a value of `ty` could call
+    ///
+    /// An inherent method hides methods of traits, otherwise there's one candidate for each
+    /// trait `ty` implements with a method called `name`. A trait method the impl leaves out
+    /// resolves to the trait's declaration
+    #[must_use]
+    pub fn resolve(&self, ty: &str, name: &str) -> Vec<Resolved<'a>> {
+        if let Some(method) = self
+            .inherent
+            .get(ty)
+            .and_then(|methods| methods.iter().find(|method| method.name == name))
+        {
+            return vec![Resolved {
+                trait_name: None,
+                method,
+            }];
+        }
+
+        self.impls
+            .get(ty)
+            .into_iter()
+            .flatten()
+            .filter_map(|implementation| {
+                let method = find(implementation.methods, name)
+                    .or_else(|| find(self.trait_methods(implementation.trait_name)?, name))?;
+                Some(Resolved {
+                    trait_name: Some(implementation.trait_name),
+                    method,
+                })
+            })
+            .collect()
+    }
+
+    /// Find the methods called `name` in the traits of `bounds`, or `None` if there are
+    /// none and some of the traits aren't declared in the program
+    fn resolve_in_traits(&self, bounds: &[&'a str], name: &str) -> Option<Vec<Resolved<'a>>> {
+        let mut unknown = false;
+        let candidates = bounds
+            .iter()
+            .filter_map(|&trait_name| {
+                let Some(methods) = self.trait_methods(trait_name) else {
+                    unknown = true;
+                    return None;
+                };
+                Some(Resolved {
+                    trait_name: Some(trait_name),
+                    method: find(methods, name)?,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        (!candidates.is_empty() || !unknown).then_some(candidates)
+    }
+}
+
+fn find<'a>(methods: &'a [Method], name: &str) -> Option<&'a Method> {
+    methods.iter().find(|method| method.name == name)
+}
+
+/// Replace `Self` in `ty` with `target`
+fn substitute_self(ty: &Type, target: &str) -> Type {
+    let target = Type::named(target);
+    let bindings = HashMap::from([("Self".to_string(), &target)]);
+    Substitution {
+        bindings: &bindings,
+    }
+    .fold_type(ty.clone())
+}
+
+/// Get the name of the type methods are looked up on for a value of `ty`
+fn type_name(ty: &Type) -> Option<&str> {
+    match ty {
+        Type::Named(name) | Type::Generic { name, .. } => Some(name),
+        _ => None,
+    }
+}
+
+/// Format the signature of `method` in an impl for `target`, like `fn area(self) -> float`
+fn print_signature(method: &Method, target: &str) -> String {
+    let parameters = method
+        .parameters
+        .iter()
+        .enumerate()
+        .map(|(i, parameter)| match &parameter.type_annotation {
+            _ if i == 0 && method.takes_self() => "self".to_string(),
+            Some(ty) => print_type(&substitute_self(ty, target)),
+            None => "_".to_string(),
+        })
+        .collect::<Vec<_>>();
+    let return_type = match &method.return_type {
+        None | Some(Type::Unit) => String::new(),
+        Some(ty) => format!(" -> {}", print_type(&substitute_self(ty, target))),
+    };
+
+    format!("fn {}({}){return_type}", method.name, parameters.join(", "))
+}
+
+/// Check if `found` in an impl for `target` has the signature of `expected` from the trait
+///
+/// Parameters without a type annotation match any type, since names aren't resolved to
+/// types yet
+fn signatures_match(expected: &Method, found: &Method, target: &str) -> bool {
+    let unit = Type::Unit;
+    let resolve = |ty: &Type| substitute_self(ty, target);
+
+    expected.takes_self() == found.takes_self()
+        && expected.parameters.len() == found.parameters.len()
+        && expected
+            .parameters
+            .iter()
+            .zip(&found.parameters)
+            .all(|(e, f)| match (&e.type_annotation, &f.type_annotation) {
+                (Some(e), Some(f)) => resolve(e) == resolve(f),
+                _ => true,
+            })
+        && resolve(expected.return_type.as_ref().unwrap_or(&unit))
+            == resolve(found.return_type.as_ref().unwrap_or(&unit))
+}
+
+/// Checks that impls provide every method their trait requires with matching signatures,
+/// and resolves each method call like `c.area()` to the impl it calls
+///
+/// The type of a value is only known from an annotation, a struct literal, `self`, or the
+/// return type of a method that returns it, since names aren't resolved to types yet.
+/// Calls on values of other types aren't checked
+pub struct MethodChecker<'a> {
+    table: MethodTable<'a>,
+    /// Types of the names bound in each scope, or `None` if a name's type isn't known
+    scopes: Vec<HashMap<&'a str, Option<Type>>>,
+    /// Bounds of the type parameters of each enclosing generic function or trait, where
+    /// `Self` in a trait is bound by the trait
+    type_parameters: Vec<HashMap<&'a str, Vec<&'a str>>>,
+    /// The type `Self` stands for in the impl being checked
+    self_type: Option<&'a str>,
+    /// The first impl of each trait for each type
+    impls: HashMap<(&'a str, &'a str), &'a Span>,
+    /// The first method with each name in the inherent impls of each type
+    inherent: HashMap<&'a str, HashMap<&'a str, &'a Span>>,
+    errors: Vec<CarbideCoreError>,
+}
+
+/// Check every trait, impl and method call in `program`
+#[must_use]
+pub fn check_methods(program: &[Statement]) -> Vec<CarbideCoreError> {
+    let mut checker = MethodChecker::new(program);
+    for statement in program {
+        checker.visit_statement(statement);
+    }
+    checker.errors
+}
+
+impl<'a> MethodChecker<'a> {
+    /// Create a [`MethodChecker`] aware of every trait, impl and type declared in `program`
+    #[must_use]
+    pub fn new(program: &'a [Statement]) -> Self {
+        Self {
+            table: MethodTable::new(program),
+            scopes: vec![HashMap::new()],
+            type_parameters: Vec::new(),
+            self_type: None,
+            impls: HashMap::new(),
+            inherent: HashMap::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Run `check` in a new scope
+    fn scoped(&mut self, check: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        check(self);
+        self.scopes.pop();
+    }
+
+    /// Declare every name bound by `pattern`, where a plain binding has type `ty`
+    fn declare_pattern(&mut self, pattern: &'a Pattern, ty: Option<Type>) {
+        let Some(scope) = self.scopes.last_mut() else {
+            return;
+        };
+
+        if let Pattern::Binding { name, .. } = pattern {
+            scope.insert(name, ty);
+        } else {
+            for name in pattern.bindings() {
+                scope.insert(name, None);
+            }
+        }
+    }
+
+    fn declare_parameters(&mut self, parameters: &'a [Parameter]) {
+        for parameter in parameters {
+            let ty = match (&parameter.pattern, &parameter.type_annotation) {
+                (_, Some(ty)) => Some(self.resolve_self(ty)),
+                (Pattern::Binding { name, .. }, None) if name == "self" => {
+                    Some(Type::named(self.self_type.unwrap_or("Self")))
+                }
+                _ => None,
+            };
+            self.declare_pattern(&parameter.pattern, ty);
+        }
+    }
+
+    /// Replace `Self` in `ty` with the type of the impl being checked
+    fn resolve_self(&self, ty: &Type) -> Type {
+        match self.self_type {
+            Some(target) => substitute_self(ty, target),
+            None => ty.clone(),
+        }
+    }
+
+    fn bounds(&self, type_parameter: &str) -> Option<&[&'a str]> {
+        self.type_parameters
+            .iter()
+            .rev()
+            .find_map(|parameters| parameters.get(type_parameter))
+            .map(Vec::as_slice)
+    }
+
+    /// Get the type of `expression`, if it can be seen from the declarations in scope
+    fn type_of(&self, expression: &Expression) -> Option<Type> {
+        match expression {
+            Expression::Identifier { name, .. } => self
+                .scopes
+                .iter()
+                .rev()
+                .find_map(|scope| scope.get(name.as_str()))?
+                .clone(),
+            Expression::Grouped(inner) => self.type_of(inner),
+            // A struct variant like `Shape::Rect { .. }` is a value of its enum
+            Expression::StructLiteral { name, .. } => Some(Type::named(
+                name.rsplit_once("::").map_or(name.as_str(), |(ty, _)| ty),
+            )),
+            Expression::Call { callee, .. } => self.return_type(callee),
+            expression => evident_type(expression),
+        }
+    }
+
+    /// Get the type returned by calling `callee`, if it's a method with a known return type
+    fn return_type(&self, callee: &Expression) -> Option<Type> {
+        let (ty, candidates) = match callee {
+            Expression::Identifier { name: path, .. } => {
+                let (ty, name) = path.rsplit_once("::")?;
+                (ty.to_string(), self.table.resolve(ty, name))
+            }
+            Expression::MemberAccess { target, member, .. } => {
+                let ty = type_name(&self.type_of(target)?)?.to_string();
+                let candidates = self.candidates(&ty, member)?;
+                (ty, candidates)
+            }
+            _ => return None,
+        };
+
+        match candidates.as_slice() {
+            [resolved] => Some(substitute_self(resolved.method.return_type.as_ref()?, &ty)),
+            _ => None,
+        }
+    }
+
+    /// Find the methods called `name` on a value of `ty`, or `None` if `ty` isn't declared
+    /// in the program
+    fn candidates(&self, ty: &str, name: &str) -> Option<Vec<Resolved<'a>>> {
+        if let Some(bounds) = self.bounds(ty) {
+            self.table.resolve_in_traits(bounds, name)
+        } else if self.table.is_known_type(ty) {
+            Some(self.table.resolve(ty, name))
+        } else {
+            None
+        }
+    }
+
+    /// Check that the call `target.name(arguments)` resolves to exactly one method that
+    /// takes `self` and `arguments` more parameters
+    fn check_call(&mut self, target: &Expression, name: &str, arguments: usize, span: &Span) {
+        // Calls on values of unknown types can't be checked
+        let Some(ty) = self.type_of(target) else {
+            return;
+        };
+        let Some(ty) = type_name(&ty) else {
+            return;
+        };
+        let Some(candidates) = self.candidates(ty, name) else {
+            return;
+        };
+
+        match candidates.as_slice() {
+            // Calling a field that holds a function
+            [] if self.table.has_field(ty, name) => {}
+            [] => self.errors.push(CarbideCoreError::UnknownMethod {
+                target: ty.to_string(),
+                name: name.to_string(),
+                span: span.clone(),
+            }),
+            [Resolved { method, .. }] if !method.takes_self() => {
+                self.errors.push(CarbideCoreError::NotAMethod {
+                    target: ty.to_string(),
+                    name: name.to_string(),
+                    span: span.clone(),
+                });
+            }
+            [Resolved { method, .. }] => {
+                let expected = method.parameters.len() - 1;
+                if expected != arguments {
+                    self.errors
+                        .push(CarbideCoreError::WrongMethodArgumentCount {
+                            name: name.to_string(),
+                            expected,
+                            found: arguments,
+                            span: span.clone(),
+                        });
+                }
+            }
+            candidates => self.errors.push(CarbideCoreError::AmbiguousMethod {
+                target: ty.to_string(),
+                name: name.to_string(),
+                traits: candidates
+                    .iter()
+                    .filter_map(|resolved| resolved.trait_name.map(str::to_string))
+                    .collect(),
+                span: span.clone(),
+            }),
+        }
+    }
+
+    /// Report methods of `methods` with the same name as an earlier one in `seen`
+    fn check_duplicates(&mut self, methods: &'a [Method], seen: &mut HashMap<&'a str, &'a Span>) {
+        for method in methods {
+            if let Some(first) = seen.get(method.name.as_str()) {
+                self.errors.push(CarbideCoreError::DuplicateMethod {
+                    name: method.name.clone(),
+                    span: method.span.clone(),
+                    first: (*first).clone(),
+                });
+            } else {
+                seen.insert(&method.name, &method.span);
+            }
+        }
+    }
+
+    /// Check that the impl of `trait_name` for `target` provides every method without a
+    /// default, and only methods of the trait with the same signatures
+    fn check_trait_impl(
+        &mut self,
+        trait_name: &'a str,
+        target: &'a str,
+        methods: &'a [Method],
+        span: &'a Span,
+    ) {
+        if let Some(first) = self.impls.get(&(trait_name, target)) {
+            self.errors.push(CarbideCoreError::DuplicateImpl {
+                trait_name: trait_name.to_string(),
+                target: target.to_string(),
+                span: span.clone(),
+                first: (*first).clone(),
+            });
+        } else {
+            self.impls.insert((trait_name, target), span);
+        }
+        self.check_duplicates(methods, &mut HashMap::new());
+
+        // Traits from other modules can't be checked
+        let Some(required) = self.table.trait_methods(trait_name) else {
+            return;
+        };
+
+        for method in methods {
+            let Some(expected) = find(required, &method.name) else {
+                self.errors.push(CarbideCoreError::UnknownTraitMethod {
+                    trait_name: trait_name.to_string(),
+                    name: method.name.clone(),
+                    span: method.span.clone(),
+                });
+                continue;
+            };
+
+            if !signatures_match(expected, method, target) {
+                self.errors
+                    .push(CarbideCoreError::MismatchedMethodSignature {
+                        name: method.name.clone(),
+                        expected: print_signature(expected, target),
+                        found: print_signature(method, target),
+                        span: method.span.clone(),
+                        declaration: expected.span.clone(),
+                    });
+            }
+        }
+
+        let missing = required
+            .iter()
+            .filter(|method| method.body.is_none() && find(methods, &method.name).is_none())
+            .map(|method| method.name.clone())
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            self.errors.push(CarbideCoreError::MissingTraitMethod {
+                trait_name: trait_name.to_string(),
+                target: target.to_string(),
+                missing,
+                span: span.clone(),
+            });
+        }
+    }
+}
+
+impl<'a> Visitor<'a> for MethodChecker<'a> {
+    fn visit_statement(&mut self, statement: &'a Statement) {
+        match statement {
+            Statement::LetDeclaration {
+                pattern,
+                type_annotation,
+                initializer,
+                ..
+            } => {
+                walk_statement(self, statement);
+                let ty = match type_annotation {
+                    Some(ty) => Some(self.resolve_self(ty)),
+                    None => initializer.as_ref().and_then(|value| self.type_of(value)),
+                };
+                self.declare_pattern(pattern, ty);
+            }
+            Statement::FunctionDeclaration {
+                type_parameters,
+                parameters,
+                ..
+            } => {
+                self.type_parameters.push(
+                    type_parameters
+                        .iter()
+                        .map(|parameter| {
+                            let bounds = parameter.bounds.iter().map(String::as_str).collect();
+                            (parameter.name.as_str(), bounds)
+                        })
+                        .collect(),
+                );
+                self.scoped(|checker| {
+                    checker.declare_parameters(parameters);
+                    walk_statement(checker, statement);
+                });
+                self.type_parameters.pop();
+            }
+            Statement::TraitDeclaration { name, methods, .. } => {
+                self.check_duplicates(methods, &mut HashMap::new());
+
+                let outer = self.self_type.take();
+                self.type_parameters
+                    .push(HashMap::from([("Self", vec![name.as_str()])]));
+                walk_statement(self, statement);
+                self.type_parameters.pop();
+                self.self_type = outer;
+            }
+            Statement::ImplDeclaration {
+                trait_name,
+                target,
+                methods,
+                span,
+            } => {
+                if let Some(trait_name) = trait_name {
+                    self.check_trait_impl(trait_name, target, methods, span);
+                } else {
+                    let mut seen = self.inherent.remove(target.as_str()).unwrap_or_default();
+                    self.check_duplicates(methods, &mut seen);
+                    self.inherent.insert(target, seen);
+                }
+
+                let outer = self.self_type.replace(target);
+                walk_statement(self, statement);
+                self.self_type = outer;
+            }
+            Statement::While { .. } | Statement::For { .. } | Statement::Block(_) => {
+                self.scoped(|checker| walk_statement(checker, statement));
+            }
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_method(&mut self, method: &'a Method) {
+        self.scoped(|checker| {
+            checker.declare_parameters(&method.parameters);
+            walk_method(checker, method);
+        });
+    }
+
+    fn visit_expression(&mut self, expression: &'a Expression) {
+        match expression {
+            Expression::Lambda { parameters, .. } => self.scoped(|checker| {
+                checker.declare_parameters(parameters);
+                walk_expression(checker, expression);
+            }),
+            Expression::Call { callee, arguments } => {
+                walk_expression(self, expression);
+                if let Expression::MemberAccess {
+                    target,
+                    member,
+                    span,
+                } = callee.as_ref()
+                {
+                    self.check_call(target, member, arguments.len(), span);
+                }
+            }
+            _ => walk_expression(self, expression),
+        }
+    }
+
+    fn visit_block(&mut self, block: &'a Block) {
+        self.scoped(|checker| walk_block(checker, block));
+    }
+
+    fn visit_match_arm(&mut self, arm: &'a MatchArm) {
+        self.scoped(|checker| {
+            checker.declare_pattern(&arm.pattern, None);
+            walk_match_arm(checker, arm);
+        });
+    }
+}
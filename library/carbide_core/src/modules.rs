@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use carbide_lexer::{errors::CarbideLexerError, lexer::CarbideLexer};
+use carbide_parser::{
+    errors::CarbideParserError,
+    nodes::{Statement, Visibility},
+    parser::CarbideParser,
+};
+
+use crate::errors::CarbideCoreError;
+
+/// File extension of Carbide source files
+pub const SOURCE_EXTENSION: &str = "cb";
+
+/// A parsed source file, named by its import path like `util.strings`
+#[derive(Debug)]
+pub struct Module {
+    pub name: String,
+    pub file: PathBuf,
+    pub source: String,
+    pub ast: Vec<Statement>,
+    pub lexer_errors: Vec<Box<CarbideLexerError>>,
+    pub parser_errors: Vec<Box<CarbideParserError>>,
+    /// Errors from resolving this module's imports
+    pub errors: Vec<CarbideCoreError>,
+}
+
+impl Module {
+    /// Check if there were any errors loading this [`Module`]
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        !self.lexer_errors.is_empty() || !self.parser_errors.is_empty() || !self.errors.is_empty()
+    }
+
+    /// Get the [`Visibility`] of the top level item called `name`, if there is one
+    #[must_use]
+    pub fn item_visibility(&self, name: &str) -> Option<Visibility> {
+        self.ast.iter().find_map(|statement| match statement {
+            Statement::FunctionDeclaration {
+                visibility,
+                name: item,
+                ..
+            }
+            | Statement::StructDeclaration {
+                visibility,
+                name: item,
+                ..
+            }
+            | Statement::EnumDeclaration {
+                visibility,
+                name: item,
+                ..
+            }
+            | Statement::ConstDeclaration {
+                visibility,
+                name: item,
+                ..
+            }
+            | Statement::TraitDeclaration {
+                visibility,
+                name: item,
+                ..
+            } if item == name => Some(*visibility),
+            _ => None,
+        })
+    }
+}
+
+/// Loads a module and everything it imports, resolving import paths to files under a root
+///
+/// `import util.strings;` resolves to `<root>/util/strings.cb`
+pub struct ModuleLoader {
+    root: PathBuf,
+    modules: Vec<Module>,
+    /// Index into `modules` for each loaded module name
+    indices: HashMap<String, usize>,
+    /// Names of the modules currently being loaded, used to detect import cycles
+    stack: Vec<String>,
+}
+
+impl ModuleLoader {
+    #[must_use]
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            modules: Vec::new(),
+            indices: HashMap::new(),
+            stack: Vec::new(),
+        }
+    }
+
+    /// Get the file an import `path` resolves to
+    #[must_use]
+    pub fn resolve(&self, path: &[String]) -> PathBuf {
+        let mut file = path.iter().fold(self.root.clone(), |file, s| file.join(s));
+        file.set_extension(SOURCE_EXTENSION);
+        file
+    }
+
+    /// Load the module `name`, like `main` or `util.strings`, along with its imports
+    ///
+    /// Errors in the loaded modules are stored on each [`Module`]
+    ///
+    /// # Errors
+    /// Returns `Err` if the module's file can't be read
+    pub fn load(&mut self, name: &str) -> io::Result<&Module> {
+        let path = name.split('.').map(str::to_string).collect::<Vec<_>>();
+        let index = self.load_path(&path)?;
+        Ok(&self.modules[index])
+    }
+
+    /// Get all loaded modules, with each module after the modules it imports
+    #[must_use]
+    pub fn modules(&self) -> &[Module] {
+        &self.modules
+    }
+
+    /// Get a loaded module by name
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Module> {
+        self.indices.get(name).map(|&index| &self.modules[index])
+    }
+
+    /// Get the root that import paths are resolved against
+    #[must_use]
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Load the module at `path` if it isn't loaded yet, returning its index
+    ///
+    /// # Errors
+    /// Returns `Err` if the module's file can't be read
+    fn load_path(&mut self, path: &[String]) -> io::Result<usize> {
+        let name = path.join(".");
+        if let Some(&index) = self.indices.get(&name) {
+            return Ok(index);
+        }
+
+        let file = self.resolve(path);
+        let source = fs::read_to_string(&file)?;
+
+        // The lexer recovers from its errors, so the tokens it did produce are still parsed
+        let (ast, lexer_errors, parser_errors) = {
+            let mut lexer = CarbideLexer::from_src(&source);
+            let lex_result = lexer.lex();
+            let parsed = CarbideParser::new(lex_result.tokens).parse();
+            (parsed.ast, lex_result.errors, parsed.errors)
+        };
+
+        self.stack.push(name.clone());
+        let errors = self.resolve_imports(&ast);
+        self.stack.pop();
+
+        let index = self.modules.len();
+        self.modules.push(Module {
+            name: name.clone(),
+            file,
+            source,
+            ast,
+            lexer_errors,
+            parser_errors,
+            errors,
+        });
+        self.indices.insert(name, index);
+
+        Ok(index)
+    }
+
+    /// Load the modules imported at the top level of `ast`, checking any imported items
+    fn resolve_imports(&mut self, ast: &[Statement]) -> Vec<CarbideCoreError> {
+        let mut errors = Vec::new();
+
+        for statement in ast {
+            let Statement::Import { path, items, span } = statement else {
+                continue;
+            };
+            let module = path.join(".");
+
+            if let Some(start) = self.stack.iter().position(|m| *m == module) {
+                let mut cycle = self.stack[start..].to_vec();
+                cycle.push(module);
+                errors.push(CarbideCoreError::ImportCycle {
+                    cycle,
+                    span: span.clone(),
+                });
+                continue;
+            }
+
+            let index = match self.load_path(path) {
+                Ok(index) => index,
+                Err(error) if error.kind() == io::ErrorKind::NotFound => {
+                    errors.push(CarbideCoreError::UnresolvedImport {
+                        path: module,
+                        span: span.clone(),
+                    });
+                    continue;
+                }
+                Err(error) => {
+                    errors.push(CarbideCoreError::UnreadableImport {
+                        path: module,
+                        reason: error.to_string(),
+                        span: span.clone(),
+                    });
+                    continue;
+                }
+            };
+
+            for item in items.iter().flatten() {
+                match self.modules[index].item_visibility(item) {
+                    Some(Visibility::Public) => {}
+                    Some(Visibility::Private) => errors.push(CarbideCoreError::PrivateImport {
+                        item: item.clone(),
+                        module: module.clone(),
+                        span: span.clone(),
+                    }),
+                    None => errors.push(CarbideCoreError::UnresolvedImport {
+                        path: format!("{module}.{item}"),
+                        span: span.clone(),
+                    }),
+                }
+            }
+        }
+
+        errors
+    }
+}
@@ -0,0 +1,207 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use carbide_parser::fold::{Folder, walk_expression};
+use carbide_parser::nodes::{Expression, Statement, Type};
+use carbide_parser::print::print_type;
+
+use crate::errors::CarbideCoreError;
+use crate::generics::{Signature, Substitution};
+use crate::methods::MethodTable;
+
+/// Upper bound on the number of instances, which stops polymorphic recursion like a call to
+/// `f::<[T]>` inside `f<T>`
+const MAX_INSTANCES: usize = 256;
+
+/// A program where every generic function is replaced by its instances
+#[derive(Debug, Clone)]
+pub struct Monomorphized {
+    pub statements: Vec<Statement>,
+    pub errors: Vec<CarbideCoreError>,
+}
+
+/// Get the name of the instance of `function` for `arguments`, like `first<int>`
+///
+/// `<` can't appear in identifiers, so instances never clash with declared functions
+#[must_use]
+pub fn instance_name(function: &str, arguments: &[Type]) -> String {
+    let arguments = arguments.iter().map(print_type).collect::<Vec<_>>();
+    format!("{function}<{}>", arguments.join(", "))
+}
+
+/// Replace each top level generic function in `program` with a copy per distinct set of
+/// type arguments it's instantiated with, and each `name::<types>` with the name of its
+/// instance
+///
+/// Instances take the place of their generic function, in the order they're first needed.
+/// Generic functions that are never instantiated are left out, and generic functions nested
+/// in bodies are kept as they are. Instantiations inside instances are checked against the
+/// bounds of their type parameters
+#[must_use]
+pub fn monomorphize(program: &[Statement]) -> Monomorphized {
+    let generics = program
+        .iter()
+        .filter_map(|statement| match statement {
+            Statement::FunctionDeclaration { name, .. } => Signature::of(statement)
+                .filter(Signature::is_generic)
+                .map(|_| (name.as_str(), statement)),
+            _ => None,
+        })
+        .collect::<HashMap<_, _>>();
+
+    let table = MethodTable::new(program);
+    let mut instantiator = Instantiator {
+        generics: &generics,
+        table: &table,
+        check_bounds: false,
+        queue: VecDeque::new(),
+        instances: HashSet::new(),
+        errors: Vec::new(),
+    };
+
+    let mut statements = program
+        .iter()
+        .map(|statement| match statement {
+            Statement::FunctionDeclaration { name, .. } if generics.contains_key(name.as_str()) => {
+                None
+            }
+            statement => Some(instantiator.fold_statement(statement.clone())),
+        })
+        .collect::<Vec<_>>();
+
+    // Instantiations in the source are checked by `check_generics`, but ones in instances
+    // can only be checked now that their type parameters are replaced
+    instantiator.check_bounds = true;
+    let mut instances: HashMap<&str, Vec<Statement>> = HashMap::new();
+    while let Some((function, arguments)) = instantiator.queue.pop_front() {
+        let Some((&function, generic)) = generics.get_key_value(function.as_str()) else {
+            continue;
+        };
+        let instance = instantiate(generic, &arguments);
+        let instance = instantiator.fold_statement(instance);
+        instances.entry(function).or_default().push(instance);
+    }
+
+    let statements = program
+        .iter()
+        .zip(statements.iter_mut())
+        .flat_map(|(original, folded)| match folded.take() {
+            Some(statement) => vec![statement],
+            None => match original {
+                Statement::FunctionDeclaration { name, .. } => {
+                    instances.remove(name.as_str()).unwrap_or_default()
+                }
+                _ => Vec::new(),
+            },
+        })
+        .collect();
+
+    Monomorphized {
+        statements,
+        errors: instantiator.errors,
+    }
+}
+
+/// Copy the generic function `generic` with its type parameters replaced by `arguments`
+fn instantiate(generic: &Statement, arguments: &[Type]) -> Statement {
+    let Some(signature) = Signature::of(generic) else {
+        return generic.clone();
+    };
+    let bindings = signature.bindings(arguments);
+
+    match (Substitution {
+        bindings: &bindings,
+    })
+    .fold_statement(generic.clone())
+    {
+        Statement::FunctionDeclaration {
+            attributes,
+            visibility,
+            name,
+            parameters,
+            return_type,
+            body,
+            ..
+        } => Statement::FunctionDeclaration {
+            attributes,
+            visibility,
+            name: instance_name(&name, arguments),
+            type_parameters: Vec::new(),
+            parameters,
+            return_type,
+            body,
+        },
+        statement => statement,
+    }
+}
+
+/// Replaces instantiations of generic functions with the names of their instances, queueing
+/// the instances that haven't been seen yet
+struct Instantiator<'a> {
+    generics: &'a HashMap<&'a str, &'a Statement>,
+    table: &'a MethodTable<'a>,
+    /// Whether instantiations are checked against the bounds of their type parameters
+    check_bounds: bool,
+    /// Instances left to create, as the generic function and its type arguments
+    queue: VecDeque<(String, Vec<Type>)>,
+    /// Names of every instance queued so far
+    instances: HashSet<String>,
+    errors: Vec<CarbideCoreError>,
+}
+
+impl Folder for Instantiator<'_> {
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        match walk_expression(self, expression) {
+            Expression::Generic {
+                name,
+                arguments,
+                span,
+            } => {
+                // Wrong type argument counts are reported by `check_generics`
+                let signature = self
+                    .generics
+                    .get(name.as_str())
+                    .and_then(|generic| Signature::of(generic))
+                    .filter(|signature| signature.type_parameters.len() == arguments.len());
+                let Some(signature) = signature else {
+                    return Expression::Generic {
+                        name,
+                        arguments,
+                        span,
+                    };
+                };
+                if self.check_bounds {
+                    let errors = signature.check_bounds(&name, &arguments, self.table, &span);
+                    self.errors.extend(errors);
+                }
+
+                let instance = instance_name(&name, &arguments);
+                if !self.instances.contains(&instance) {
+                    if self.instances.len() >= MAX_INSTANCES {
+                        let reported = self.errors.iter().any(|error| {
+                            matches!(error, CarbideCoreError::TooManyInstances { .. })
+                        });
+                        if !reported {
+                            self.errors.push(CarbideCoreError::TooManyInstances {
+                                function: name.clone(),
+                                span: span.clone(),
+                            });
+                        }
+                        return Expression::Generic {
+                            name,
+                            arguments,
+                            span,
+                        };
+                    }
+                    self.instances.insert(instance.clone());
+                    self.queue.push_back((name, arguments));
+                }
+
+                Expression::Identifier {
+                    name: instance,
+                    span,
+                }
+            }
+            expression => expression,
+        }
+    }
+}
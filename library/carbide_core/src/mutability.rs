@@ -0,0 +1,243 @@
+use std::collections::HashMap;
+
+use carbide_lexer::tokens::Span;
+use carbide_parser::nodes::{Block, Expression, MatchArm, Method, Parameter, Pattern, Statement};
+use carbide_parser::visit::{
+    Visitor, walk_block, walk_expression, walk_match_arm, walk_method, walk_statement,
+};
+
+use crate::errors::CarbideCoreError;
+
+/// How a name was bound
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BindingKind {
+    /// `let`, parameters and pattern bindings
+    Immutable,
+    /// `let mut` and `mut` parameters
+    Mutable,
+    /// `const`
+    Constant,
+}
+
+#[derive(Debug, Clone)]
+struct Binding {
+    kind: BindingKind,
+    /// Span of the declaration
+    span: Span,
+}
+
+/// Checks that only mutable bindings are assigned to, and that constants have constant
+/// initializers
+pub struct MutabilityChecker {
+    scopes: Vec<HashMap<String, Binding>>,
+    errors: Vec<CarbideCoreError>,
+}
+
+/// Check every assignment and constant in `program`
+#[must_use]
+pub fn check_mutability(program: &[Statement]) -> Vec<CarbideCoreError> {
+    let mut checker = MutabilityChecker::new();
+    checker.check_program(program);
+    checker.errors
+}
+
+impl Default for MutabilityChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MutabilityChecker {
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            scopes: vec![HashMap::new()],
+            errors: Vec::new(),
+        }
+    }
+
+    /// Check a whole program, where top level constants are visible everywhere
+    fn check_program(&mut self, program: &[Statement]) {
+        for statement in program {
+            if let Statement::ConstDeclaration { name, span, .. } = statement {
+                self.declare(name, BindingKind::Constant, span);
+            }
+        }
+
+        for statement in program {
+            if let Statement::ConstDeclaration {
+                name, value, span, ..
+            } = statement
+                && !self.is_constant(value)
+            {
+                self.errors.push(CarbideCoreError::NonConstantInitializer {
+                    name: name.clone(),
+                    span: span.clone(),
+                });
+            }
+        }
+
+        for statement in program {
+            self.visit_statement(statement);
+        }
+    }
+
+    /// Check whether `expr` can be evaluated at compile time
+    fn is_constant(&self, expr: &Expression) -> bool {
+        match expr {
+            Expression::Literal(_) => true,
+            Expression::Identifier { name, .. } => self
+                .lookup(name)
+                .is_some_and(|b| b.kind == BindingKind::Constant),
+            Expression::BinaryOp { left, right, .. } => {
+                self.is_constant(left) && self.is_constant(right)
+            }
+            Expression::UnaryOp { operand, .. } => self.is_constant(operand),
+            Expression::Grouped(inner) => self.is_constant(inner),
+            Expression::Array(elements) | Expression::Tuple(elements) => {
+                elements.iter().all(|e| self.is_constant(e))
+            }
+            Expression::Map(entries) => entries
+                .iter()
+                .all(|e| self.is_constant(&e.key) && self.is_constant(&e.value)),
+            _ => false,
+        }
+    }
+
+    fn declare(&mut self, name: &str, kind: BindingKind, span: &Span) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.insert(
+                name.to_string(),
+                Binding {
+                    kind,
+                    span: span.clone(),
+                },
+            );
+        }
+    }
+
+    fn lookup(&self, name: &str) -> Option<&Binding> {
+        self.scopes.iter().rev().find_map(|scope| scope.get(name))
+    }
+
+    /// Run `check` in a new scope
+    fn scoped(&mut self, check: impl FnOnce(&mut Self)) {
+        self.scopes.push(HashMap::new());
+        check(self);
+        self.scopes.pop();
+    }
+
+    fn declare_parameters(&mut self, parameters: &[Parameter]) {
+        for parameter in parameters {
+            let kind = if parameter.mutable {
+                BindingKind::Mutable
+            } else {
+                BindingKind::Immutable
+            };
+            self.declare_pattern(&parameter.pattern, kind);
+        }
+    }
+
+    /// Declare every name bound by `pattern`, each declared at its own name
+    fn declare_pattern(&mut self, pattern: &Pattern, kind: BindingKind) {
+        for (name, span) in pattern.binding_spans() {
+            self.declare(name, kind, span);
+        }
+    }
+
+    /// Report an assignment to `target` if the binding it modifies isn't mutable
+    fn check_assignment(&mut self, target: &Expression, span: &Span) {
+        // Assigning to a field or element modifies the binding that holds it
+        let mut root = target;
+        while let Expression::MemberAccess { target, .. }
+        | Expression::Index { target, .. }
+        | Expression::Grouped(target) = root
+        {
+            root = target;
+        }
+
+        let Expression::Identifier { name, .. } = root else {
+            return;
+        };
+        let Some(binding) = self.lookup(name) else {
+            return;
+        };
+
+        let error = match binding.kind {
+            BindingKind::Mutable => return,
+            BindingKind::Immutable => CarbideCoreError::AssignToImmutable {
+                name: name.clone(),
+                span: span.clone(),
+                declaration: binding.span.clone(),
+            },
+            BindingKind::Constant => CarbideCoreError::AssignToConstant {
+                name: name.clone(),
+                span: span.clone(),
+                declaration: binding.span.clone(),
+            },
+        };
+        self.errors.push(error);
+    }
+}
+
+impl<'ast> Visitor<'ast> for MutabilityChecker {
+    fn visit_statement(&mut self, statement: &'ast Statement) {
+        match statement {
+            Statement::LetDeclaration {
+                mutable, pattern, ..
+            } => {
+                walk_statement(self, statement);
+                let kind = if *mutable {
+                    BindingKind::Mutable
+                } else {
+                    BindingKind::Immutable
+                };
+                self.declare_pattern(pattern, kind);
+            }
+            Statement::ConstDeclaration { name, span, .. } => {
+                walk_statement(self, statement);
+                self.declare(name, BindingKind::Constant, span);
+            }
+            Statement::FunctionDeclaration { parameters, .. } => self.scoped(|checker| {
+                checker.declare_parameters(parameters);
+                walk_statement(checker, statement);
+            }),
+            Statement::While { .. } | Statement::For { .. } | Statement::Block(_) => {
+                self.scoped(|checker| walk_statement(checker, statement));
+            }
+            _ => walk_statement(self, statement),
+        }
+    }
+
+    fn visit_method(&mut self, method: &'ast Method) {
+        self.scoped(|checker| {
+            checker.declare_parameters(&method.parameters);
+            walk_method(checker, method);
+        });
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        self.scoped(|checker| walk_block(checker, block));
+    }
+
+    fn visit_match_arm(&mut self, arm: &'ast MatchArm) {
+        self.scoped(|checker| {
+            checker.declare_pattern(&arm.pattern, BindingKind::Immutable);
+            walk_match_arm(checker, arm);
+        });
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression) {
+        match expression {
+            Expression::Lambda { parameters, .. } => self.scoped(|checker| {
+                checker.declare_parameters(parameters);
+                walk_expression(checker, expression);
+            }),
+            Expression::Assignment { target, span, .. } => {
+                walk_expression(self, expression);
+                self.check_assignment(target, span);
+            }
+            _ => walk_expression(self, expression),
+        }
+    }
+}
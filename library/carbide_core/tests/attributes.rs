@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod attributes {
+    use carbide_core::{
+        attributes::{
+            ArgumentKind, AttributeRegistry, AttributeSpec, AttributeTarget, attributes_of,
+            check_attributes, declarations_with, find_attribute,
+        },
+        errors::CarbideCoreError,
+    };
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{LiteralValue, Statement},
+        parser::CarbideParser,
+    };
+
+    fn parse(src: &str) -> Vec<Statement> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        result.ast
+    }
+
+    fn check(src: &str) -> Vec<CarbideCoreError> {
+        check_attributes(&parse(src), &AttributeRegistry::new())
+    }
+
+    #[test]
+    fn builtin_attributes() {
+        assert_eq!(
+            check(
+                "#[inline] fn f() {}\n#[test] fn t() { #[deprecated] let x = 1; }\n\
+                 #[deprecated(\"use g\")] pub fn old() {}"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn unknown_attribute() {
+        assert_eq!(
+            check("fn f() {\n    #[inlined] fn g() {}\n}"),
+            vec![CarbideCoreError::UnknownAttribute {
+                name: "inlined".to_string(),
+                span: 13..23,
+            }]
+        );
+    }
+
+    #[test]
+    fn misplaced_attribute() {
+        assert_eq!(
+            check("#[test] let x = 1;"),
+            vec![CarbideCoreError::MisplacedAttribute {
+                name: "test".to_string(),
+                target: AttributeTarget::Let,
+                span: 0..7,
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_arguments() {
+        let errors = check("#[inline(1)] fn f() {}\n#[deprecated(1)] fn g() {}");
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                CarbideCoreError::InvalidAttributeArguments { name: a, expected: x, .. },
+                CarbideCoreError::InvalidAttributeArguments { name: b, expected: y, .. },
+            ] if a == "inline" && x == "no arguments" && b == "deprecated" && y == "an optional string"
+        ));
+
+        let errors = check("#[deprecated(\"a\", \"b\")] fn f() {}");
+        assert_eq!(
+            errors[0].to_string(),
+            "`#[deprecated]` takes an optional string"
+        );
+    }
+
+    #[test]
+    fn duplicate_attribute() {
+        assert_eq!(
+            check("#[inline]\n#[inline] fn f() {}"),
+            vec![CarbideCoreError::DuplicateAttribute {
+                name: "inline".to_string(),
+                span: 10..19,
+                first: 0..9,
+            }]
+        );
+    }
+
+    #[test]
+    fn registered_attribute() {
+        let mut registry = AttributeRegistry::new();
+        assert!(!registry.is_known("since"));
+        registry.register(AttributeSpec {
+            arguments: vec![ArgumentKind::Int, ArgumentKind::Bool],
+            required: 1,
+            ..AttributeSpec::new("since", &[AttributeTarget::Let])
+        });
+        assert!(registry.is_known("since"));
+
+        let program = parse("#[since(2)] let a = 1;\n#[since(2, true)] let b = 1;");
+        assert_eq!(check_attributes(&program, &registry), vec![]);
+
+        let program = parse("#[since] let a = 1;");
+        assert!(matches!(
+            check_attributes(&program, &registry).as_slice(),
+            [CarbideCoreError::InvalidAttributeArguments { expected, .. }]
+                if expected == "an int and an optional bool"
+        ));
+
+        assert!(!check_attributes(&program, &AttributeRegistry::empty()).is_empty());
+    }
+
+    #[test]
+    fn query_attributes() {
+        let program = parse(
+            "#[test] fn a() {}\nfn b() { #[test] fn c() {} }\n\
+             #[deprecated(\"use a\")] fn d() {}",
+        );
+
+        let tests: Vec<_> = declarations_with(&program, "test")
+            .into_iter()
+            .filter_map(|statement| match statement {
+                Statement::FunctionDeclaration { name, .. } => Some(name.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(tests, ["a", "c"]);
+
+        let deprecated = find_attribute(attributes_of(&program[2]), "deprecated");
+        assert_eq!(
+            deprecated.map(|attribute| attribute.arguments.as_slice()),
+            Some([LiteralValue::String("use a".to_string())].as_slice())
+        );
+        assert!(find_attribute(attributes_of(&program[1]), "test").is_none());
+    }
+}
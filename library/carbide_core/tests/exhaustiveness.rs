@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod exhaustiveness {
+    use carbide_core::{errors::CarbideCoreError, exhaustiveness::check_matches};
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::parser::CarbideParser;
+
+    const SHAPE: &str = "enum Shape { Empty, Circle(float), Rect { w: float, h: float } }\n";
+
+    fn check(src: &str) -> Vec<CarbideCoreError> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+
+        check_matches(&result.ast)
+    }
+
+    fn missing(src: &str) -> Vec<String> {
+        match check(src).as_slice() {
+            [CarbideCoreError::NonExhaustiveMatch { missing, .. }] => missing.clone(),
+            other => panic!("Expected a single non-exhaustive match, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn all_variants_covered() {
+        let src = format!(
+            "{SHAPE}match s {{ Shape::Empty => 0, Shape::Circle(r) => 1, Shape::Rect {{ .. }} => 2 }}"
+        );
+        assert_eq!(check(&src), vec![]);
+    }
+
+    #[test]
+    fn missing_variants() {
+        let src = format!("{SHAPE}match s {{ Shape::Circle(_) => 1 }}");
+        assert_eq!(missing(&src), vec!["Shape::Empty", "Shape::Rect { .. }"]);
+    }
+
+    #[test]
+    fn wildcard_covers_everything() {
+        let src = format!("{SHAPE}match s {{ Shape::Empty => 0, _ => 1 }}");
+        assert_eq!(check(&src), vec![]);
+    }
+
+    #[test]
+    fn missing_bool() {
+        assert_eq!(missing("match b { true => 1 }"), vec!["false"]);
+        assert_eq!(check("match b { true => 1, false => 0 }"), vec![]);
+    }
+
+    #[test]
+    fn missing_tuple_combination() {
+        assert_eq!(
+            missing("match p { (true, _) => 1, (_, true) => 2 }"),
+            vec!["(false, false)"]
+        );
+    }
+
+    #[test]
+    fn literals_need_wildcard() {
+        assert_eq!(missing("match n { 1 => 1, 2 => 2 }"), vec!["_"]);
+        assert_eq!(check("match n { 1 => 1, n => n }"), vec![]);
+    }
+
+    #[test]
+    fn nested_patterns() {
+        let src = format!(
+            "{SHAPE}enum Wrap {{ Some(Shape), None }}\n\
+             match w {{ Wrap::Some(Shape::Circle(_)) => 1, Wrap::None => 0 }}"
+        );
+        assert_eq!(
+            missing(&src),
+            vec!["Wrap::Some(Shape::Empty)", "Wrap::Some(Shape::Rect { .. })"]
+        );
+    }
+
+    #[test]
+    fn guarded_arms_do_not_cover() {
+        assert_eq!(
+            missing("match b { true => 1, false if c => 0 }"),
+            vec!["false"]
+        );
+    }
+
+    #[test]
+    fn unreachable_after_wildcard() {
+        let errors = check("match b {\n _ => 1,\n true => 2 }");
+        assert_eq!(errors, vec![CarbideCoreError::UnreachablePattern(20..24)]);
+    }
+
+    #[test]
+    fn unreachable_duplicate_variant() {
+        let src = format!("{SHAPE}match s {{ Shape::Empty => 0, Shape::Empty => 1, _ => 2 }}");
+        let errors = check(&src);
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(errors[0], CarbideCoreError::UnreachablePattern(_)));
+    }
+
+    #[test]
+    fn nested_match_in_function() {
+        let errors = check("fn f(b: bool) { let x = match b { true => 1 }; }");
+        assert!(matches!(
+            errors.as_slice(),
+            [CarbideCoreError::NonExhaustiveMatch { span, .. }] if *span == (24..31)
+        ));
+    }
+
+    #[test]
+    fn unknown_enum_is_skipped() {
+        assert_eq!(check("match s { Other::A => 1 }"), vec![]);
+    }
+
+    #[test]
+    fn array_lengths() {
+        assert_eq!(
+            missing("match xs { [] => 0, [x] => 1 }"),
+            vec!["[_, _, ..]"]
+        );
+        assert_eq!(
+            missing("match xs { [] => 0, [_, _, ..rest] => 2 }"),
+            vec!["[_]"]
+        );
+        assert_eq!(
+            check("match xs { [] => 0, [x] => 1, [first, .., last] => 2 }"),
+            vec![]
+        );
+        assert_eq!(check("match xs { [..] => 0 }"), vec![]);
+    }
+
+    #[test]
+    fn array_elements() {
+        assert_eq!(
+            missing("match xs { [true, ..] => 1, [] => 0 }"),
+            vec!["[false, ..]"]
+        );
+        assert_eq!(
+            missing("match xs { [.., true] => 1, [] => 0 }"),
+            vec!["[.., false]"]
+        );
+    }
+
+    #[test]
+    fn unreachable_array_arm() {
+        let errors = check("match xs {\n [..rest] => 1,\n [x] => 2 }");
+        assert_eq!(errors, vec![CarbideCoreError::UnreachablePattern(28..31)]);
+    }
+
+    #[test]
+    fn irrefutable_destructuring() {
+        let src = format!(
+            "{SHAPE}struct Point {{ x: float, y: float }}\n\
+             let (a, [b, ..rest]) = pair;\n\
+             let Point {{ x, .. }} = p;\n\
+             fn f((a, b): (int, int), Point {{ x, y }}: Point) {{}}\n\
+             let g = |[first, ..]| => first;"
+        );
+        assert_eq!(check(&src), vec![]);
+    }
+
+    #[test]
+    fn refutable_let() {
+        let src = format!("{SHAPE}let Shape::Circle(r) = s;");
+        assert_eq!(
+            check(&src),
+            vec![CarbideCoreError::RefutablePattern {
+                missing: vec!["Shape::Empty".to_string(), "Shape::Rect { .. }".to_string()],
+                span: 69..85,
+            }]
+        );
+        assert_eq!(
+            check("let (1, x) = p;")[0].to_string(),
+            "Refutable pattern, `(_, _)` not covered"
+        );
+    }
+
+    #[test]
+    fn refutable_parameter() {
+        let errors = check("fn f(true: bool) {}\nlet g = |[x, false]| => x;");
+        assert!(matches!(
+            errors.as_slice(),
+            [
+                CarbideCoreError::RefutablePattern { span: a, .. },
+                CarbideCoreError::RefutablePattern { missing, span: b },
+            ] if *a == (5..9) && *b == (29..39) && missing == &["[]", "[_]", "[_, _, _, ..]"]
+        ));
+    }
+
+    #[test]
+    fn fixed_length_arrays_are_refutable() {
+        // Array types have no length, so only a `..` makes an array pattern irrefutable
+        assert_eq!(
+            check("let [a, b] = xs;"),
+            vec![CarbideCoreError::RefutablePattern {
+                missing: vec![
+                    "[]".to_string(),
+                    "[_]".to_string(),
+                    "[_, _, _, ..]".to_string()
+                ],
+                span: 4..10,
+            }]
+        );
+        assert_eq!(
+            check("fn f([a]: [int]) {}"),
+            vec![CarbideCoreError::RefutablePattern {
+                missing: vec!["[]".to_string(), "[_, _, ..]".to_string()],
+                span: 5..8,
+            }]
+        );
+        assert_eq!(
+            check("let [[a, b], ..] = xs;"),
+            vec![CarbideCoreError::RefutablePattern {
+                missing: vec![
+                    "[[], ..]".to_string(),
+                    "[[_], ..]".to_string(),
+                    "[[_, _, _, ..], ..]".to_string(),
+                ],
+                span: 4..16,
+            }]
+        );
+        assert_eq!(
+            check(
+                "let [a, ..] = xs;
+let [.., b] = xs;"
+            ),
+            vec![]
+        );
+    }
+}
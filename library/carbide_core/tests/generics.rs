@@ -0,0 +1,165 @@
+#[cfg(test)]
+mod generics {
+    use carbide_core::{errors::CarbideCoreError, generics::check_generics};
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{nodes::Statement, parser::CarbideParser};
+
+    fn parse(src: &str) -> Vec<Statement> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        result.ast
+    }
+
+    fn check(src: &str) -> Vec<CarbideCoreError> {
+        check_generics(&parse(src))
+    }
+
+    #[test]
+    fn valid_generics() {
+        assert_eq!(
+            check(
+                "fn first<T>(xs: [T]) -> T { return xs[0]; }\n\
+                 fn pair<A, B>(a: A, b: B?) -> (A, B) { return (a, b); }\n\
+                 let a = first::<int>([1, 2]);\n\
+                 let b = first::<[string]>([[\"a\"]]);\n\
+                 let c = pair::<bool, (int, float)>(!true, (1, 2.0));\n\
+                 let d = util::first::<int>(xs);"
+            ),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn duplicate_type_parameter() {
+        assert_eq!(
+            check("fn f<T, U, T>(x: T) {}"),
+            vec![CarbideCoreError::DuplicateTypeParameter {
+                name: "T".to_string(),
+                span: 11..12,
+                first: 5..6,
+            }]
+        );
+    }
+
+    #[test]
+    fn wrong_type_argument_count() {
+        assert_eq!(
+            check("fn f<T>(x: T) {}\nf::<int, bool>(1);\nfn g(x: int) {}\ng::<int>(1);"),
+            vec![
+                CarbideCoreError::WrongTypeArgumentCount {
+                    name: "f".to_string(),
+                    expected: 1,
+                    found: 2,
+                    span: 17..31,
+                },
+                CarbideCoreError::WrongTypeArgumentCount {
+                    name: "g".to_string(),
+                    expected: 0,
+                    found: 1,
+                    span: 52..60,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn mismatched_argument_type() {
+        assert_eq!(
+            check("fn first<T>(xs: [T]) -> T { return xs[0]; }\nfirst::<int>([\"a\"]);"),
+            vec![CarbideCoreError::MismatchedArgumentType {
+                function: "first".to_string(),
+                expected: "[int]".to_string(),
+                found: "[string]".to_string(),
+                span: 44..56,
+            }]
+        );
+    }
+
+    #[test]
+    fn missing_type_arguments() {
+        let errors = check(
+            "fn id<T>(x: T) -> T { return x; }\nlet a = id(1);\nlet b = id;\nfn f() {}\nf();",
+        );
+        assert_eq!(
+            errors,
+            vec![
+                CarbideCoreError::MissingTypeArguments {
+                    function: "id".to_string(),
+                    span: 42..44,
+                },
+                CarbideCoreError::MissingTypeArguments {
+                    function: "id".to_string(),
+                    span: 57..59,
+                },
+            ]
+        );
+        assert_eq!(
+            errors[0].to_string(),
+            "Generic function `id` is used without type arguments"
+        );
+    }
+
+    #[test]
+    fn local_bindings_hide_generic_functions() {
+        // Only the initializer of `let id = id;` refers to the function
+        assert_eq!(
+            check(
+                "fn id<T>(x: T) -> T { return x; }\n\
+                 fn f(id: int) { id; }\n\
+                 let g = |id| => id;\n\
+                 let h = match 1 { id => id };\n\
+                 let id = id;\n\
+                 id;"
+            ),
+            vec![CarbideCoreError::MissingTypeArguments {
+                function: "id".to_string(),
+                span: 115..117,
+            }]
+        );
+    }
+
+    const SHOW: &str = "trait Show { fn show(self) -> string; }\n\
+                        struct P { x: int }\n\
+                        impl Show for P { fn show(self) -> string { \"p\" } }\n\
+                        fn f<T: Show>(x: T) {}\n";
+
+    #[test]
+    fn unknown_trait_bound() {
+        let errors = check(
+            "trait Show { fn show(self) -> string; }\n\
+             import fmt.{Display};\n\
+             fn f<T: Shwo, U: Show + Display>(x: T) {}",
+        );
+        assert_eq!(
+            errors,
+            vec![CarbideCoreError::UnknownTraitBound {
+                name: "Shwo".to_string(),
+                span: 67..68,
+            }]
+        );
+        assert_eq!(errors[0].to_string(), "Unknown trait `Shwo` in bound");
+    }
+
+    #[test]
+    fn unsatisfied_bound() {
+        // `f::<U>` depends on `U`, so it's checked once `g` is monomorphized
+        let errors = check(&format!(
+            "{SHOW}f::<P>(P {{ x: 1 }});\nf::<int>(1);\nfn g<U>(u: U) {{ f::<U>(u); }}"
+        ));
+        assert_eq!(
+            errors,
+            vec![CarbideCoreError::UnsatisfiedBound {
+                function: "f".to_string(),
+                trait_name: "Show".to_string(),
+                ty: "int".to_string(),
+                span: 155..163,
+            }]
+        );
+        assert_eq!(errors[0].to_string(), "`int` doesn't implement `Show`");
+    }
+}
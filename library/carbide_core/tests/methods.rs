@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod methods {
+    use carbide_core::{
+        errors::CarbideCoreError,
+        methods::{MethodTable, check_methods},
+    };
+    use carbide_errors::reporter::ErrorReporter;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{nodes::Statement, parser::CarbideParser};
+
+    fn parse(src: &str) -> Vec<Statement> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        result.ast
+    }
+
+    fn check(src: &str) -> Vec<CarbideCoreError> {
+        check_methods(&parse(src))
+    }
+
+    const SHAPES: &str = "trait Shape {\n\
+                          fn area(self) -> float;\n\
+                          fn describe(self) -> string { \"shape\" }\n\
+                          }\n\
+                          struct Circle { r: float, on_draw: (float) -> float }\n";
+
+    #[test]
+    fn valid_methods() {
+        let src = [
+            SHAPES,
+            "impl Shape for Circle { fn area(self) -> float { self.r * self.r } }\n\
+             impl Circle {\n\
+             fn new(r: float) -> Self { Circle { r, on_draw: |x| => x } }\n\
+             fn scale(self, k: float) -> Circle { Circle::new(self.r * k) }\n\
+             }\n\
+             let c = Circle::new(1.0);\n\
+             let a = c.area() + c.scale(2.0).area();\n\
+             let d = c.describe();\n\
+             let e = c.on_draw(1.0);\n\
+             fn total<T: Shape>(s: T) -> float { s.area() }\n\
+             fn other<T: Remote>(s: T) { s.anything(); }\n\
+             let f = unknown.whatever(1, 2);",
+        ]
+        .concat();
+        assert_eq!(check(&src), vec![]);
+    }
+
+    #[test]
+    fn missing_trait_method() {
+        assert_eq!(
+            check(&format!("{SHAPES}impl Shape for Circle {{}}")),
+            vec![CarbideCoreError::MissingTraitMethod {
+                trait_name: "Shape".to_string(),
+                target: "Circle".to_string(),
+                missing: vec!["area".to_string()],
+                span: 134..155,
+            }]
+        );
+    }
+
+    #[test]
+    fn unknown_trait_method_and_mismatched_signature() {
+        assert_eq!(
+            check(&format!(
+                "{SHAPES}impl Shape for Circle {{\n\
+                 fn area(self, k: float) -> float {{ k }}\n\
+                 fn perimeter(self) -> float {{ 0.0 }}\n\
+                 }}"
+            )),
+            vec![
+                CarbideCoreError::MismatchedMethodSignature {
+                    name: "area".to_string(),
+                    expected: "fn area(self) -> float".to_string(),
+                    found: "fn area(self, float) -> float".to_string(),
+                    span: 161..165,
+                    declaration: 17..21,
+                },
+                CarbideCoreError::UnknownTraitMethod {
+                    trait_name: "Shape".to_string(),
+                    name: "perimeter".to_string(),
+                    span: 200..209,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn self_in_signatures() {
+        assert_eq!(
+            check(
+                "trait Same { fn same(self, other: Self) -> bool; }\n\
+                 struct P {}\n\
+                 impl Same for P { fn same(self, other: P) -> bool { true } }\n\
+                 struct Q {}\n\
+                 impl Same for Q { fn same(self, other: P) { } }"
+            ),
+            vec![CarbideCoreError::MismatchedMethodSignature {
+                name: "same".to_string(),
+                expected: "fn same(self, Q) -> bool".to_string(),
+                found: "fn same(self, P)".to_string(),
+                span: 157..161,
+                declaration: 16..20,
+            }]
+        );
+    }
+
+    #[test]
+    fn duplicates() {
+        assert_eq!(
+            check(
+                "trait T { fn f(self); fn f(self); }\n\
+                 struct S {}\n\
+                 impl T for S { fn f(self) {} }\n\
+                 impl T for S { fn f(self) {} }\n\
+                 impl S { fn g(self) {} }\n\
+                 impl S { fn g(self) {} }"
+            ),
+            vec![
+                CarbideCoreError::DuplicateMethod {
+                    name: "f".to_string(),
+                    span: 25..26,
+                    first: 13..14,
+                },
+                CarbideCoreError::DuplicateImpl {
+                    trait_name: "T".to_string(),
+                    target: "S".to_string(),
+                    span: 79..91,
+                    first: 48..60,
+                },
+                CarbideCoreError::DuplicateMethod {
+                    name: "g".to_string(),
+                    span: 147..148,
+                    first: 122..123,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn method_calls() {
+        assert_eq!(
+            check(&format!(
+                "{SHAPES}\
+                 impl Shape for Circle {{ fn area(self) -> float {{ 1.0 }} }}\n\
+                 impl Circle {{ fn new(r: float) -> Circle {{ Circle::new(r) }} }}\n\
+                 let c: Circle = Circle::new(1.0);\n\
+                 c.perimeter();\n\
+                 c.new(2.0);\n\
+                 Circle::new(1.0).area(3);"
+            )),
+            vec![
+                CarbideCoreError::UnknownMethod {
+                    target: "Circle".to_string(),
+                    name: "perimeter".to_string(),
+                    span: 289..298,
+                },
+                CarbideCoreError::NotAMethod {
+                    target: "Circle".to_string(),
+                    name: "new".to_string(),
+                    span: 304..307,
+                },
+                CarbideCoreError::WrongMethodArgumentCount {
+                    name: "area".to_string(),
+                    expected: 0,
+                    found: 1,
+                    span: 331..335,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ambiguous_method() {
+        assert_eq!(
+            check(
+                "trait A { fn name(self) -> string; }\n\
+                 trait B { fn name(self) -> string; }\n\
+                 struct S {}\n\
+                 impl A for S { fn name(self) -> string { \"a\" } }\n\
+                 impl B for S { fn name(self) -> string { \"b\" } }\n\
+                 let s = S {};\n\
+                 s.name();"
+            ),
+            vec![CarbideCoreError::AmbiguousMethod {
+                target: "S".to_string(),
+                name: "name".to_string(),
+                traits: vec!["A".to_string(), "B".to_string()],
+                span: 200..204,
+            }]
+        );
+    }
+
+    #[test]
+    fn inherent_methods_come_first() {
+        let program = parse(
+            "trait A { fn name(self) -> string; }\n\
+             struct S {}\n\
+             impl A for S { fn name(self) -> string { \"a\" } }\n\
+             impl S { fn name(self) -> string { \"s\" } }",
+        );
+        let table = MethodTable::new(&program);
+
+        let resolved = table.resolve("S", "name");
+        assert_eq!(resolved.len(), 1);
+        assert_eq!(resolved[0].trait_name, None);
+        assert!(table.resolve("S", "missing").is_empty());
+        assert!(table.is_known_type("S"));
+        assert!(!table.is_known_type("int"));
+    }
+
+    #[test]
+    fn methods_of_type_parameters() {
+        assert_eq!(
+            check(
+                "trait Shape {\n\
+                 fn area(self) -> float;\n\
+                 fn twice(self) -> float { self.area() * 2.0 + self.size() }\n\
+                 }\n\
+                 fn f<T: Shape>(s: T) { s.area(); s.perimeter(); }\n\
+                 fn g<T>(x: T) { x.area(); }"
+            ),
+            vec![
+                CarbideCoreError::UnknownMethod {
+                    target: "Self".to_string(),
+                    name: "size".to_string(),
+                    span: 89..93,
+                },
+                CarbideCoreError::UnknownMethod {
+                    target: "T".to_string(),
+                    name: "perimeter".to_string(),
+                    span: 135..144,
+                },
+                CarbideCoreError::UnknownMethod {
+                    target: "T".to_string(),
+                    name: "area".to_string(),
+                    span: 168..172,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn shadowed_names_lose_their_type() {
+        assert_eq!(
+            check(
+                "struct S {}\n\
+                 let s = S {};\n\
+                 { let s = other(); s.anything(); }\n\
+                 match pair { (s, _) => s.anything() };\n\
+                 let f = |s| => s.anything();\n\
+                 s.missing();"
+            ),
+            vec![CarbideCoreError::UnknownMethod {
+                target: "S".to_string(),
+                name: "missing".to_string(),
+                span: 131..138,
+            }]
+        );
+    }
+
+    #[test]
+    fn report() {
+        let src = &format!("{SHAPES}impl Shape for Circle {{}}");
+        let errors = check(src);
+
+        let mut reporter = ErrorReporter::new();
+        reporter.add_source("test.cb", src);
+        let report = reporter
+            .format_error("test.cb", &errors[0])
+            .expect("Expected formatting to succeed");
+        assert!(report.contains("E2050"), "{report}");
+        assert!(
+            report.contains("Impl of `Shape` for `Circle` is missing `area`"),
+            "{report}"
+        );
+    }
+}
@@ -0,0 +1,212 @@
+#[cfg(test)]
+mod modules {
+    use std::fs;
+    use std::path::PathBuf;
+
+    use carbide_core::{errors::CarbideCoreError, modules::ModuleLoader};
+
+    /// Write `files` under a fresh directory named after the test, returning its path
+    fn write_tree(test: &str, files: &[(&str, &str)]) -> PathBuf {
+        let root = PathBuf::from(env!("CARGO_TARGET_TMPDIR"))
+            .join("modules")
+            .join(test);
+        let _ = fs::remove_dir_all(&root);
+
+        for (path, src) in files {
+            let file = root.join(path);
+            if let Some(parent) = file.parent() {
+                fs::create_dir_all(parent).expect("Expected creating directories to succeed");
+            }
+            fs::write(file, src).expect("Expected writing the file to succeed");
+        }
+
+        root
+    }
+
+    #[test]
+    fn resolves_nested_imports() {
+        let root = write_tree(
+            "resolves_nested_imports",
+            &[
+                (
+                    "main.cb",
+                    "import math;\nimport util.strings.{trim, split};",
+                ),
+                (
+                    "math.cb",
+                    "pub fn add(a: int, b: int) -> int { return a + b; }",
+                ),
+                (
+                    "util/strings.cb",
+                    "import math;\npub fn trim(s: string) {}\npub fn split(s: string) {}",
+                ),
+            ],
+        );
+
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert!(!main.has_errors(), "{:?}", main.errors);
+
+        let names = loader
+            .modules()
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>();
+        // Shared imports are loaded once, before the modules that import them
+        assert_eq!(names, vec!["math", "util.strings", "main"]);
+        assert_eq!(
+            loader.get("util.strings").map(|m| m.file.clone()),
+            Some(root.join("util").join("strings.cb"))
+        );
+    }
+
+    #[test]
+    fn unresolved_module() {
+        let root = write_tree(
+            "unresolved_module",
+            &[("main.cb", "import missing.module;")],
+        );
+
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert_eq!(
+            main.errors,
+            vec![CarbideCoreError::UnresolvedImport {
+                path: "missing.module".to_string(),
+                span: 0..21,
+            }]
+        );
+    }
+
+    #[test]
+    fn unresolved_and_private_items() {
+        let root = write_tree(
+            "unresolved_and_private_items",
+            &[
+                ("main.cb", "import shapes.{Shape, area, hidden};"),
+                (
+                    "shapes.cb",
+                    "pub enum Shape { Empty }\npub fn area() {}\nstruct hidden {}",
+                ),
+            ],
+        );
+
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert!(matches!(
+            main.errors.as_slice(),
+            [CarbideCoreError::PrivateImport { item, module, .. }]
+                if item == "hidden" && module == "shapes"
+        ));
+
+        let root = write_tree(
+            "unresolved_item",
+            &[("main.cb", "import shapes.{nothing};"), ("shapes.cb", "")],
+        );
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert!(matches!(
+            main.errors.as_slice(),
+            [CarbideCoreError::UnresolvedImport { path, .. }] if path == "shapes.nothing"
+        ));
+    }
+
+    #[test]
+    fn import_cycle() {
+        let root = write_tree(
+            "import_cycle",
+            &[
+                ("a.cb", "import b;"),
+                ("b.cb", "import c;"),
+                ("c.cb", "import a;"),
+            ],
+        );
+
+        let mut loader = ModuleLoader::new(&root);
+        loader.load("a").expect("Expected a to load");
+
+        let c = loader.get("c").expect("Expected c to be loaded");
+        assert_eq!(
+            c.errors,
+            vec![CarbideCoreError::ImportCycle {
+                cycle: vec![
+                    "a".to_string(),
+                    "b".to_string(),
+                    "c".to_string(),
+                    "a".to_string()
+                ],
+                span: 0..8,
+            }]
+        );
+        assert!(loader.get("a").is_some_and(|a| a.errors.is_empty()));
+    }
+
+    #[test]
+    fn self_import() {
+        let root = write_tree("self_import", &[("main.cb", "import main;")]);
+
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert!(matches!(
+            main.errors.as_slice(),
+            [CarbideCoreError::ImportCycle { cycle, .. }] if cycle.len() == 2
+        ));
+    }
+
+    #[test]
+    fn keeps_parser_errors() {
+        let root = write_tree(
+            "keeps_parser_errors",
+            &[("main.cb", "import util;"), ("util.cb", "pub let x = 1;")],
+        );
+
+        let mut loader = ModuleLoader::new(&root);
+        loader.load("main").expect("Expected main to load");
+        assert!(
+            loader
+                .get("util")
+                .is_some_and(|u| !u.parser_errors.is_empty())
+        );
+    }
+
+    #[test]
+    fn keeps_items_after_lexer_errors() {
+        let root = write_tree(
+            "keeps_items_after_lexer_errors",
+            &[
+                ("main.cb", "import util.{area};"),
+                ("util.cb", "pub fn area() {}\nlet s = \"open"),
+            ],
+        );
+
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert_eq!(main.errors, vec![]);
+        assert!(
+            loader
+                .get("util")
+                .is_some_and(|u| !u.lexer_errors.is_empty())
+        );
+    }
+
+    #[test]
+    fn unreadable_module() {
+        let root = write_tree("unreadable_module", &[("main.cb", "import util;")]);
+        // A directory where the module's file should be can't be read as one
+        fs::create_dir_all(root.join("util.cb")).expect("Expected creating directories to succeed");
+
+        let mut loader = ModuleLoader::new(&root);
+        let main = loader.load("main").expect("Expected main to load");
+        assert!(matches!(
+            main.errors.as_slice(),
+            [CarbideCoreError::UnreadableImport { path, span, .. }]
+                if path == "util" && *span == (0..11)
+        ));
+    }
+
+    #[test]
+    fn missing_entry() {
+        let root = write_tree("missing_entry", &[]);
+        assert!(ModuleLoader::new(&root).load("main").is_err());
+    }
+}
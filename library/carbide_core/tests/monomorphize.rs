@@ -0,0 +1,118 @@
+#[cfg(test)]
+mod monomorphize {
+    use carbide_core::{
+        errors::CarbideCoreError,
+        monomorphize::{instance_name, monomorphize},
+    };
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Statement, Type},
+        parser::CarbideParser,
+        print::print_program,
+    };
+
+    fn parse(src: &str) -> Vec<Statement> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        result.ast
+    }
+
+    fn monomorphized(src: &str) -> String {
+        let result = monomorphize(&parse(src));
+        assert_eq!(result.errors, vec![]);
+        print_program(&result.statements).expect("Expected the program to print")
+    }
+
+    #[test]
+    fn instance_names() {
+        assert_eq!(instance_name("first", &[Type::named("int")]), "first<int>");
+        assert_eq!(
+            instance_name(
+                "pair",
+                &[
+                    Type::Array(Box::new(Type::named("int"))),
+                    Type::Tuple(vec![Type::named("bool"), Type::named("float")]),
+                ]
+            ),
+            "pair<[int], (bool, float)>"
+        );
+    }
+
+    #[test]
+    fn instances_replace_the_generic() {
+        assert_eq!(
+            monomorphized(
+                "let a = 1;\nfn first<T>(xs: [T]) -> T { return xs[0]; }\n\
+                 let b = first::<int>([1]);\nlet c = first::<string>([\"a\"]);\n\
+                 let d = first::<int>([2]);"
+            ),
+            "let a = 1;\n\
+             fn first<int>(xs: [int]) -> int {\n    return xs[0];\n}\n\
+             fn first<string>(xs: [string]) -> string {\n    return xs[0];\n}\n\
+             let b = first<int>([1]);\n\
+             let c = first<string>([\"a\"]);\n\
+             let d = first<int>([2]);\n"
+        );
+    }
+
+    #[test]
+    fn nested_instantiation() {
+        assert_eq!(
+            monomorphized(
+                "fn id<T>(x: T) -> T { return x; }\n\
+                 fn twice<T>(x: T) -> (T, T) { return (id::<T>(x), id::<T>(x)); }\n\
+                 let a = twice::<[int]>([1]);"
+            ),
+            "fn id<[int]>(x: [int]) -> [int] {\n    return x;\n}\n\
+             fn twice<[int]>(x: [int]) -> ([int], [int]) {\n    \
+             return (id<[int]>(x), id<[int]>(x));\n}\n\
+             let a = twice<[int]>([1]);\n"
+        );
+    }
+
+    #[test]
+    fn uninstantiated_generics_are_dropped() {
+        assert_eq!(
+            monomorphized("fn id<T>(x: T) -> T { return x; }\nfn f(x: int) {}\nf(1);"),
+            "fn f(x: int) {}\nf(1);\n"
+        );
+    }
+
+    #[test]
+    fn polymorphic_recursion() {
+        let result = monomorphize(&parse(
+            "fn grow<T>(x: T) { grow::<[T]>([x]); }\ngrow::<int>(1);",
+        ));
+        assert!(matches!(
+            result.errors.as_slice(),
+            [CarbideCoreError::TooManyInstances { function, .. }] if function == "grow"
+        ));
+    }
+
+    #[test]
+    fn unsatisfied_bound_in_instance() {
+        let result = monomorphize(&parse(
+            "trait Show { fn show(self) -> string; }\n\
+             struct P { x: int }\n\
+             impl Show for P { fn show(self) -> string { \"p\" } }\n\
+             fn f<T: Show>(x: T) {}\n\
+             fn g<U>(u: U) { f::<U>(u); }\n\
+             g::<P>(P { x: 1 });\n\
+             g::<[int]>([1]);",
+        ));
+        assert_eq!(
+            result.errors,
+            vec![CarbideCoreError::UnsatisfiedBound {
+                function: "f".to_string(),
+                trait_name: "Show".to_string(),
+                ty: "[int]".to_string(),
+                span: 151..157,
+            }]
+        );
+    }
+}
@@ -0,0 +1,171 @@
+#[cfg(test)]
+mod mutability {
+    use carbide_core::{errors::CarbideCoreError, mutability::check_mutability};
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::parser::CarbideParser;
+
+    fn check(src: &str) -> Vec<CarbideCoreError> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+
+        check_mutability(&result.ast)
+    }
+
+    #[test]
+    fn assign_to_mutable() {
+        assert_eq!(check("let mut x = 1; x = 2;"), vec![]);
+    }
+
+    #[test]
+    fn assign_to_immutable() {
+        assert_eq!(
+            check("let x = 1;\nx = 2;"),
+            vec![CarbideCoreError::AssignToImmutable {
+                name: "x".to_string(),
+                span: 11..16,
+                declaration: 4..5,
+            }]
+        );
+    }
+
+    #[test]
+    fn assign_to_constant() {
+        assert_eq!(
+            check("const MAX: int = 1;\nfn f() { MAX = 2; }"),
+            vec![CarbideCoreError::AssignToConstant {
+                name: "MAX".to_string(),
+                span: 29..36,
+                declaration: 6..9,
+            }]
+        );
+    }
+
+    #[test]
+    fn assign_inside_interpolation() {
+        assert_eq!(
+            check("let x = 1; let s = \"{x = 2}\";"),
+            vec![CarbideCoreError::AssignToImmutable {
+                name: "x".to_string(),
+                span: 21..26,
+                declaration: 4..5,
+            }]
+        );
+    }
+
+    #[test]
+    fn assign_to_field_of_immutable() {
+        let errors = check("let p = Point { x: 1 }; p.x = 2; let mut q = [1]; q[0] = 2;");
+        assert!(matches!(
+            errors.as_slice(),
+            [CarbideCoreError::AssignToImmutable { name, .. }] if name == "p"
+        ));
+    }
+
+    #[test]
+    fn parameters_are_immutable() {
+        let errors = check("fn f(a: int, mut b: int) { a = 1; b = 2; }");
+        assert!(matches!(
+            errors.as_slice(),
+            [CarbideCoreError::AssignToImmutable { name, declaration, .. }]
+                if name == "a" && *declaration == (5..6)
+        ));
+    }
+
+    #[test]
+    fn shadowing() {
+        assert_eq!(check("let x = 1; { let mut x = 2; x = 3; }"), vec![]);
+        assert_eq!(check("let mut x = 1; fn f(x: int) {} x = 2;"), vec![]);
+        assert_eq!(check("let mut x = 1; { let x = 2; } x = 3;").len(), 0);
+        assert_eq!(check("let mut x = 1; { let x = 2; x = 3; }").len(), 1);
+    }
+
+    #[test]
+    fn match_bindings_are_immutable() {
+        let errors = check("let mut n = 1; match n { n => n = 2 }");
+        assert!(matches!(
+            errors.as_slice(),
+            [CarbideCoreError::AssignToImmutable { name, .. }] if name == "n"
+        ));
+    }
+
+    #[test]
+    fn lambda_captures() {
+        assert_eq!(
+            check("let mut total = 0; let add = |x| => total = total + x;"),
+            vec![]
+        );
+        assert_eq!(check("let total = 0; let add = |x| => total = x;").len(), 1);
+    }
+
+    #[test]
+    fn block_expressions_are_scoped() {
+        let errors =
+            check("let x = 1; let y = if c { let mut x = 2; x = 3; x } else { x = 4; x };");
+        assert!(matches!(
+            errors.as_slice(),
+            [CarbideCoreError::AssignToImmutable { span, .. }] if *span == (59..64)
+        ));
+    }
+
+    #[test]
+    fn unknown_names_are_ignored() {
+        assert_eq!(check("global = 1;"), vec![]);
+    }
+
+    #[test]
+    fn constant_initializers() {
+        assert_eq!(
+            check("const A: int = 1 + 2;\nconst B: [int] = [A, (A * 2)];\nconst C: bool = !true;"),
+            vec![]
+        );
+    }
+
+    #[test]
+    fn non_constant_initializer() {
+        assert_eq!(
+            check("let x = 1;\nconst A: int = x + 1;\nconst B: int = f();"),
+            vec![
+                CarbideCoreError::NonConstantInitializer {
+                    name: "A".to_string(),
+                    span: 17..18,
+                },
+                CarbideCoreError::NonConstantInitializer {
+                    name: "B".to_string(),
+                    span: 39..40,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn destructured_bindings() {
+        assert_eq!(
+            check(
+                "let mut (a, [b, ..rest]) = p; a = 1; rest = [];\nfn f(mut (x, y): (int, int)) { y = x; }"
+            ),
+            vec![]
+        );
+        // Each binding is declared at its own name rather than the whole pattern
+        assert_eq!(
+            check("let (a, Point { x, .. }) = p;\nx = 2;"),
+            vec![CarbideCoreError::AssignToImmutable {
+                name: "x".to_string(),
+                span: 30..35,
+                declaration: 16..17,
+            }]
+        );
+        assert_eq!(
+            check("fn f([a, ..rest]: [int]) { rest = []; }"),
+            vec![CarbideCoreError::AssignToImmutable {
+                name: "rest".to_string(),
+                span: 27..36,
+                declaration: 11..15,
+            }]
+        );
+    }
+}
@@ -27,14 +27,54 @@ macro_rules! error_codes {
             E1100: 1100, // Parser::CastFailed
             E1001: 1001, // Parser::UnexpectedEOF
             E1002: 1002, // Parser::UnexpectedToken
+            E1003: 1003, // Parser::MissingSemicolon
             E1010: 1010, // Parser::ExpectedIdentifier
             E1011: 1011, // Parser::ExpectedExpression
             E1020: 1020, // Parser::TooManyParameters
             E1021: 1021, // Parser::TooManyArguments
+            E1022: 1022, // Parser::NestingTooDeep
+            E1023: 1023, // Parser::TooManyTokens
             E1030: 1030, // Parser::InvalidAssignmentTarget
             E1040: 1040, // Parser::BreakOutsideLoop
             E1041: 1041, // Parser::ContinueOutsideLoop
             E1042: 1042, // Parser::ReturnOutsideFunction
+            E1043: 1043, // Parser::ConstOutsideTopLevel
+            E1050: 1050, // Parser::MisplacedAttribute
+            E1060: 1060, // Parser::MultipleRestPatterns
+            E1070: 1070, // Parser::MisplacedStructBase
+
+            E2000: 2000, // Core::Generic
+            E2100: 2100, // Core::CastFailed
+            E2001: 2001, // Core::NonExhaustiveMatch
+            E2002: 2002, // Core::UnreachablePattern
+            E2003: 2003, // Core::RefutablePattern
+            E2010: 2010, // Core::UnresolvedImport
+            E2011: 2011, // Core::ImportCycle
+            E2012: 2012, // Core::PrivateImport
+            E2013: 2013, // Core::UnreadableImport
+            E2020: 2020, // Core::AssignToImmutable
+            E2021: 2021, // Core::AssignToConstant
+            E2022: 2022, // Core::NonConstantInitializer
+            E2030: 2030, // Core::UnknownAttribute
+            E2031: 2031, // Core::MisplacedAttribute
+            E2032: 2032, // Core::InvalidAttributeArguments
+            E2033: 2033, // Core::DuplicateAttribute
+            E2040: 2040, // Core::DuplicateTypeParameter
+            E2041: 2041, // Core::WrongTypeArgumentCount
+            E2042: 2042, // Core::MismatchedArgumentType
+            E2043: 2043, // Core::MissingTypeArguments
+            E2044: 2044, // Core::TooManyInstances
+            E2045: 2045, // Core::UnknownTraitBound
+            E2046: 2046, // Core::UnsatisfiedBound
+            E2050: 2050, // Core::MissingTraitMethod
+            E2051: 2051, // Core::UnknownTraitMethod
+            E2052: 2052, // Core::MismatchedMethodSignature
+            E2053: 2053, // Core::DuplicateMethod
+            E2054: 2054, // Core::DuplicateImpl
+            E2055: 2055, // Core::UnknownMethod
+            E2056: 2056, // Core::AmbiguousMethod
+            E2057: 2057, // Core::NotAMethod
+            E2058: 2058, // Core::WrongMethodArgumentCount
         );
     )
 }
@@ -1,4 +1,5 @@
 use crate::codes::ErrCode;
+use crate::suggestion::Suggestion;
 
 pub trait CarbideError {
     /// The [`ariadne::Span`] type for this error
@@ -13,8 +14,12 @@ pub trait CarbideError {
     /// Build a formatted [`ariadne::Report`]
     ///
     /// # Errors
-    /// Returns `Err` if building the error fails
-    fn report(&'_ self, file: &str, src: &str) -> Result<ariadne::Report<'_, Self::Span>, Self>
+    /// Returns `Err` if building the error fails, boxed since errors can be large
+    fn report(
+        &'_ self,
+        file: &str,
+        src: &str,
+    ) -> Result<ariadne::Report<'_, Self::Span>, Box<Self>>
     where
         Self: Sized;
 
@@ -22,4 +27,9 @@ pub trait CarbideError {
     fn help(&self) -> Option<&'static str> {
         None
     }
+
+    /// Return edits to the source that fix this error
+    fn suggestions(&self) -> Vec<Suggestion> {
+        Vec::new()
+    }
 }
@@ -0,0 +1,46 @@
+use std::ops::Range;
+
+/// How safe it is to apply a [`Suggestion`] without checking it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Applicability {
+    /// The suggestion is definitely what was meant, so tools can apply it automatically
+    MachineApplicable,
+    /// The suggestion might not be what was meant
+    MaybeIncorrect,
+}
+
+/// An edit to the source that fixes an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub message: String,
+    /// Byte range of the source to replace, which is empty for insertions
+    pub span: Range<usize>,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl Suggestion {
+    /// Suggest inserting `text` at byte `offset`
+    #[must_use]
+    pub fn insert(
+        message: impl Into<String>,
+        offset: usize,
+        text: impl Into<String>,
+        applicability: Applicability,
+    ) -> Self {
+        Self {
+            message: message.into(),
+            span: offset..offset,
+            replacement: text.into(),
+            applicability,
+        }
+    }
+
+    /// Apply this suggestion to `src`, returning `None` if its span is outside of `src`
+    #[must_use]
+    pub fn apply(&self, src: &str) -> Option<String> {
+        let before = src.get(..self.span.start)?;
+        let after = src.get(self.span.end..)?;
+        Some(format!("{before}{}{after}", self.replacement))
+    }
+}
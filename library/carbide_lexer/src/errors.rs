@@ -38,6 +38,9 @@ pub enum CarbideLexerError {
     #[error("Failed to cast `{0}` as a keyword")]
     CastKeywordFailed(String),
 
+    #[error("Failed to cast `{0}` as a primitive type")]
+    CastPrimitiveTypeFailed(String),
+
     #[error("Failed to cast `{0}` as a binary operator")]
     CastBinaryOpFailed(String),
 
@@ -111,7 +114,7 @@ impl CarbideError for CarbideLexerError {
     }
 
     #[allow(clippy::too_many_lines)]
-    fn report(&'_ self, file: &str, src: &str) -> Result<Report<'_, ErrorSpan>, Self> {
+    fn report(&'_ self, file: &str, src: &str) -> Result<Report<'_, ErrorSpan>, Box<Self>> {
         match self {
             Self::NonASCIIChar(ch, loc) => {
                 let offset = usize::try_from(loc.offset).map_err(|e| {
@@ -37,4 +37,19 @@ define_keywords! {
     Let => "let",
     Fn  => "fn",
     Return => "return",
+    While => "while",
+    For => "for",
+    Break => "break",
+    Continue => "continue",
+    Struct => "struct",
+    Enum => "enum",
+    Match => "match",
+    If => "if",
+    Else => "else",
+    Import => "import",
+    Pub => "pub",
+    Mut => "mut",
+    Const => "const",
+    Trait => "trait",
+    Impl => "impl",
 }
@@ -1,6 +1,7 @@
 use crate::errors::CarbideLexerError;
 use crate::keywords::Keywords;
 use crate::operators::{BinaryOperators, UnaryOperators};
+use crate::primitives::PrimitiveTypes;
 use crate::tokens::{SourceLocation, StringPart, Token, Tokens};
 
 pub struct CarbideLexer<'a> {
@@ -259,6 +260,13 @@ impl<'a> CarbideLexer<'a> {
                 continue;
             }
 
+            if (ch == '.' || ch == ':')
+                && let Ok(Some(token)) = self.lex_double_punctuation(start, start_loc)
+            {
+                tokens.push(token);
+                continue;
+            }
+
             if BinaryOperators::starts_with(ch) || UnaryOperators::starts_with(ch) {
                 match self.lex_operator(start, start_loc) {
                     Ok(token) => tokens.push(token),
@@ -454,6 +462,8 @@ impl<'a> CarbideLexer<'a> {
 
         let token_type = if let Ok(keyword) = Keywords::try_from(slice) {
             Tokens::Keyword(keyword)
+        } else if PrimitiveTypes::try_from(slice).is_ok() {
+            Tokens::TypeIdentifier(slice)
         } else {
             Tokens::Identifier(slice)
         };
@@ -541,6 +551,17 @@ impl<'a> CarbideLexer<'a> {
             });
         }
 
+        // Chars like `|` start an operator, but are also a token on their own
+        if let Some(token_type) = Tokens::from_char(first_ch) {
+            return Ok(Token {
+                token_type,
+                start: start_loc,
+                end: end_loc,
+                span: start..end,
+                src: slice,
+            });
+        }
+
         Err(Box::new(CarbideLexerError::UnexpectedChar(
             first_ch, start_loc,
         )))
@@ -695,6 +716,12 @@ impl<'a> CarbideLexer<'a> {
         let mut parts = Vec::new();
         let mut current = 0;
         let bytes = raw.as_bytes();
+        // The raw string starts after the opening `"`
+        let raw_start = SourceLocation {
+            line: loc.line,
+            column: loc.column + 1,
+            offset: loc.offset + 1,
+        };
 
         while current < bytes.len() {
             let mut text_end = current;
@@ -750,13 +777,25 @@ impl<'a> CarbideLexer<'a> {
                 }
 
                 let interp = &raw[interp_start..text_end];
-                parts.push(StringPart::Interpolation(interp.to_string()));
+                parts.push(StringPart::Interpolation {
+                    code: interp.to_string(),
+                    start: CarbideLexer::<'a>::advance_location(raw_start, &raw[..interp_start]),
+                });
                 current = text_end + 1;
             }
         }
 
         Ok(parts)
     }
+
+    /// Get the location just past `text`, when it starts at `start`
+    fn advance_location(start: SourceLocation, text: &str) -> SourceLocation {
+        text.chars().fold(start, |loc, ch| SourceLocation {
+            line: if ch == '\n' { loc.line + 1 } else { loc.line },
+            column: if ch == '\n' { 1 } else { loc.column + 1 },
+            offset: loc.offset + ch.len_utf8() as u64,
+        })
+    }
 }
 
 impl<'a> CarbideLexer<'a> {
@@ -803,3 +842,38 @@ impl<'a> CarbideLexer<'a> {
         Ok(None)
     }
 }
+
+impl<'a> CarbideLexer<'a> {
+    /// Attempt to lex punctuation made of a doubled char (`..`, `::`)
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing fails
+    fn lex_double_punctuation(
+        &mut self,
+        start: u64,
+        start_loc: SourceLocation,
+    ) -> Result<Option<Token<'a>>, Box<CarbideLexerError>> {
+        let token_type = if self.src[self.pos..].starts_with("..") {
+            Tokens::DotDot
+        } else if self.src[self.pos..].starts_with("::") {
+            Tokens::ColonColon
+        } else {
+            return Ok(None);
+        };
+
+        self.pos += 2;
+        self.column += 2;
+
+        let end = self.pos as u64;
+        let end_loc = self.current_location();
+        let slice = &self.src[usize_from(start)?..usize_from(end)?];
+
+        Ok(Some(Token {
+            token_type,
+            start: start_loc,
+            end: end_loc,
+            span: start..end,
+            src: slice,
+        }))
+    }
+}
@@ -2,4 +2,5 @@ pub mod errors;
 pub mod keywords;
 pub mod lexer;
 pub mod operators;
+pub mod primitives;
 pub mod tokens;
@@ -82,12 +82,20 @@ macro_rules! define_unary_ops {
 define_bin_ops! {
     EqEq => "==",
     NotEq  => "!=",
+    LtEq => "<=",
+    GtEq => ">=",
+    AndAnd => "&&",
+    OrOr => "||",
+    StarStar => "**",
 
     Eq => "=",
+    Lt => "<",
+    Gt => ">",
     Plus => "+",
     Minus => "-",
     Star => "*",
     Slash => "/",
+    Percent => "%",
 
 }
 
@@ -0,0 +1,41 @@
+use crate::errors::CarbideLexerError;
+
+macro_rules! define_primitive_types {
+    ($($ty:ident => $lit:literal),* $(,)?) => {
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum PrimitiveTypes {
+            $($ty),*
+        }
+
+        impl PrimitiveTypes {
+            pub const ALL: &'static [Self] = &[
+                $(Self::$ty),*
+            ];
+
+            #[must_use]
+            pub fn as_str(&self) -> &'static str {
+                match self {
+                    $(Self::$ty => $lit),*
+                }
+            }
+        }
+
+        impl<'a> TryFrom<&'a str> for PrimitiveTypes {
+            type Error = CarbideLexerError;
+
+            fn try_from(ident: &'a str) -> Result<Self, Self::Error> {
+                match ident {
+                    $($lit => Ok(Self::$ty),)*
+                    _ => Err(CarbideLexerError::CastPrimitiveTypeFailed(ident.to_string())),
+                }
+            }
+        }
+    };
+}
+
+define_primitive_types! {
+    Int => "int",
+    Float => "float",
+    String => "string",
+    Bool => "bool",
+}
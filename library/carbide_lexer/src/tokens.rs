@@ -23,7 +23,12 @@ pub enum StringPart {
     /// Literal text within the string
     Text(String),
     /// An interpolation placeholder like `{name}`
-    Interpolation(String),
+    Interpolation {
+        /// The code between the braces, like `name`
+        code: String,
+        /// Where the code starts, just after the `{`
+        start: SourceLocation,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -53,6 +58,8 @@ pub enum Tokens<'a> {
 
     ThinArrow,
     FatArrow,
+    DotDot,
+    ColonColon,
 
     LeftParen,
     RightParen,
@@ -65,6 +72,10 @@ pub enum Tokens<'a> {
     Period,
     Comma,
     Tilde,
+    Question,
+    Pipe,
+    /// The `#` that starts an attribute, like `#[inline]`
+    Hash,
 }
 
 macro_rules! define_single_char_tokens {
@@ -103,6 +114,9 @@ define_single_char_tokens! {
     Period => '.',
     Comma => ',',
     Tilde => '~',
+    Question => '?',
+    Pipe => '|',
+    Hash => '#',
 }
 
 pub type Span = Range<u64>;
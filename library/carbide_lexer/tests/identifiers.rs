@@ -276,3 +276,31 @@ pub mod keyword {
         assert_eq!(tokens, expected);
     }
 }
+
+#[cfg(test)]
+pub mod primitive_type {
+    use carbide_lexer::{lexer::CarbideLexer, primitives::PrimitiveTypes, tokens::Tokens};
+
+    #[test]
+    fn all_primitive_types() {
+        for ty in PrimitiveTypes::ALL {
+            let mut lexer = CarbideLexer::from_src(ty.as_str());
+            let result = lexer.lex();
+
+            assert!(result.is_ok());
+            assert_eq!(
+                result.tokens[0].token_type,
+                Tokens::TypeIdentifier(ty.as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn prefixed_primitive_is_identifier() {
+        let mut lexer = CarbideLexer::from_src("integer");
+        let result = lexer.lex();
+
+        assert!(result.is_ok());
+        assert_eq!(result.tokens[0].token_type, Tokens::Identifier("integer"));
+    }
+}
@@ -264,12 +264,42 @@ pub mod string_literals {
             tokens[0].token_type,
             Tokens::InterpolatedString(vec![
                 StringPart::Text("Hello ".to_string()),
-                StringPart::Interpolation("name".to_string()),
+                StringPart::Interpolation {
+                    code: "name".to_string(),
+                    start: SourceLocation {
+                        line: 1,
+                        column: 10,
+                        offset: 9,
+                    },
+                },
                 StringPart::Text("!".to_string())
             ])
         );
     }
 
+    #[test]
+    fn interpolation_after_newline() {
+        let src = "\"ab\ncd {x}\"";
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+
+        assert!(result.is_ok());
+        assert_eq!(
+            result.tokens[0].token_type,
+            Tokens::InterpolatedString(vec![
+                StringPart::Text("ab\ncd ".to_string()),
+                StringPart::Interpolation {
+                    code: "x".to_string(),
+                    start: SourceLocation {
+                        line: 2,
+                        column: 5,
+                        offset: 8,
+                    },
+                },
+            ])
+        );
+    }
+
     #[test]
     fn escaped_string() {
         let src = r#" "The letter \"A\"" "#;
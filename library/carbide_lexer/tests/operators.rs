@@ -142,4 +142,41 @@ pub mod binary {
 
         assert_eq!(tokens.len(), 4);
     }
+
+    #[test]
+    fn pipe_and_or() {
+        let src = "|a| || b";
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+
+        assert!(result.is_ok());
+        let tokens = result.tokens;
+
+        assert_eq!(tokens.len(), 5);
+        assert_eq!(tokens[0].token_type, Tokens::Pipe);
+        assert_eq!(tokens[2].token_type, Tokens::Pipe);
+        assert_eq!(
+            tokens[3].token_type,
+            Tokens::BinaryOperator(BinaryOperators::OrOr)
+        );
+    }
+
+    #[test]
+    fn attribute_hash() {
+        let mut lexer = CarbideLexer::from_src("#[inline]");
+        let result = lexer.lex();
+
+        assert!(result.is_ok());
+        let tokens = result.tokens;
+
+        assert_eq!(tokens.len(), 4);
+        assert_eq!(tokens[0].token_type, Tokens::Hash);
+        assert_eq!(tokens[1].token_type, Tokens::LeftBracket);
+    }
+
+    #[test]
+    fn lone_ampersand() {
+        let mut lexer = CarbideLexer::from_src("a & b");
+        assert!(!lexer.lex().is_ok());
+    }
 }
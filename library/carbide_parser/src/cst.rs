@@ -0,0 +1,25 @@
+//! Lossless concrete syntax tree
+//!
+//! The parser can build a CST alongside the AST, which keeps every token, comment and bit
+//! of whitespace from the source. It's made of two layers, like in `rowan`:
+//!
+//! - The green tree of [`GreenNodes`][`GreenNode`] and [`GreenTokens`][`GreenToken`] is
+//!   immutable and doesn't know where it is in the source, so it can be shared and cached
+//! - The red tree of [`SyntaxNodes`][`SyntaxNode`] is built on demand over the green tree,
+//!   adding parent pointers and offsets
+//!
+//! Typed views like [`FnDecl`][`views::FnDecl`] give access to the parts of a node, and
+//! [`lower`][`lower::lower`] turns the tree into the [`Statements`][`crate::nodes::Statement`]
+//! that [`CarbideParser::parse`][`crate::parser::CarbideParser::parse`] produces
+
+pub(crate) mod builder;
+pub mod green;
+pub mod kind;
+pub mod lower;
+pub mod red;
+pub mod views;
+
+pub use green::{GreenElement, GreenNode, GreenToken};
+pub use kind::SyntaxKind;
+pub use red::{SyntaxElement, SyntaxNode, SyntaxToken};
+pub use views::AstNode;
@@ -0,0 +1,128 @@
+use carbide_lexer::tokens::Token;
+
+use super::green::{Checkpoint, GreenNode, GreenNodeBuilder};
+use super::kind::SyntaxKind;
+
+/// Builds the CST alongside the parser, filling in the whitespace and comments the lexer
+/// skips from the source
+#[derive(Debug)]
+pub(crate) struct CstBuilder<'a> {
+    src: &'a str,
+    builder: GreenNodeBuilder,
+    /// Offset up to which the source has been added to the tree
+    offset: usize,
+}
+
+impl<'a> CstBuilder<'a> {
+    pub(crate) fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            builder: GreenNodeBuilder::new(),
+            offset: 0,
+        }
+    }
+
+    /// Add `token`, along with the trivia before it
+    pub(crate) fn token(&mut self, token: &Token<'_>) {
+        let start = to_offset(token.span.start);
+        let end = to_offset(token.span.end);
+        self.trivia(start);
+
+        let text = self.src.get(start..end).unwrap_or(token.src);
+        self.builder
+            .token(SyntaxKind::from(&token.token_type), text);
+        self.offset = self.offset.max(end);
+    }
+
+    /// Get a [`Checkpoint`] for a node starting at `next`, the offset of the next token
+    ///
+    /// The trivia before `next` is added first, so it's left outside of the node
+    pub(crate) fn checkpoint(&mut self, next: u64) -> Checkpoint {
+        self.trivia(to_offset(next));
+        self.builder.checkpoint()
+    }
+
+    /// Wrap everything added since `checkpoint` in a node of `kind`
+    pub(crate) fn wrap(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        self.builder.wrap(checkpoint, kind);
+    }
+
+    /// Add the trailing trivia, and wrap everything in a [`SyntaxKind::SourceFile`]
+    pub(crate) fn finish(mut self) -> GreenNode {
+        self.trivia(self.src.len());
+        self.builder.finish(SyntaxKind::SourceFile)
+    }
+
+    /// Add the source between the last token and `end` as trivia tokens
+    fn trivia(&mut self, end: usize) {
+        while self.offset < end {
+            let Some(rest) = self.src.get(self.offset..end) else {
+                return;
+            };
+
+            let (kind, len) = split_trivia(rest);
+            self.builder.token(kind, &rest[..len]);
+            self.offset += len;
+        }
+    }
+}
+
+/// Convert a [`Span`][`carbide_lexer::tokens::Span`] offset to an index into the source
+fn to_offset(offset: u64) -> usize {
+    usize::try_from(offset).unwrap_or(usize::MAX)
+}
+
+/// Get the kind and length of the trivia token at the start of `text`, which isn't empty
+fn split_trivia(text: &str) -> (SyntaxKind, usize) {
+    if text.starts_with(char::is_whitespace) {
+        let len = text
+            .find(|c: char| !c.is_whitespace())
+            .unwrap_or(text.len());
+        return (SyntaxKind::Whitespace, len);
+    }
+
+    if text.starts_with("//") {
+        return (SyntaxKind::Comment, text.find('\n').unwrap_or(text.len()));
+    }
+
+    if text.starts_with("/*") {
+        return (SyntaxKind::Comment, block_comment_len(text));
+    }
+
+    // Anything else is text the lexer reported an error for
+    let len = text
+        .char_indices()
+        .skip(1)
+        .find(|&(i, c)| {
+            c.is_whitespace() || text[i..].starts_with("//") || text[i..].starts_with("/*")
+        })
+        .map_or(text.len(), |(i, _)| i);
+    (SyntaxKind::Unknown, len)
+}
+
+/// Get the length of the possibly nested `/* ... */` comment at the start of `text`, or
+/// the length of `text` if it's unterminated
+fn block_comment_len(text: &str) -> usize {
+    let bytes = text.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+
+    while i + 1 < bytes.len() {
+        match (bytes[i], bytes[i + 1]) {
+            (b'/', b'*') => {
+                depth += 1;
+                i += 2;
+            }
+            (b'*', b'/') => {
+                depth -= 1;
+                i += 2;
+                if depth == 0 {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+
+    text.len()
+}
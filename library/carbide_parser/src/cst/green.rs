@@ -0,0 +1,171 @@
+use std::sync::Arc;
+
+use super::kind::SyntaxKind;
+
+/// An immutable, position independent token in the green tree
+///
+/// Identical tokens can be shared between trees, since they don't know where they are
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreenToken(Arc<GreenTokenData>);
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct GreenTokenData {
+    kind: SyntaxKind,
+    text: String,
+}
+
+impl GreenToken {
+    #[must_use]
+    pub fn new(kind: SyntaxKind, text: &str) -> Self {
+        Self(Arc::new(GreenTokenData {
+            kind,
+            text: text.to_string(),
+        }))
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> SyntaxKind {
+        self.0.kind
+    }
+
+    #[must_use]
+    pub fn text(&self) -> &str {
+        &self.0.text
+    }
+
+    /// Get the length of the token's text in bytes
+    #[must_use]
+    pub fn text_len(&self) -> u64 {
+        self.0.text.len() as u64
+    }
+}
+
+/// An immutable, position independent node in the green tree
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GreenNode(Arc<GreenNodeData>);
+
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct GreenNodeData {
+    kind: SyntaxKind,
+    text_len: u64,
+    children: Vec<GreenElement>,
+}
+
+impl GreenNode {
+    #[must_use]
+    pub fn new(kind: SyntaxKind, children: Vec<GreenElement>) -> Self {
+        let text_len = children.iter().map(GreenElement::text_len).sum();
+        Self(Arc::new(GreenNodeData {
+            kind,
+            text_len,
+            children,
+        }))
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> SyntaxKind {
+        self.0.kind
+    }
+
+    /// Get the length of the node's text, including trivia, in bytes
+    #[must_use]
+    pub fn text_len(&self) -> u64 {
+        self.0.text_len
+    }
+
+    #[must_use]
+    pub fn children(&self) -> &[GreenElement] {
+        &self.0.children
+    }
+
+    /// Get the source text of the node, including trivia
+    #[must_use]
+    pub fn text(&self) -> String {
+        let mut text = String::new();
+        self.write_text(&mut text);
+        text
+    }
+
+    fn write_text(&self, text: &mut String) {
+        for child in self.children() {
+            match child {
+                GreenElement::Node(node) => node.write_text(text),
+                GreenElement::Token(token) => text.push_str(token.text()),
+            }
+        }
+    }
+}
+
+/// Either a [`GreenNode`] or a [`GreenToken`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum GreenElement {
+    Node(GreenNode),
+    Token(GreenToken),
+}
+
+impl GreenElement {
+    #[must_use]
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            Self::Node(node) => node.kind(),
+            Self::Token(token) => token.kind(),
+        }
+    }
+
+    #[must_use]
+    pub fn text_len(&self) -> u64 {
+        match self {
+            Self::Node(node) => node.text_len(),
+            Self::Token(token) => token.text_len(),
+        }
+    }
+}
+
+/// A position in a [`GreenNodeBuilder`] that a node can later be started at
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Checkpoint(usize);
+
+/// Builds a [`GreenNode`] bottom up
+///
+/// Nodes are created once their end is known, by wrapping everything added since a
+/// [`Checkpoint`]. This lets the kind of a node be decided after its contents are parsed,
+/// like a binary expression wrapping its already built left operand
+#[derive(Debug, Default)]
+pub struct GreenNodeBuilder {
+    children: Vec<GreenElement>,
+}
+
+impl GreenNodeBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a token to the node being built
+    pub fn token(&mut self, kind: SyntaxKind, text: &str) {
+        self.children
+            .push(GreenElement::Token(GreenToken::new(kind, text)));
+    }
+
+    /// Get a [`Checkpoint`] at the current position
+    #[must_use]
+    pub fn checkpoint(&self) -> Checkpoint {
+        Checkpoint(self.children.len())
+    }
+
+    /// Wrap everything added since `checkpoint` in a node of `kind`
+    ///
+    /// Checkpoints taken after `checkpoint` are no longer valid afterwards
+    pub fn wrap(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        let start = checkpoint.0.min(self.children.len());
+        let children = self.children.split_off(start);
+        self.children
+            .push(GreenElement::Node(GreenNode::new(kind, children)));
+    }
+
+    /// Wrap everything added in a root node of `kind`
+    #[must_use]
+    pub fn finish(self, kind: SyntaxKind) -> GreenNode {
+        GreenNode::new(kind, self.children)
+    }
+}
@@ -0,0 +1,243 @@
+use carbide_lexer::tokens::Tokens;
+
+/// The kind of a token or node in the CST
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SyntaxKind {
+    // Trivia
+    /// Spaces, tabs and newlines
+    Whitespace,
+    /// A `// line` or `/* block */` comment
+    Comment,
+    /// Source text the lexer couldn't turn into a token
+    Unknown,
+
+    // Tokens
+    IntLiteral,
+    FloatLiteral,
+    HexLiteral,
+    BinaryLiteral,
+    StringLiteral,
+    InterpolatedString,
+    Identifier,
+    TypeIdentifier,
+    Keyword,
+    BinaryOperator,
+    UnaryOperator,
+    ThinArrow,
+    FatArrow,
+    DotDot,
+    ColonColon,
+    LeftParen,
+    RightParen,
+    LeftBracket,
+    RightBracket,
+    LeftBrace,
+    RightBrace,
+    Semicolon,
+    Colon,
+    Period,
+    Comma,
+    Tilde,
+    Question,
+    Pipe,
+    Hash,
+
+    // Nodes
+    /// The root of the tree
+    SourceFile,
+    /// Tokens that failed to parse, along with anything parsed before the error
+    Error,
+    /// The `pub` before an item
+    Visibility,
+    /// An attribute before a declaration, like `#[inline]`
+    Attribute,
+
+    // Statements
+    LetStmt,
+    ConstDecl,
+    FnDecl,
+    /// The `<T: Bound, U>` type parameters of a generic function
+    TypeParamList,
+    TypeParam,
+    ParamList,
+    Param,
+    StructDecl,
+    /// The `{ name: type, ... }` fields of a struct or enum variant
+    FieldList,
+    FieldDecl,
+    EnumDecl,
+    Variant,
+    TraitDecl,
+    /// An `impl Trait for Type { ... }` or `impl Type { ... }` block
+    ImplDecl,
+    /// A method in a trait or impl, like `fn area(self) -> float;`
+    Method,
+    ImportDecl,
+    ReturnStmt,
+    WhileStmt,
+    ForStmt,
+    ExprStmt,
+    BreakStmt,
+    ContinueStmt,
+    /// A `{ ... }` block, either as a statement, an expression or the body of an item
+    Block,
+
+    // Types
+    NamedType,
+    GenericType,
+    ArrayType,
+    TupleType,
+    /// A parenthesized type, like `(int)`
+    ParenType,
+    FunctionType,
+    MapType,
+    OptionalType,
+    UnitType,
+
+    // Expressions
+    Literal,
+    /// A possibly qualified name, like `x` or `Shape::Circle`
+    PathExpr,
+    /// A generic function with type arguments, like `first::<int>`
+    GenericExpr,
+    BinaryExpr,
+    AssignExpr,
+    PrefixExpr,
+    PostfixExpr,
+    CallExpr,
+    ArgList,
+    IndexExpr,
+    FieldExpr,
+    ParenExpr,
+    TupleExpr,
+    ArrayExpr,
+    MapExpr,
+    MapEntry,
+    MatchExpr,
+    MatchArm,
+    StructLit,
+    FieldInit,
+    LambdaExpr,
+    IfExpr,
+
+    // Patterns
+    LiteralPat,
+    WildcardPat,
+    BindingPat,
+    PathPat,
+    TupleStructPat,
+    StructPat,
+    FieldPat,
+    TuplePat,
+    /// A parenthesized pattern, like `(x)`
+    ParenPat,
+    ArrayPat,
+    /// The `..` or `..name` of an array pattern
+    RestPat,
+}
+
+impl SyntaxKind {
+    /// Check if this is whitespace, a comment or unknown text between tokens
+    #[must_use]
+    pub fn is_trivia(self) -> bool {
+        matches!(self, Self::Whitespace | Self::Comment | Self::Unknown)
+    }
+
+    /// Check if this kind of node is a type
+    #[must_use]
+    pub fn is_type(self) -> bool {
+        matches!(
+            self,
+            Self::NamedType
+                | Self::GenericType
+                | Self::ArrayType
+                | Self::TupleType
+                | Self::ParenType
+                | Self::FunctionType
+                | Self::MapType
+                | Self::OptionalType
+                | Self::UnitType
+        )
+    }
+
+    /// Check if this kind of node is an expression
+    #[must_use]
+    pub fn is_expression(self) -> bool {
+        matches!(
+            self,
+            Self::Block
+                | Self::Literal
+                | Self::PathExpr
+                | Self::GenericExpr
+                | Self::BinaryExpr
+                | Self::AssignExpr
+                | Self::PrefixExpr
+                | Self::PostfixExpr
+                | Self::CallExpr
+                | Self::IndexExpr
+                | Self::FieldExpr
+                | Self::ParenExpr
+                | Self::TupleExpr
+                | Self::ArrayExpr
+                | Self::MapExpr
+                | Self::MatchExpr
+                | Self::StructLit
+                | Self::LambdaExpr
+                | Self::IfExpr
+        )
+    }
+
+    /// Check if this kind of node is a pattern
+    #[must_use]
+    pub fn is_pattern(self) -> bool {
+        matches!(
+            self,
+            Self::LiteralPat
+                | Self::WildcardPat
+                | Self::BindingPat
+                | Self::PathPat
+                | Self::TupleStructPat
+                | Self::StructPat
+                | Self::TuplePat
+                | Self::ParenPat
+                | Self::ArrayPat
+                | Self::RestPat
+        )
+    }
+}
+
+impl From<&Tokens<'_>> for SyntaxKind {
+    fn from(token: &Tokens<'_>) -> Self {
+        match token {
+            Tokens::IntLiteral(_) => Self::IntLiteral,
+            Tokens::FloatLiteral(_) => Self::FloatLiteral,
+            Tokens::HexLiteral(_) => Self::HexLiteral,
+            Tokens::BinaryLiteral(_) => Self::BinaryLiteral,
+            Tokens::StringLiteral(_) => Self::StringLiteral,
+            Tokens::InterpolatedString(_) => Self::InterpolatedString,
+            Tokens::Identifier(_) => Self::Identifier,
+            Tokens::TypeIdentifier(_) => Self::TypeIdentifier,
+            Tokens::Keyword(_) => Self::Keyword,
+            Tokens::BinaryOperator(_) => Self::BinaryOperator,
+            Tokens::UnaryOperator(_) => Self::UnaryOperator,
+            Tokens::ThinArrow => Self::ThinArrow,
+            Tokens::FatArrow => Self::FatArrow,
+            Tokens::DotDot => Self::DotDot,
+            Tokens::ColonColon => Self::ColonColon,
+            Tokens::LeftParen => Self::LeftParen,
+            Tokens::RightParen => Self::RightParen,
+            Tokens::LeftBracket => Self::LeftBracket,
+            Tokens::RightBracket => Self::RightBracket,
+            Tokens::LeftBrace => Self::LeftBrace,
+            Tokens::RightBrace => Self::RightBrace,
+            Tokens::Semicolon => Self::Semicolon,
+            Tokens::Colon => Self::Colon,
+            Tokens::Period => Self::Period,
+            Tokens::Comma => Self::Comma,
+            Tokens::Tilde => Self::Tilde,
+            Tokens::Question => Self::Question,
+            Tokens::Pipe => Self::Pipe,
+            Tokens::Hash => Self::Hash,
+        }
+    }
+}
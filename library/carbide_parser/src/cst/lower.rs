@@ -0,0 +1,628 @@
+use carbide_lexer::lexer::CarbideLexer;
+use carbide_lexer::tokens::Tokens;
+
+use super::kind::SyntaxKind;
+use super::red::{SyntaxNode, SyntaxToken};
+use super::views::{
+    self, AssignExpr, AstNode, Attribute, BinaryExpr, Block, CallExpr, ConstDecl, EnumDecl,
+    ExprStmt, FieldList, FnDecl, ForStmt, IfExpr, ImplDecl, ImportDecl, LambdaExpr, LetStmt,
+    MatchExpr, Method, Param, ParamList, ReturnStmt, StructDecl, StructLit, TraitDecl,
+    TypeParamList, WhileStmt,
+};
+use crate::nodes::{
+    self, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody, LiteralValue, MapEntry,
+    MatchArm, Parameter, Pattern, Statement, StructField, Type, TypeParameter, VariantPayload,
+};
+use crate::parser::CarbideParser;
+
+/// Lower the CST below `root`, a [`SyntaxKind::SourceFile`], to the same [`Statements`][`Statement`]
+/// that [`CarbideParser::parse`] produces
+#[must_use]
+pub fn lower(root: &SyntaxNode) -> Vec<Statement> {
+    root.children().map(|node| lower_statement(&node)).collect()
+}
+
+/// Lower a statement node, or an [`SyntaxKind::Error`] node in its place
+#[must_use]
+pub fn lower_statement(node: &SyntaxNode) -> Statement {
+    let statement = match node.kind() {
+        SyntaxKind::LetStmt => LetStmt::cast(node.clone()).and_then(|s| lower_let(&s)),
+        SyntaxKind::ConstDecl => ConstDecl::cast(node.clone()).and_then(|s| lower_const(&s)),
+        SyntaxKind::FnDecl => FnDecl::cast(node.clone()).and_then(|s| lower_fn(&s)),
+        SyntaxKind::StructDecl => StructDecl::cast(node.clone()).and_then(|s| lower_struct(&s)),
+        SyntaxKind::EnumDecl => EnumDecl::cast(node.clone()).and_then(|s| lower_enum(&s)),
+        SyntaxKind::TraitDecl => TraitDecl::cast(node.clone()).and_then(|s| lower_trait(&s)),
+        SyntaxKind::ImplDecl => ImplDecl::cast(node.clone()).and_then(|s| lower_impl(&s)),
+        SyntaxKind::ImportDecl => ImportDecl::cast(node.clone()).and_then(|s| lower_import(&s)),
+        SyntaxKind::ReturnStmt => ReturnStmt::cast(node.clone())
+            .map(|s| Statement::Return(s.value().map(|value| lower_expression(&value)))),
+        SyntaxKind::WhileStmt => WhileStmt::cast(node.clone()).and_then(|s| lower_while(&s)),
+        SyntaxKind::ForStmt => ForStmt::cast(node.clone()).and_then(|s| lower_for(&s)),
+        SyntaxKind::ExprStmt => ExprStmt::cast(node.clone())
+            .and_then(|s| s.expr())
+            .map(|expr| Statement::Expression(lower_expression(&expr))),
+        SyntaxKind::BreakStmt => Some(Statement::Break),
+        SyntaxKind::ContinueStmt => Some(Statement::Continue),
+        // A block with a value is wrapped in an `ExprStmt`, so this one has none
+        SyntaxKind::Block => Block::cast(node.clone()).map(|block| {
+            Statement::Block(block.statements().map(|s| lower_statement(&s)).collect())
+        }),
+        _ => None,
+    };
+
+    statement.unwrap_or_else(|| Statement::Error(node.text_range()))
+}
+
+fn lower_let(stmt: &LetStmt) -> Option<Statement> {
+    let pattern = stmt.pattern()?;
+
+    Some(Statement::LetDeclaration {
+        attributes: lower_attributes(stmt.attributes()),
+        mutable: stmt.is_mutable(),
+        pattern: lower_pattern(&pattern),
+        type_annotation: stmt.type_annotation().map(|ty| lower_type(&ty)),
+        initializer: stmt.initializer().map(|expr| lower_expression(&expr)),
+        span: pattern.text_range(),
+    })
+}
+
+fn lower_const(decl: &ConstDecl) -> Option<Statement> {
+    let name = decl.name()?;
+
+    Some(Statement::ConstDeclaration {
+        visibility: decl.visibility(),
+        name: name.text().to_string(),
+        type_annotation: lower_type(&decl.type_annotation()?),
+        value: lower_expression(&decl.value()?),
+        span: name.text_range(),
+    })
+}
+
+fn lower_fn(decl: &FnDecl) -> Option<Statement> {
+    Some(Statement::FunctionDeclaration {
+        attributes: lower_attributes(decl.attributes()),
+        visibility: decl.visibility(),
+        name: decl.name()?.text().to_string(),
+        type_parameters: lower_type_params(decl.type_param_list().as_ref()),
+        parameters: lower_params(decl.param_list().as_ref()),
+        return_type: decl.return_type().map(|ty| lower_type(&ty)),
+        body: lower_block(&decl.body()?),
+    })
+}
+
+fn lower_attributes(attributes: impl Iterator<Item = Attribute>) -> Vec<nodes::Attribute> {
+    attributes
+        .filter_map(|attribute| {
+            Some(nodes::Attribute {
+                name: attribute.name()?.text().to_string(),
+                arguments: attribute
+                    .arguments()
+                    .filter_map(|argument| literal_value(&argument.tokens().next()?))
+                    .collect(),
+                span: attribute.syntax().text_range(),
+            })
+        })
+        .collect()
+}
+
+fn lower_type_params(type_params: Option<&TypeParamList>) -> Vec<TypeParameter> {
+    type_params
+        .into_iter()
+        .flat_map(TypeParamList::type_params)
+        .filter_map(|type_param| {
+            let name = type_param.name()?;
+            Some(TypeParameter {
+                name: name.text().to_string(),
+                bounds: type_param
+                    .bounds()
+                    .map(|bound| bound.text().to_string())
+                    .collect(),
+                span: name.text_range(),
+            })
+        })
+        .collect()
+}
+
+fn lower_params(params: Option<&ParamList>) -> Vec<Parameter> {
+    params
+        .into_iter()
+        .flat_map(ParamList::params)
+        .filter_map(|param| lower_param(&param))
+        .collect()
+}
+
+fn lower_param(param: &Param) -> Option<Parameter> {
+    let pattern = param.pattern()?;
+
+    Some(Parameter {
+        mutable: param.is_mutable(),
+        pattern: lower_pattern(&pattern),
+        type_annotation: param.type_annotation().map(|ty| lower_type(&ty)),
+        span: pattern.text_range(),
+    })
+}
+
+fn lower_struct(decl: &StructDecl) -> Option<Statement> {
+    Some(Statement::StructDeclaration {
+        visibility: decl.visibility(),
+        name: decl.name()?.text().to_string(),
+        fields: lower_fields(&decl.field_list()?),
+    })
+}
+
+fn lower_fields(fields: &FieldList) -> Vec<StructField> {
+    fields
+        .fields()
+        .filter_map(|field| {
+            Some(StructField {
+                name: field.name()?.text().to_string(),
+                type_annotation: lower_type(&field.type_annotation()?),
+            })
+        })
+        .collect()
+}
+
+fn lower_enum(decl: &EnumDecl) -> Option<Statement> {
+    let variants = decl
+        .variants()
+        .filter_map(|variant| {
+            let payload = if let Some(fields) = variant.field_list() {
+                VariantPayload::Struct(lower_fields(&fields))
+            } else if let Some(types) = variant.tuple_types() {
+                VariantPayload::Tuple(types.iter().map(lower_type).collect())
+            } else {
+                VariantPayload::Unit
+            };
+
+            Some(EnumVariant {
+                name: variant.name()?.text().to_string(),
+                payload,
+            })
+        })
+        .collect();
+
+    Some(Statement::EnumDeclaration {
+        visibility: decl.visibility(),
+        name: decl.name()?.text().to_string(),
+        variants,
+    })
+}
+
+fn lower_trait(decl: &TraitDecl) -> Option<Statement> {
+    let name = decl.name()?;
+
+    Some(Statement::TraitDeclaration {
+        visibility: decl.visibility(),
+        name: name.text().to_string(),
+        methods: decl.methods().filter_map(|m| lower_method(&m)).collect(),
+        span: name.text_range(),
+    })
+}
+
+fn lower_impl(decl: &ImplDecl) -> Option<Statement> {
+    let target = decl.target()?;
+
+    Some(Statement::ImplDeclaration {
+        trait_name: decl.trait_name().map(|name| name.text().to_string()),
+        target: target.text().to_string(),
+        methods: decl.methods().filter_map(|m| lower_method(&m)).collect(),
+        span: decl.syntax().text_range().start..target.text_range().end,
+    })
+}
+
+fn lower_method(method: &Method) -> Option<nodes::Method> {
+    let name = method.name()?;
+
+    Some(nodes::Method {
+        name: name.text().to_string(),
+        parameters: lower_params(method.param_list().as_ref()),
+        return_type: method.return_type().map(|ty| lower_type(&ty)),
+        body: method.body().map(|body| lower_block(&body)),
+        span: name.text_range(),
+    })
+}
+
+fn lower_import(decl: &ImportDecl) -> Option<Statement> {
+    let node = decl.syntax();
+    // The span of an import doesn't include its `;`
+    let end = node
+        .tokens()
+        .filter(|token| token.kind() != SyntaxKind::Semicolon)
+        .last()?
+        .text_range()
+        .end;
+
+    Some(Statement::Import {
+        path: decl.path().map(|name| name.text().to_string()).collect(),
+        items: decl
+            .items()
+            .map(|items| items.iter().map(|name| name.text().to_string()).collect()),
+        span: node.text_range().start..end,
+    })
+}
+
+fn lower_while(stmt: &WhileStmt) -> Option<Statement> {
+    Some(Statement::While {
+        condition: lower_expression(&stmt.condition()?),
+        body: lower_loop_body(&stmt.body()?),
+    })
+}
+
+fn lower_for(stmt: &ForStmt) -> Option<Statement> {
+    Some(Statement::For {
+        initializer: stmt
+            .initializer()
+            .map(|init| Box::new(lower_statement(&init))),
+        condition: stmt.condition().map(|expr| lower_expression(&expr)),
+        increment: stmt.increment().map(|expr| lower_expression(&expr)),
+        body: lower_loop_body(&stmt.body()?),
+    })
+}
+
+fn lower_block(block: &Block) -> nodes::Block {
+    nodes::Block {
+        statements: block.statements().map(|s| lower_statement(&s)).collect(),
+        tail: block.tail().map(|tail| Box::new(lower_expression(&tail))),
+    }
+}
+
+/// Lower the body of a loop, where the value of the tail expression is discarded
+fn lower_loop_body(block: &Block) -> Vec<Statement> {
+    let nodes::Block {
+        mut statements,
+        tail,
+    } = lower_block(block);
+    statements.extend(tail.map(|tail| Statement::Expression(*tail)));
+    statements
+}
+
+/// Lower an expression node, or an [`SyntaxKind::Error`] node in its place
+#[must_use]
+pub fn lower_expression(node: &SyntaxNode) -> Expression {
+    let expr = match node.kind() {
+        SyntaxKind::Literal => lower_literal(node),
+        SyntaxKind::PathExpr => Some(Expression::Identifier {
+            name: views::path(node),
+            span: node.text_range(),
+        }),
+        SyntaxKind::GenericExpr => Some(Expression::Generic {
+            name: views::path(node),
+            arguments: node.children().map(|ty| lower_type(&ty)).collect(),
+            span: node.text_range(),
+        }),
+        SyntaxKind::BinaryExpr => BinaryExpr::cast(node.clone()).and_then(|expr| {
+            let Tokens::BinaryOperator(operator) = lex(expr.operator()?.text())? else {
+                return None;
+            };
+
+            Some(Expression::BinaryOp {
+                left: Box::new(lower_expression(&expr.lhs()?)),
+                operator,
+                right: Box::new(lower_expression(&expr.rhs()?)),
+            })
+        }),
+        SyntaxKind::AssignExpr => AssignExpr::cast(node.clone()).and_then(|expr| {
+            Some(Expression::Assignment {
+                target: Box::new(lower_expression(&expr.target()?)),
+                value: Box::new(lower_expression(&expr.value()?)),
+                span: node.text_range(),
+            })
+        }),
+        SyntaxKind::PrefixExpr | SyntaxKind::PostfixExpr => lower_unary(node),
+        SyntaxKind::CallExpr => CallExpr::cast(node.clone()).and_then(|expr| {
+            Some(Expression::Call {
+                callee: Box::new(lower_expression(&expr.callee()?)),
+                arguments: expr
+                    .arg_list()?
+                    .args()
+                    .map(|arg| lower_expression(&arg))
+                    .collect(),
+            })
+        }),
+        SyntaxKind::IndexExpr => {
+            let mut children = node.children();
+            children
+                .next()
+                .zip(children.next())
+                .map(|(target, index)| Expression::Index {
+                    target: Box::new(lower_expression(&target)),
+                    index: Box::new(lower_expression(&index)),
+                })
+        }
+        SyntaxKind::FieldExpr => node
+            .token_of_kind(SyntaxKind::Identifier)
+            .zip(first_child(node))
+            .map(|(member, target)| Expression::MemberAccess {
+                target: Box::new(target),
+                member: member.text().to_string(),
+                span: member.text_range(),
+            }),
+        SyntaxKind::ParenExpr => first_child(node).map(|expr| Expression::Grouped(Box::new(expr))),
+        SyntaxKind::TupleExpr => Some(Expression::Tuple(lower_children(node))),
+        SyntaxKind::ArrayExpr => Some(Expression::Array(lower_children(node))),
+        SyntaxKind::MapExpr => Some(Expression::Map(
+            node.children()
+                .filter_map(|entry| {
+                    let mut children = entry.children();
+                    let (key, value) = children.next().zip(children.next())?;
+                    Some(MapEntry {
+                        key: lower_expression(&key),
+                        value: lower_expression(&value),
+                    })
+                })
+                .collect(),
+        )),
+        SyntaxKind::MatchExpr => MatchExpr::cast(node.clone()).and_then(|expr| lower_match(&expr)),
+        SyntaxKind::StructLit => {
+            StructLit::cast(node.clone()).map(|expr| lower_struct_literal(&expr))
+        }
+        SyntaxKind::LambdaExpr => {
+            LambdaExpr::cast(node.clone()).and_then(|expr| lower_lambda(&expr))
+        }
+        SyntaxKind::Block => {
+            Block::cast(node.clone()).map(|block| Expression::Block(lower_block(&block)))
+        }
+        SyntaxKind::IfExpr => IfExpr::cast(node.clone()).and_then(|expr| lower_if(&expr)),
+        _ => None,
+    };
+
+    expr.unwrap_or_else(|| Expression::Error(node.text_range()))
+}
+
+fn lower_unary(node: &SyntaxNode) -> Option<Expression> {
+    let operator = node.token_of_kind(SyntaxKind::UnaryOperator)?;
+    let Tokens::UnaryOperator(operator) = lex(operator.text())? else {
+        return None;
+    };
+
+    Some(Expression::UnaryOp {
+        operator,
+        operand: Box::new(first_child(node)?),
+    })
+}
+
+/// Lower the first child node of `node` as an expression
+fn first_child(node: &SyntaxNode) -> Option<Expression> {
+    node.children().next().map(|child| lower_expression(&child))
+}
+
+/// Lower every child node of `node` as an expression
+fn lower_children(node: &SyntaxNode) -> Vec<Expression> {
+    node.children()
+        .map(|child| lower_expression(&child))
+        .collect()
+}
+
+fn lower_literal(node: &SyntaxNode) -> Option<Expression> {
+    let token = node.tokens().next()?;
+
+    if token.kind() == SyntaxKind::InterpolatedString {
+        let Tokens::InterpolatedString(mut parts) = lex(token.text())? else {
+            return None;
+        };
+        // The token was lexed on its own, so its interpolations start from offset 0
+        for part in &mut parts {
+            if let carbide_lexer::tokens::StringPart::Interpolation { start, .. } = part {
+                start.offset += token.text_range().start;
+            }
+        }
+
+        // Anything wrong with the interpolations was reported when the CST was built
+        return CarbideParser::new(Vec::new())
+            .parse_interpolated_string(&parts)
+            .ok()
+            .map(|parts| Expression::InterpolatedString { parts });
+    }
+
+    literal_value(&token).map(Expression::Literal)
+}
+
+/// Get the value of a literal token, including `true` and `false`
+fn literal_value(token: &SyntaxToken) -> Option<LiteralValue> {
+    if token.kind() == SyntaxKind::Identifier {
+        return Some(LiteralValue::Bool(token.text() == "true"));
+    }
+
+    match lex(token.text())? {
+        Tokens::IntLiteral(value) | Tokens::HexLiteral(value) | Tokens::BinaryLiteral(value) => {
+            Some(LiteralValue::Int(value))
+        }
+        Tokens::FloatLiteral(value) => Some(LiteralValue::Float(value)),
+        Tokens::StringLiteral(value) => Some(LiteralValue::String(value)),
+        _ => None,
+    }
+}
+
+/// Lex the text of a single token again, to get its value
+fn lex(text: &str) -> Option<Tokens<'_>> {
+    CarbideLexer::from_src(text)
+        .lex()
+        .tokens
+        .into_iter()
+        .next()
+        .map(|token| token.token_type)
+}
+
+fn lower_match(expr: &MatchExpr) -> Option<Expression> {
+    let scrutinee = expr.scrutinee()?;
+    // The span covers `match scrutinee`
+    let span = expr.syntax().text_range().start..scrutinee.text_range().end;
+
+    let arms = expr
+        .arms()
+        .filter_map(|arm| {
+            let pattern = arm.pattern()?;
+            Some(MatchArm {
+                pattern: lower_pattern(&pattern),
+                guard: arm.guard().map(|guard| lower_expression(&guard)),
+                body: lower_expression(&arm.body()?),
+                span: pattern.text_range(),
+            })
+        })
+        .collect();
+
+    Some(Expression::Match {
+        scrutinee: Box::new(lower_expression(&scrutinee)),
+        arms,
+        span,
+    })
+}
+
+fn lower_struct_literal(expr: &StructLit) -> Expression {
+    let fields = expr
+        .fields()
+        .filter_map(|field| {
+            let token = field.name()?;
+            let name = token.text().to_string();
+            let value = field.value().map_or_else(
+                // Shorthand, `Point { x }` is `Point { x: x }`
+                || Expression::Identifier {
+                    name: name.clone(),
+                    span: token.text_range(),
+                },
+                |value| lower_expression(&value),
+            );
+
+            Some(FieldInit { name, value })
+        })
+        .collect();
+
+    Expression::StructLiteral {
+        name: expr.name(),
+        fields,
+        base: expr.base().map(|base| Box::new(lower_expression(&base))),
+    }
+}
+
+fn lower_lambda(expr: &LambdaExpr) -> Option<Expression> {
+    let body = expr.body()?;
+    let body = match Block::cast(body.clone()) {
+        Some(block) => LambdaBody::Block(lower_block(&block)),
+        None => LambdaBody::Expression(Box::new(lower_expression(&body))),
+    };
+
+    Some(Expression::Lambda {
+        parameters: lower_params(expr.param_list().as_ref()),
+        return_type: expr.return_type().map(|ty| lower_type(&ty)),
+        body,
+    })
+}
+
+fn lower_if(expr: &IfExpr) -> Option<Expression> {
+    Some(Expression::If {
+        condition: Box::new(lower_expression(&expr.condition()?)),
+        then_branch: lower_block(&expr.then_branch()?),
+        else_branch: expr
+            .else_branch()
+            .map(|branch| Box::new(lower_expression(&branch))),
+    })
+}
+
+/// Lower a type node
+#[must_use]
+pub fn lower_type(node: &SyntaxNode) -> Type {
+    let types = || node.children().map(|child| lower_type(&child));
+    let first = || types().next().map(Box::new);
+
+    let ty = match node.kind() {
+        SyntaxKind::NamedType => node
+            .tokens()
+            .next()
+            .map(|name| Type::Named(name.text().to_string())),
+        SyntaxKind::GenericType => {
+            node.token_of_kind(SyntaxKind::Identifier)
+                .map(|name| Type::Generic {
+                    name: name.text().to_string(),
+                    arguments: types().collect(),
+                })
+        }
+        SyntaxKind::ArrayType => first().map(Type::Array),
+        SyntaxKind::TupleType => Some(Type::Tuple(types().collect())),
+        SyntaxKind::ParenType => first().map(|ty| *ty),
+        SyntaxKind::FunctionType => {
+            let mut parameters: Vec<Type> = types().collect();
+            parameters.pop().map(|return_type| Type::Function {
+                parameters,
+                return_type: Box::new(return_type),
+            })
+        }
+        SyntaxKind::MapType => {
+            let mut types = types();
+            types
+                .next()
+                .zip(types.next())
+                .map(|(key, value)| Type::Map {
+                    key: Box::new(key),
+                    value: Box::new(value),
+                })
+        }
+        SyntaxKind::OptionalType => first().map(Type::Optional),
+        _ => None,
+    };
+
+    ty.unwrap_or(Type::Unit)
+}
+
+/// Lower a pattern node
+#[must_use]
+pub fn lower_pattern(node: &SyntaxNode) -> Pattern {
+    let patterns = || node.children().map(|child| lower_pattern(&child));
+
+    let pattern = match node.kind() {
+        SyntaxKind::LiteralPat => node
+            .tokens()
+            .next()
+            .and_then(|token| literal_value(&token))
+            .map(Pattern::Literal),
+        SyntaxKind::WildcardPat => Some(Pattern::Wildcard),
+        SyntaxKind::BindingPat => {
+            node.token_of_kind(SyntaxKind::Identifier)
+                .map(|name| Pattern::Binding {
+                    name: name.text().to_string(),
+                    span: name.text_range(),
+                })
+        }
+        SyntaxKind::PathPat => Some(Pattern::Path(views::path(node))),
+        SyntaxKind::TupleStructPat => Some(Pattern::TupleStruct {
+            name: views::path(node),
+            elements: patterns().collect(),
+        }),
+        SyntaxKind::StructPat => Some(Pattern::Struct {
+            name: views::path(node),
+            fields: node
+                .children()
+                .filter_map(|field| {
+                    let token = field.token_of_kind(SyntaxKind::Identifier)?;
+                    let name = token.text().to_string();
+                    let pattern = field.children().next().map_or_else(
+                        || Pattern::Binding {
+                            name: name.clone(),
+                            span: token.text_range(),
+                        },
+                        |pattern| lower_pattern(&pattern),
+                    );
+
+                    Some(FieldPattern { name, pattern })
+                })
+                .collect(),
+            rest: node.token_of_kind(SyntaxKind::DotDot).is_some(),
+        }),
+        SyntaxKind::TuplePat => Some(Pattern::Tuple(patterns().collect())),
+        SyntaxKind::ParenPat => patterns().next(),
+        SyntaxKind::ArrayPat => Some(Pattern::Array(patterns().collect())),
+        SyntaxKind::RestPat => {
+            let name = node.token_of_kind(SyntaxKind::Identifier);
+            let span = name
+                .clone()
+                .or_else(|| node.token_of_kind(SyntaxKind::DotDot))
+                .map_or_else(|| node.text_range(), |token| token.text_range());
+            Some(Pattern::Rest {
+                name: name.map(|name| name.text().to_string()),
+                span,
+            })
+        }
+        _ => None,
+    };
+
+    pattern.unwrap_or(Pattern::Wildcard)
+}
@@ -0,0 +1,237 @@
+use std::fmt;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use carbide_lexer::tokens::Span;
+
+use super::green::{GreenElement, GreenNode, GreenToken};
+use super::kind::SyntaxKind;
+
+/// A node in the red tree: a [`GreenNode`] along with its parent and position in the source
+///
+/// Red nodes are created on demand while walking down from the root, so they're cheap to
+/// clone and drop
+#[derive(Clone)]
+pub struct SyntaxNode(Rc<SyntaxNodeData>);
+
+struct SyntaxNodeData {
+    green: GreenNode,
+    parent: Option<SyntaxNode>,
+    offset: u64,
+}
+
+impl SyntaxNode {
+    /// Create the root of a red tree over `green`
+    #[must_use]
+    pub fn new_root(green: GreenNode) -> Self {
+        Self(Rc::new(SyntaxNodeData {
+            green,
+            parent: None,
+            offset: 0,
+        }))
+    }
+
+    #[must_use]
+    pub fn kind(&self) -> SyntaxKind {
+        self.0.green.kind()
+    }
+
+    #[must_use]
+    pub fn green(&self) -> &GreenNode {
+        &self.0.green
+    }
+
+    #[must_use]
+    pub fn parent(&self) -> Option<SyntaxNode> {
+        self.0.parent.clone()
+    }
+
+    /// Get the [`Span`] of the node in the source, including any trivia inside it
+    #[must_use]
+    pub fn text_range(&self) -> Span {
+        self.0.offset..self.0.offset + self.0.green.text_len()
+    }
+
+    /// Get the source text of the node, including trivia
+    #[must_use]
+    pub fn text(&self) -> String {
+        self.0.green.text()
+    }
+
+    /// Iterate over the child nodes and tokens, including trivia
+    pub fn children_with_tokens(&self) -> impl Iterator<Item = SyntaxElement> + '_ {
+        let mut offset = self.0.offset;
+
+        self.0.green.children().iter().map(move |child| {
+            let start = offset;
+            offset += child.text_len();
+
+            match child {
+                GreenElement::Node(node) => SyntaxElement::Node(Self(Rc::new(SyntaxNodeData {
+                    green: node.clone(),
+                    parent: Some(self.clone()),
+                    offset: start,
+                }))),
+                GreenElement::Token(token) => SyntaxElement::Token(SyntaxToken {
+                    green: token.clone(),
+                    parent: self.clone(),
+                    offset: start,
+                }),
+            }
+        })
+    }
+
+    /// Iterate over the child nodes
+    pub fn children(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.children_with_tokens()
+            .filter_map(SyntaxElement::into_node)
+    }
+
+    /// Iterate over the child tokens, skipping trivia
+    pub fn tokens(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        self.children_with_tokens()
+            .filter_map(SyntaxElement::into_token)
+            .filter(|token| !token.kind().is_trivia())
+    }
+
+    /// Find the first child token of `kind` with the text `text`, like the `mut` keyword
+    #[must_use]
+    pub fn token(&self, kind: SyntaxKind, text: &str) -> Option<SyntaxToken> {
+        self.tokens()
+            .find(|token| token.kind() == kind && token.text() == text)
+    }
+
+    /// Find the first child token of `kind`
+    #[must_use]
+    pub fn token_of_kind(&self, kind: SyntaxKind) -> Option<SyntaxToken> {
+        self.tokens().find(|token| token.kind() == kind)
+    }
+
+    /// Find the first child node of `kind`
+    #[must_use]
+    pub fn child_of_kind(&self, kind: SyntaxKind) -> Option<SyntaxNode> {
+        self.children().find(|node| node.kind() == kind)
+    }
+
+    /// Format the tree below this node, one element per line, for debugging
+    #[must_use]
+    pub fn debug_tree(&self) -> String {
+        let mut tree = String::new();
+        self.write_tree(&mut tree, 0);
+        tree
+    }
+
+    fn write_tree(&self, tree: &mut String, depth: usize) {
+        let _ = writeln!(tree, "{:indent$}{self:?}", "", indent = depth * 2);
+
+        for child in self.children_with_tokens() {
+            match child {
+                SyntaxElement::Node(node) => node.write_tree(tree, depth + 1),
+                SyntaxElement::Token(token) => {
+                    let _ = writeln!(tree, "{:indent$}{token:?}", "", indent = (depth + 1) * 2);
+                }
+            }
+        }
+    }
+}
+
+impl PartialEq for SyntaxNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.offset == other.0.offset && self.0.green == other.0.green
+    }
+}
+
+impl Eq for SyntaxNode {}
+
+impl fmt::Debug for SyntaxNode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let range = self.text_range();
+        write!(f, "{:?}@{}..{}", self.kind(), range.start, range.end)
+    }
+}
+
+/// A token in the red tree, with its parent and position in the source
+#[derive(Clone, PartialEq, Eq)]
+pub struct SyntaxToken {
+    green: GreenToken,
+    parent: SyntaxNode,
+    offset: u64,
+}
+
+impl SyntaxToken {
+    #[must_use]
+    pub fn kind(&self) -> SyntaxKind {
+        self.green.kind()
+    }
+
+    #[must_use]
+    pub fn text(&self) -> &str {
+        self.green.text()
+    }
+
+    #[must_use]
+    pub fn parent(&self) -> SyntaxNode {
+        self.parent.clone()
+    }
+
+    /// Get the [`Span`] of the token in the source
+    #[must_use]
+    pub fn text_range(&self) -> Span {
+        self.offset..self.offset + self.green.text_len()
+    }
+}
+
+impl fmt::Debug for SyntaxToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let range = self.text_range();
+        write!(
+            f,
+            "{:?}@{}..{} {:?}",
+            self.kind(),
+            range.start,
+            range.end,
+            self.text()
+        )
+    }
+}
+
+/// Either a [`SyntaxNode`] or a [`SyntaxToken`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SyntaxElement {
+    Node(SyntaxNode),
+    Token(SyntaxToken),
+}
+
+impl SyntaxElement {
+    #[must_use]
+    pub fn kind(&self) -> SyntaxKind {
+        match self {
+            Self::Node(node) => node.kind(),
+            Self::Token(token) => token.kind(),
+        }
+    }
+
+    #[must_use]
+    pub fn text_range(&self) -> Span {
+        match self {
+            Self::Node(node) => node.text_range(),
+            Self::Token(token) => token.text_range(),
+        }
+    }
+
+    #[must_use]
+    pub fn into_node(self) -> Option<SyntaxNode> {
+        match self {
+            Self::Node(node) => Some(node),
+            Self::Token(_) => None,
+        }
+    }
+
+    #[must_use]
+    pub fn into_token(self) -> Option<SyntaxToken> {
+        match self {
+            Self::Node(_) => None,
+            Self::Token(token) => Some(token),
+        }
+    }
+}
@@ -0,0 +1,697 @@
+use super::kind::SyntaxKind;
+use super::red::{SyntaxNode, SyntaxToken};
+use crate::nodes::Visibility;
+
+/// A typed view of a [`SyntaxNode`] of one kind
+pub trait AstNode: Sized {
+    /// Check if nodes of `kind` can be viewed as `Self`
+    fn can_cast(kind: SyntaxKind) -> bool;
+
+    /// View `node` as `Self`, if it's the right kind
+    fn cast(node: SyntaxNode) -> Option<Self>;
+
+    /// Get the underlying [`SyntaxNode`]
+    fn syntax(&self) -> &SyntaxNode;
+}
+
+macro_rules! ast_nodes {
+    ($($(#[$meta:meta])* $name:ident),* $(,)?) => {$(
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq)]
+        pub struct $name(SyntaxNode);
+
+        impl AstNode for $name {
+            fn can_cast(kind: SyntaxKind) -> bool {
+                kind == SyntaxKind::$name
+            }
+
+            fn cast(node: SyntaxNode) -> Option<Self> {
+                Self::can_cast(node.kind()).then_some(Self(node))
+            }
+
+            fn syntax(&self) -> &SyntaxNode {
+                &self.0
+            }
+        }
+    )*};
+}
+
+ast_nodes! {
+    /// The root of the tree
+    SourceFile,
+    /// `#[name(arguments)]`
+    Attribute,
+    /// `let mut pattern: type = initializer;`
+    LetStmt,
+    /// `pub const NAME: type = value;`
+    ConstDecl,
+    /// `pub fn name<types>(params) -> type { ... }`
+    FnDecl,
+    /// `<T: Bound, U>`
+    TypeParamList,
+    /// `name: Bound + Other`
+    TypeParam,
+    /// The parameters of a function or lambda, without their delimiters
+    ParamList,
+    /// `mut pattern: type`
+    Param,
+    /// `pub struct Name { ... }`
+    StructDecl,
+    /// `{ name: type, ... }`
+    FieldList,
+    /// `name: type`
+    FieldDecl,
+    /// `pub enum Name { ... }`
+    EnumDecl,
+    /// `Name`, `Name(types)` or `Name { fields }`
+    Variant,
+    /// `pub trait Name { methods }`
+    TraitDecl,
+    /// `impl Trait for Type { methods }` or `impl Type { methods }`
+    ImplDecl,
+    /// `fn name(params) -> type { ... }`, or `fn name(params) -> type;` in a trait
+    Method,
+    /// `import path.to.module.{items};`
+    ImportDecl,
+    /// `return value;`
+    ReturnStmt,
+    /// `while condition { ... }`
+    WhileStmt,
+    /// `for (initializer; condition; increment) { ... }`
+    ForStmt,
+    /// An expression followed by a `;`
+    ExprStmt,
+    /// `{ statements; tail }`
+    Block,
+    /// `if condition { ... } else ...`
+    IfExpr,
+    /// `match scrutinee { arms }`
+    MatchExpr,
+    /// `pattern if guard => body`
+    MatchArm,
+    /// `|params| -> type => body`
+    LambdaExpr,
+    /// `callee(arguments)`
+    CallExpr,
+    /// `(arguments)`
+    ArgList,
+    /// `lhs op rhs`
+    BinaryExpr,
+    /// `target = value`
+    AssignExpr,
+    /// `Name { fields, ..base }`
+    StructLit,
+    /// `name: value` or the shorthand `name`
+    FieldInit,
+}
+
+/// Get the name of a declaration, which is its first identifier
+fn name(node: &SyntaxNode) -> Option<SyntaxToken> {
+    node.token_of_kind(SyntaxKind::Identifier)
+}
+
+/// Check if a declaration has a `mut` keyword
+fn is_mutable(node: &SyntaxNode) -> bool {
+    node.token(SyntaxKind::Keyword, "mut").is_some()
+}
+
+/// Get the visibility of an item from its `pub`
+fn visibility(node: &SyntaxNode) -> Visibility {
+    node.child_of_kind(SyntaxKind::Visibility)
+        .map_or(Visibility::Private, |_| Visibility::Public)
+}
+
+/// Get the first child node that is a type
+fn type_annotation(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.children().find(|child| child.kind().is_type())
+}
+
+/// Get the first child node that is a pattern
+fn pattern(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.children().find(|child| child.kind().is_pattern())
+}
+
+/// Get the first child node that is an expression
+fn expression(node: &SyntaxNode) -> Option<SyntaxNode> {
+    node.children().find(|child| child.kind().is_expression())
+}
+
+/// Get the child nodes that are `T`
+fn children<'a, T: AstNode + 'a>(node: &'a SyntaxNode) -> impl Iterator<Item = T> + 'a {
+    node.children().filter_map(T::cast)
+}
+
+impl SourceFile {
+    /// Get the top level statements, including [`SyntaxKind::Error`] nodes
+    pub fn statements(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children()
+    }
+}
+
+impl Attribute {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    /// Get the literal arguments, which are [`SyntaxKind::Literal`] nodes
+    pub fn arguments(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children()
+    }
+}
+
+impl LetStmt {
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+        children(&self.0)
+    }
+
+    #[must_use]
+    pub fn is_mutable(&self) -> bool {
+        is_mutable(&self.0)
+    }
+
+    #[must_use]
+    pub fn pattern(&self) -> Option<SyntaxNode> {
+        pattern(&self.0)
+    }
+
+    #[must_use]
+    pub fn type_annotation(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+
+    #[must_use]
+    pub fn initializer(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+}
+
+impl ConstDecl {
+    #[must_use]
+    pub fn visibility(&self) -> Visibility {
+        visibility(&self.0)
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    #[must_use]
+    pub fn type_annotation(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+
+    #[must_use]
+    pub fn value(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+}
+
+impl FnDecl {
+    pub fn attributes(&self) -> impl Iterator<Item = Attribute> + '_ {
+        children(&self.0)
+    }
+
+    #[must_use]
+    pub fn visibility(&self) -> Visibility {
+        visibility(&self.0)
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    #[must_use]
+    pub fn type_param_list(&self) -> Option<TypeParamList> {
+        children(&self.0).next()
+    }
+
+    #[must_use]
+    pub fn param_list(&self) -> Option<ParamList> {
+        children(&self.0).next()
+    }
+
+    #[must_use]
+    pub fn return_type(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+
+    #[must_use]
+    pub fn body(&self) -> Option<Block> {
+        children(&self.0).next()
+    }
+}
+
+impl TypeParamList {
+    pub fn type_params(&self) -> impl Iterator<Item = TypeParam> + '_ {
+        children(&self.0)
+    }
+}
+
+impl TypeParam {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    /// Get the names of the bounds after the `:`
+    pub fn bounds(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        self.0
+            .tokens()
+            .filter(|token| token.kind() == SyntaxKind::Identifier)
+            .skip(1)
+    }
+}
+
+impl ParamList {
+    pub fn params(&self) -> impl Iterator<Item = Param> + '_ {
+        children(&self.0)
+    }
+}
+
+impl Param {
+    #[must_use]
+    pub fn is_mutable(&self) -> bool {
+        is_mutable(&self.0)
+    }
+
+    #[must_use]
+    pub fn pattern(&self) -> Option<SyntaxNode> {
+        pattern(&self.0)
+    }
+
+    #[must_use]
+    pub fn type_annotation(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+}
+
+impl StructDecl {
+    #[must_use]
+    pub fn visibility(&self) -> Visibility {
+        visibility(&self.0)
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    #[must_use]
+    pub fn field_list(&self) -> Option<FieldList> {
+        children(&self.0).next()
+    }
+}
+
+impl FieldList {
+    pub fn fields(&self) -> impl Iterator<Item = FieldDecl> + '_ {
+        children(&self.0)
+    }
+}
+
+impl FieldDecl {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    #[must_use]
+    pub fn type_annotation(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+}
+
+impl EnumDecl {
+    #[must_use]
+    pub fn visibility(&self) -> Visibility {
+        visibility(&self.0)
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    pub fn variants(&self) -> impl Iterator<Item = Variant> + '_ {
+        children(&self.0)
+    }
+}
+
+impl Variant {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    /// Get the fields of a struct variant, like `Rect { w: float, h: float }`
+    #[must_use]
+    pub fn field_list(&self) -> Option<FieldList> {
+        children(&self.0).next()
+    }
+
+    /// Get the types of a tuple variant, like `Circle(float)`
+    #[must_use]
+    pub fn tuple_types(&self) -> Option<Vec<SyntaxNode>> {
+        self.0.token_of_kind(SyntaxKind::LeftParen)?;
+        Some(
+            self.0
+                .children()
+                .filter(|child| child.kind().is_type())
+                .collect(),
+        )
+    }
+}
+
+impl TraitDecl {
+    #[must_use]
+    pub fn visibility(&self) -> Visibility {
+        visibility(&self.0)
+    }
+
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = Method> + '_ {
+        children(&self.0)
+    }
+}
+
+impl ImplDecl {
+    /// Get the name of the trait before `for`, if there is one
+    #[must_use]
+    pub fn trait_name(&self) -> Option<SyntaxToken> {
+        self.0.token(SyntaxKind::Keyword, "for")?;
+        name(&self.0)
+    }
+
+    /// Get the name of the type the methods belong to, which is the last name before `{`
+    #[must_use]
+    pub fn target(&self) -> Option<SyntaxToken> {
+        self.0
+            .tokens()
+            .filter(|token| token.kind() == SyntaxKind::Identifier)
+            .last()
+    }
+
+    pub fn methods(&self) -> impl Iterator<Item = Method> + '_ {
+        children(&self.0)
+    }
+}
+
+impl Method {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    #[must_use]
+    pub fn param_list(&self) -> Option<ParamList> {
+        children(&self.0).next()
+    }
+
+    #[must_use]
+    pub fn return_type(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+
+    /// Get the body, which is missing for `fn name(params) -> type;`
+    #[must_use]
+    pub fn body(&self) -> Option<Block> {
+        children(&self.0).next()
+    }
+}
+
+impl ImportDecl {
+    /// Get the names in the module path, like `util` and `strings` in
+    /// `import util.strings.{trim};`
+    pub fn path(&self) -> impl Iterator<Item = SyntaxToken> + '_ {
+        self.0
+            .tokens()
+            .take_while(|token| token.kind() != SyntaxKind::LeftBrace)
+            .filter(|token| token.kind() == SyntaxKind::Identifier)
+    }
+
+    /// Get the items imported from the module, if there is a `{ ... }` list
+    #[must_use]
+    pub fn items(&self) -> Option<Vec<SyntaxToken>> {
+        self.0.token_of_kind(SyntaxKind::LeftBrace)?;
+        Some(
+            self.0
+                .tokens()
+                .skip_while(|token| token.kind() != SyntaxKind::LeftBrace)
+                .filter(|token| token.kind() == SyntaxKind::Identifier)
+                .collect(),
+        )
+    }
+}
+
+impl ReturnStmt {
+    #[must_use]
+    pub fn value(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+}
+
+impl WhileStmt {
+    #[must_use]
+    pub fn condition(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+
+    #[must_use]
+    pub fn body(&self) -> Option<Block> {
+        self.0.children().last().and_then(Block::cast)
+    }
+}
+
+impl ForStmt {
+    /// Get the [`LetStmt`] or [`ExprStmt`] that runs before the loop
+    #[must_use]
+    pub fn initializer(&self) -> Option<SyntaxNode> {
+        self.0
+            .children()
+            .find(|child| matches!(child.kind(), SyntaxKind::LetStmt | SyntaxKind::ExprStmt))
+    }
+
+    #[must_use]
+    pub fn condition(&self) -> Option<SyntaxNode> {
+        self.header_expression(true)
+    }
+
+    #[must_use]
+    pub fn increment(&self) -> Option<SyntaxNode> {
+        self.header_expression(false)
+    }
+
+    #[must_use]
+    pub fn body(&self) -> Option<Block> {
+        self.0.children().last().and_then(Block::cast)
+    }
+
+    /// Get the expression in the `( ... )` before or after the `;` that ends the condition
+    fn header_expression(&self, before: bool) -> Option<SyntaxNode> {
+        let start = |token: SyntaxToken| token.text_range().start;
+        let separator = self
+            .0
+            .tokens()
+            .filter(|token| token.kind() == SyntaxKind::Semicolon)
+            .last()
+            .map(start)?;
+        let close = self.0.token_of_kind(SyntaxKind::RightParen).map(start)?;
+
+        self.0.children().find(|child| {
+            let offset = child.text_range().start;
+            child.kind().is_expression() && offset < close && (offset < separator) == before
+        })
+    }
+}
+
+impl ExprStmt {
+    #[must_use]
+    pub fn expr(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+}
+
+impl Block {
+    /// Get the statements of the block, which are every item but the tail expression
+    pub fn statements(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        let tail = self.tail();
+        self.0
+            .children()
+            .filter(move |child| tail.as_ref() != Some(child))
+    }
+
+    /// Get the expression at the end of the block that gives its value, if there is one
+    ///
+    /// A nested block without a value of its own is a statement rather than the tail
+    #[must_use]
+    pub fn tail(&self) -> Option<SyntaxNode> {
+        let last = self.0.children().last()?;
+
+        match Self::cast(last.clone()) {
+            Some(block) => block.tail().map(|_| last),
+            None => last.kind().is_expression().then_some(last),
+        }
+    }
+}
+
+impl IfExpr {
+    #[must_use]
+    pub fn condition(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    #[must_use]
+    pub fn then_branch(&self) -> Option<Block> {
+        self.0.children().nth(1).and_then(Block::cast)
+    }
+
+    /// Get the [`Block`] or nested [`IfExpr`] after `else`
+    #[must_use]
+    pub fn else_branch(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(2)
+    }
+}
+
+impl MatchExpr {
+    #[must_use]
+    pub fn scrutinee(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    pub fn arms(&self) -> impl Iterator<Item = MatchArm> + '_ {
+        children(&self.0)
+    }
+}
+
+impl MatchArm {
+    #[must_use]
+    pub fn pattern(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    #[must_use]
+    pub fn guard(&self) -> Option<SyntaxNode> {
+        self.0.token(SyntaxKind::Keyword, "if")?;
+        self.0.children().nth(1)
+    }
+
+    #[must_use]
+    pub fn body(&self) -> Option<SyntaxNode> {
+        self.0.children().last()
+    }
+}
+
+impl LambdaExpr {
+    /// Get the parameters, which are missing for `||`
+    #[must_use]
+    pub fn param_list(&self) -> Option<ParamList> {
+        children(&self.0).next()
+    }
+
+    #[must_use]
+    pub fn return_type(&self) -> Option<SyntaxNode> {
+        type_annotation(&self.0)
+    }
+
+    /// Get the [`Block`] or expression after `=>`
+    #[must_use]
+    pub fn body(&self) -> Option<SyntaxNode> {
+        self.0.children().last()
+    }
+}
+
+impl CallExpr {
+    #[must_use]
+    pub fn callee(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    #[must_use]
+    pub fn arg_list(&self) -> Option<ArgList> {
+        children(&self.0).next()
+    }
+}
+
+impl ArgList {
+    /// Get the arguments, including [`SyntaxKind::Error`] nodes
+    pub fn args(&self) -> impl Iterator<Item = SyntaxNode> + '_ {
+        self.0.children()
+    }
+}
+
+impl BinaryExpr {
+    #[must_use]
+    pub fn lhs(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    #[must_use]
+    pub fn operator(&self) -> Option<SyntaxToken> {
+        self.0.token_of_kind(SyntaxKind::BinaryOperator)
+    }
+
+    #[must_use]
+    pub fn rhs(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+}
+
+impl AssignExpr {
+    #[must_use]
+    pub fn target(&self) -> Option<SyntaxNode> {
+        self.0.children().next()
+    }
+
+    #[must_use]
+    pub fn value(&self) -> Option<SyntaxNode> {
+        self.0.children().nth(1)
+    }
+}
+
+impl StructLit {
+    /// Get the name of the struct, like `Shape::Rect`
+    #[must_use]
+    pub fn name(&self) -> String {
+        path(&self.0)
+    }
+
+    pub fn fields(&self) -> impl Iterator<Item = FieldInit> + '_ {
+        children(&self.0)
+    }
+
+    /// Get the expression after `..`
+    #[must_use]
+    pub fn base(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+}
+
+impl FieldInit {
+    #[must_use]
+    pub fn name(&self) -> Option<SyntaxToken> {
+        name(&self.0)
+    }
+
+    /// Get the value, which is missing for the shorthand `name`
+    #[must_use]
+    pub fn value(&self) -> Option<SyntaxNode> {
+        expression(&self.0)
+    }
+}
+
+/// Join the identifiers of a possibly qualified name directly inside `node`, like
+/// `Shape::Circle`
+#[must_use]
+pub fn path(node: &SyntaxNode) -> String {
+    node.tokens()
+        .filter(|token| token.kind() == SyntaxKind::Identifier)
+        .map(|token| token.text().to_string())
+        .collect::<Vec<_>>()
+        .join("::")
+}
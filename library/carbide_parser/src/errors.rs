@@ -1,31 +1,206 @@
 use ariadne::{Color, Label, Report, ReportKind, Span};
 use carbide_errors::{
-    codes::{E1001, E1002, E1010, E1011, E1020, E1021, E1030, E1040, E1041, E1042, E1100},
+    codes::{
+        E1001, E1002, E1003, E1010, E1011, E1020, E1021, E1022, E1023, E1030, E1040, E1041, E1042,
+        E1043, E1050, E1060, E1070, E1100,
+    },
     error::CarbideError,
+    suggestion::{Applicability, Suggestion},
 };
 use carbide_lexer::errors::ErrorSpan;
+use carbide_lexer::keywords::Keywords;
+use std::fmt;
+
 use carbide_lexer::tokens::{SourceLocation, Token};
 use thiserror::Error;
 
+/// An owned summary of a [`Token`], so errors don't borrow the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenSummary {
+    /// The token type, like `Identifier("x")`
+    pub kind: String,
+    /// Source text of the token
+    pub text: String,
+    pub span: carbide_lexer::tokens::Span,
+}
+
+impl From<&Token<'_>> for TokenSummary {
+    fn from(token: &Token<'_>) -> Self {
+        Self {
+            kind: format!("{:?}", token.token_type),
+            text: token.src.to_string(),
+            span: token.span.clone(),
+        }
+    }
+}
+
+impl fmt::Display for TokenSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "`{}` ({}) at {}..{}",
+            self.text, self.kind, self.span.start, self.span.end
+        )
+    }
+}
+
+/// Something the parser would have accepted where it failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Expected {
+    /// A specific token, like `)`, holding its source text
+    Token(&'static str),
+    /// A kind of syntax, like `an expression`, holding its description
+    Kind(&'static str),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Token(token) => write!(f, "`{token}`"),
+            Self::Kind(kind) => f.write_str(kind),
+        }
+    }
+}
+
+/// Everything the parser tried at the token where it failed, like `,`, `)` and an operator
+/// after the `a` in `f(a;`
+///
+/// Specific tokens come before kinds of syntax, and each group keeps the order the parser
+/// tried them in
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExpectedSet {
+    items: Vec<Expected>,
+}
+
+impl ExpectedSet {
+    /// Create an empty [`ExpectedSet`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `expected`, unless it's already in the set
+    pub fn insert(&mut self, expected: Expected) {
+        if self.contains(&expected) {
+            return;
+        }
+
+        let index = match expected {
+            Expected::Token(_) => self
+                .items
+                .iter()
+                .position(|item| matches!(item, Expected::Kind(_)))
+                .unwrap_or(self.items.len()),
+            Expected::Kind(_) => self.items.len(),
+        };
+        self.items.insert(index, expected);
+    }
+
+    /// Remove everything from the set
+    pub fn clear(&mut self) {
+        self.items.clear();
+    }
+
+    /// Check if `expected` is in the set
+    #[must_use]
+    pub fn contains(&self, expected: &Expected) -> bool {
+        self.items.contains(expected)
+    }
+
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Expected> {
+        self.items.iter()
+    }
+
+    /// Get the source text of every specific token in the set, like the candidates for
+    /// completing the input at the error
+    pub fn tokens(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.items.iter().filter_map(|item| match item {
+            Expected::Token(token) => Some(*token),
+            Expected::Kind(_) => None,
+        })
+    }
+}
+
+impl FromIterator<Expected> for ExpectedSet {
+    fn from_iter<I: IntoIterator<Item = Expected>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Expected> for ExpectedSet {
+    fn extend<I: IntoIterator<Item = Expected>>(&mut self, iter: I) {
+        for expected in iter {
+            self.insert(expected);
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a ExpectedSet {
+    type Item = &'a Expected;
+    type IntoIter = std::slice::Iter<'a, Expected>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.items.iter()
+    }
+}
+
+/// Lists the set like `` `)` ``, `` `,` or `)` `` or `` one of `,`, `)`, or an operator ``
+impl fmt::Display for ExpectedSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.items.as_slice() {
+            [] => f.write_str("more input"),
+            [only] => write!(f, "{only}"),
+            [first, second] => write!(f, "{first} or {second}"),
+            [rest @ .., last] => {
+                f.write_str("one of ")?;
+                for item in rest {
+                    write!(f, "{item}, ")?;
+                }
+                write!(f, "or {last}")
+            }
+        }
+    }
+}
+
 #[derive(Debug, Error, Clone, PartialEq)]
 pub enum CarbideParserError {
-    #[error("Unexpected end of file at [{0}]")]
-    UnexpectedEOF(SourceLocation),
+    #[error("Unexpected end of file at [{location}], expected {expected}")]
+    UnexpectedEOF {
+        location: SourceLocation,
+        expected: ExpectedSet,
+    },
 
     #[error("Expected {expected}, but found {found}")]
     UnexpectedToken {
-        expected: String,
-        found: Token<'static>,
+        expected: ExpectedSet,
+        found: TokenSummary,
     },
 
+    /// The location is the end of the token the `;` should follow
+    #[error("Expected `;` at [{0}]")]
+    MissingSemicolon(SourceLocation),
+
     #[error("Expected identifier, but found {0}")]
-    ExpectedIdentifier(Token<'static>),
+    ExpectedIdentifier(TokenSummary),
 
     #[error("Expected expression at [{0}]")]
     ExpectedExpression(SourceLocation),
 
-    #[error("Invalid assignment target at [{0}]")]
-    InvalidAssignmentTarget(SourceLocation),
+    /// The span covers the whole left-hand side of the `=`
+    #[error("Invalid assignment target at {}..{}", .0.start, .0.end)]
+    InvalidAssignmentTarget(carbide_lexer::tokens::Span),
 
     #[error("Too many parameters in function declaration at [{0}]")]
     TooManyParameters(SourceLocation),
@@ -33,6 +208,12 @@ pub enum CarbideParserError {
     #[error("Too many arguments in function call at [{0}]")]
     TooManyArguments(SourceLocation),
 
+    #[error("Nesting is too deep at [{0}]")]
+    NestingTooDeep(SourceLocation),
+
+    #[error("Too many tokens in the input at [{0}]")]
+    TooManyTokens(SourceLocation),
+
     #[error("Break statement outside of loop at [{0}]")]
     BreakOutsideLoop(SourceLocation),
 
@@ -42,37 +223,72 @@ pub enum CarbideParserError {
     #[error("Return statement outside of function at [{0}]")]
     ReturnOutsideFunction(SourceLocation),
 
+    #[error("Constant declared outside of the top level at [{0}]")]
+    ConstOutsideTopLevel(SourceLocation),
+
+    #[error("Attributes must be followed by a function or `let` declaration, but found {0}")]
+    MisplacedAttribute(TokenSummary),
+
+    #[error("Array pattern has more than one `..` at [{0}]")]
+    MultipleRestPatterns(SourceLocation),
+
+    /// The span covers the `..` and the base expression
+    #[error("Struct base is not the last field at {}..{}", .0.start, .0.end)]
+    MisplacedStructBase(carbide_lexer::tokens::Span),
+
     #[error("Cast `{0}` as `{1}` failed!")]
     CastFailed(String, String),
 }
 
+impl CarbideParserError {
+    /// Get everything the parser would have accepted where it failed, for errors about
+    /// an unexpected token or end of the input
+    #[must_use]
+    pub fn expected(&self) -> Option<&ExpectedSet> {
+        match self {
+            Self::UnexpectedEOF { expected, .. } | Self::UnexpectedToken { expected, .. } => {
+                Some(expected)
+            }
+            _ => None,
+        }
+    }
+}
+
 impl CarbideError for CarbideParserError {
     type Span = ErrorSpan;
 
     fn code(&self) -> carbide_errors::codes::ErrCode {
         match self {
-            Self::UnexpectedEOF(_) => E1001,
+            Self::UnexpectedEOF { .. } => E1001,
             Self::UnexpectedToken { .. } => E1002,
+            Self::MissingSemicolon(_) => E1003,
             Self::ExpectedIdentifier(_) => E1010,
             Self::ExpectedExpression(_) => E1011,
             Self::TooManyParameters(_) => E1020,
             Self::TooManyArguments(_) => E1021,
+            Self::NestingTooDeep(_) => E1022,
+            Self::TooManyTokens(_) => E1023,
             Self::InvalidAssignmentTarget(_) => E1030,
             Self::BreakOutsideLoop(_) => E1040,
             Self::ContinueOutsideLoop(_) => E1041,
             Self::ReturnOutsideFunction(_) => E1042,
+            Self::ConstOutsideTopLevel(_) => E1043,
+            Self::MisplacedAttribute(_) => E1050,
+            Self::MultipleRestPatterns(_) => E1060,
+            Self::MisplacedStructBase(_) => E1070,
             Self::CastFailed(_, _) => E1100,
         }
     }
 
     fn help(&self) -> Option<&'static str> {
         match self {
-            Self::UnexpectedEOF(_) => {
+            Self::UnexpectedEOF { .. } => {
                 Some("Try closing any unclosed parentheses, braces, or quotes.")
             }
             Self::UnexpectedToken { .. } => {
                 Some("Check for missing operators, delimiters, or keywords.")
             }
+            Self::MissingSemicolon(_) => Some("Statements must end with a `;`."),
             Self::ExpectedIdentifier(_) => {
                 Some("Identifiers must start with a letter or underscore.")
             }
@@ -80,17 +296,33 @@ impl CarbideError for CarbideParserError {
                 Some("You might have forgotten to include a value or expression.")
             }
             Self::InvalidAssignmentTarget(_) => {
-                Some("Only variables or fields can appear on the left side of an assignment.")
+                Some("Only variables, indexing and fields can be assigned to.")
             }
             Self::TooManyParameters(_) => {
                 Some("Reduce the number of parameters to fit within the allowed limit.")
             }
             Self::TooManyArguments(_) => {
-                Some("Reduce the number of arguments to match the function signature.")
+                Some("Reduce the number of arguments to fit within the allowed limit.")
             }
+            Self::NestingTooDeep(_) => {
+                Some("Move some of the nested parts into separate declarations or variables.")
+            }
+            Self::TooManyTokens(_) => Some("Split the input into smaller files."),
             Self::BreakOutsideLoop(_) => Some("`break` can only appear inside a loop."),
             Self::ContinueOutsideLoop(_) => Some("`continue` can only appear inside a loop."),
             Self::ReturnOutsideFunction(_) => Some("`return` can only appear inside a function."),
+            Self::ConstOutsideTopLevel(_) => {
+                Some("Move the `const` to the top level, or use `let` instead.")
+            }
+            Self::MisplacedAttribute(_) => {
+                Some("Attributes can only be placed on functions and `let` declarations.")
+            }
+            Self::MultipleRestPatterns(_) => {
+                Some("Only one `..` is allowed, since the elements it skips would be ambiguous.")
+            }
+            Self::MisplacedStructBase(_) => {
+                Some("Move the `..base` after the other fields, since it only fills the rest.")
+            }
             Self::CastFailed(_, _) => {
                 Some("This is a compiler bug, please open an issue on github")
             }
@@ -101,8 +333,24 @@ impl CarbideError for CarbideParserError {
         format!("{self}")
     }
 
+    fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::MissingSemicolon(loc) => usize::try_from(loc.offset)
+                .map(|offset| {
+                    vec![Suggestion::insert(
+                        "Add a `;`",
+                        offset,
+                        ";",
+                        Applicability::MachineApplicable,
+                    )]
+                })
+                .unwrap_or_default(),
+            _ => Vec::new(),
+        }
+    }
+
     #[allow(clippy::too_many_lines)]
-    fn report(&'_ self, file: &str, src: &str) -> Result<Report<'_, Self::Span>, Self>
+    fn report(&'_ self, file: &str, src: &str) -> Result<Report<'_, Self::Span>, Box<Self>>
     where
         Self: Sized,
     {
@@ -112,16 +360,24 @@ impl CarbideError for CarbideParserError {
             })?;
             Ok(ErrorSpan::new(file, offset.saturating_sub(1), offset))
         };
+        let make_range = |span: &carbide_lexer::tokens::Span| -> Result<ErrorSpan, Self> {
+            let cast = |offset: u64| {
+                usize::try_from(offset).map_err(|_| {
+                    CarbideParserError::CastFailed(offset.to_string(), "usize".to_string())
+                })
+            };
+            Ok(ErrorSpan::new(file, cast(span.start)?, cast(span.end)?))
+        };
 
         let mut report = match self {
-            Self::UnexpectedEOF(loc) => {
-                let span = make_span(loc)?;
+            Self::UnexpectedEOF { location, expected } => {
+                let span = make_span(location)?;
                 Report::build(ReportKind::Error, span.clone())
                     .with_code(self.code().to_string())
                     .with_message("Unexpected end of file")
                     .with_label(
                         Label::new(span.clone())
-                            .with_message("File ended here, but more code was expected")
+                            .with_message(format!("File ended here, but expected {expected}"))
                             .with_color(Color::BrightRed),
                     )
                     .with_note("Try closing unclosed blocks, strings, or parentheses.")
@@ -140,13 +396,34 @@ impl CarbideError for CarbideParserError {
                 );
                 Report::build(ReportKind::Error, span.clone())
                     .with_code(self.code().to_string())
-                    .with_message(format!("Unexpected token `{}`", found.src))
+                    .with_message(format!("Expected {expected}, found `{}`", found.text))
                     .with_label(
                         Label::new(span.clone())
-                            .with_message(format!("Expected `{expected}` here"))
+                            .with_message(format!("Expected {expected} here"))
+                            .with_color(Color::BrightRed),
+                    )
+                    .with_note(format!("Found token of type `{}`", found.kind))
+            }
+
+            Self::MissingSemicolon(loc) => {
+                let offset = usize::try_from(loc.offset).map_err(|_| {
+                    CarbideParserError::CastFailed(loc.offset.to_string(), "usize".to_string())
+                })?;
+                // Point at the character just past the previous token, or at its last
+                // character when the source ends there
+                let span = if offset < src.len() {
+                    ErrorSpan::new(file, offset, offset + 1)
+                } else {
+                    ErrorSpan::new(file, offset.saturating_sub(1), offset)
+                };
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Missing `;`")
+                    .with_label(
+                        Label::new(span.clone())
+                            .with_message("Add `;` here")
                             .with_color(Color::BrightRed),
                     )
-                    .with_note(format!("Found token of type `{:?}`", found.token_type))
             }
 
             Self::ExpectedIdentifier(found) => {
@@ -170,7 +447,7 @@ impl CarbideError for CarbideParserError {
                     .with_message("Expected identifier")
                     .with_label(
                         Label::new(span.clone())
-                            .with_message(format!("Found `{}` instead", found.src))
+                            .with_message(format!("Found `{}` instead", found.text))
                             .with_color(Color::BrightRed),
                     )
                     .with_note("Identifiers must start with a letter or underscore.")
@@ -189,8 +466,8 @@ impl CarbideError for CarbideParserError {
                     .with_note("Try adding a value, literal, or variable.")
             }
 
-            Self::InvalidAssignmentTarget(loc) => {
-                let span = make_span(loc)?;
+            Self::InvalidAssignmentTarget(target) => {
+                let span = make_range(target)?;
                 let snippet = src
                     .get(span.start()..span.end().min(src.len()))
                     .unwrap_or("");
@@ -202,15 +479,24 @@ impl CarbideError for CarbideParserError {
                             .with_message(format!("`{snippet}` cannot be assigned to"))
                             .with_color(Color::BrightRed),
                     )
-                    .with_note("Only variables or properties can be assigned.")
+                    .with_note("Assign to a place like `x`, `xs[0]` or `p.x` instead.")
             }
 
-            Self::TooManyParameters(loc) | Self::TooManyArguments(loc) => {
-                let span = make_span(loc)?;
+            Self::TooManyParameters(loc)
+            | Self::TooManyArguments(loc)
+            | Self::NestingTooDeep(loc)
+            | Self::TooManyTokens(loc) => {
                 let msg = match self {
                     Self::TooManyParameters(_) => "Too many parameters in function declaration",
-                    _ => "Too many arguments in function call",
+                    Self::TooManyArguments(_) => "Too many arguments in function call",
+                    Self::NestingTooDeep(_) => "Nesting is too deep",
+                    _ => "Too many tokens in the input",
                 };
+                // `loc` is the start of the first token past the limit
+                let start = usize::try_from(loc.offset).map_err(|_| {
+                    CarbideParserError::CastFailed(loc.offset.to_string(), "usize".to_string())
+                })?;
+                let span = ErrorSpan::new(file, start, start + 1);
                 Report::build(ReportKind::Error, span.clone())
                     .with_code(self.code().to_string())
                     .with_message(msg)
@@ -223,14 +509,26 @@ impl CarbideError for CarbideParserError {
 
             Self::BreakOutsideLoop(loc)
             | Self::ContinueOutsideLoop(loc)
-            | Self::ReturnOutsideFunction(loc) => {
-                let span = make_span(loc)?;
-                let msg = match self {
-                    Self::BreakOutsideLoop(_) => "`break` outside of loop",
-                    Self::ContinueOutsideLoop(_) => "`continue` outside of loop",
-                    Self::ReturnOutsideFunction(_) => "`return` outside of function",
+            | Self::ReturnOutsideFunction(loc)
+            | Self::ConstOutsideTopLevel(loc) => {
+                let (keyword, msg) = match self {
+                    Self::BreakOutsideLoop(_) => (Keywords::Break, "`break` outside of loop"),
+                    Self::ContinueOutsideLoop(_) => {
+                        (Keywords::Continue, "`continue` outside of loop")
+                    }
+                    Self::ReturnOutsideFunction(_) => {
+                        (Keywords::Return, "`return` outside of function")
+                    }
+                    Self::ConstOutsideTopLevel(_) => {
+                        (Keywords::Const, "`const` outside of the top level")
+                    }
                     _ => unreachable!(),
                 };
+                // `loc` is the start of the keyword, so underline the whole keyword
+                let start = usize::try_from(loc.offset).map_err(|_| {
+                    CarbideParserError::CastFailed(loc.offset.to_string(), "usize".to_string())
+                })?;
+                let span = ErrorSpan::new(file, start, start + keyword.as_str().len());
                 Report::build(ReportKind::Error, span.clone())
                     .with_code(self.code().to_string())
                     .with_message(msg)
@@ -241,6 +539,60 @@ impl CarbideError for CarbideParserError {
                     )
             }
 
+            Self::MisplacedAttribute(found) => {
+                let span = ErrorSpan::new(
+                    file,
+                    usize::try_from(found.span.start).map_err(|_| {
+                        CarbideParserError::CastFailed(
+                            found.span.start.to_string(),
+                            "usize".to_string(),
+                        )
+                    })?,
+                    usize::try_from(found.span.end).map_err(|_| {
+                        CarbideParserError::CastFailed(
+                            found.span.end.to_string(),
+                            "usize".to_string(),
+                        )
+                    })?,
+                );
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Attribute on an unsupported item")
+                    .with_label(
+                        Label::new(span.clone())
+                            .with_message(format!("Attributes can't be placed on `{}`", found.text))
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MultipleRestPatterns(loc) => {
+                // `loc` is the start of the `..`
+                let start = usize::try_from(loc.offset).map_err(|_| {
+                    CarbideParserError::CastFailed(loc.offset.to_string(), "usize".to_string())
+                })?;
+                let span = ErrorSpan::new(file, start, start + 2);
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Multiple `..` in an array pattern")
+                    .with_label(
+                        Label::new(span.clone())
+                            .with_message("Another `..` was already used in this pattern")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
+            Self::MisplacedStructBase(base) => {
+                let span = make_range(base)?;
+                Report::build(ReportKind::Error, span.clone())
+                    .with_code(self.code().to_string())
+                    .with_message("Struct base is not the last field")
+                    .with_label(
+                        Label::new(span.clone())
+                            .with_message("This has to come after every other field")
+                            .with_color(Color::BrightRed),
+                    )
+            }
+
             CarbideParserError::CastFailed(_, _) => {
                 let span = ErrorSpan::new(file, 0, 1);
                 Report::build(ReportKind::Error, span.clone())
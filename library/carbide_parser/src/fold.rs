@@ -0,0 +1,566 @@
+//! AST-to-AST rewriting
+//!
+//! Each `fold_*` method of [`Folder`] takes a node by value and defaults to the matching
+//! `walk_*` function, which rebuilds the node from its folded children. Desugarings can
+//! override the methods for the nodes they replace, and return anything in their place
+
+use crate::nodes::{
+    Attribute, Block, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody, LiteralValue,
+    MapEntry, MatchArm, Method, Parameter, Pattern, Program, Statement, StringPart, StructField,
+    Type, TypeParameter, VariantPayload, Visibility,
+};
+
+/// Rewrites the nodes of an owned AST
+pub trait Folder {
+    fn fold_program(&mut self, program: Program) -> Program {
+        walk_program(self, program)
+    }
+
+    fn fold_statement(&mut self, statement: Statement) -> Statement {
+        walk_statement(self, statement)
+    }
+
+    fn fold_expression(&mut self, expression: Expression) -> Expression {
+        walk_expression(self, expression)
+    }
+
+    fn fold_block(&mut self, block: Block) -> Block {
+        walk_block(self, block)
+    }
+
+    fn fold_lambda_body(&mut self, body: LambdaBody) -> LambdaBody {
+        walk_lambda_body(self, body)
+    }
+
+    fn fold_string_part(&mut self, part: StringPart) -> StringPart {
+        walk_string_part(self, part)
+    }
+
+    fn fold_match_arm(&mut self, arm: MatchArm) -> MatchArm {
+        walk_match_arm(self, arm)
+    }
+
+    fn fold_map_entry(&mut self, entry: MapEntry) -> MapEntry {
+        walk_map_entry(self, entry)
+    }
+
+    fn fold_field_init(&mut self, field: FieldInit) -> FieldInit {
+        walk_field_init(self, field)
+    }
+
+    fn fold_pattern(&mut self, pattern: Pattern) -> Pattern {
+        walk_pattern(self, pattern)
+    }
+
+    fn fold_field_pattern(&mut self, field: FieldPattern) -> FieldPattern {
+        walk_field_pattern(self, field)
+    }
+
+    fn fold_attribute(&mut self, attribute: Attribute) -> Attribute {
+        walk_attribute(self, attribute)
+    }
+
+    fn fold_parameter(&mut self, parameter: Parameter) -> Parameter {
+        walk_parameter(self, parameter)
+    }
+
+    fn fold_method(&mut self, method: Method) -> Method {
+        walk_method(self, method)
+    }
+
+    fn fold_struct_field(&mut self, field: StructField) -> StructField {
+        walk_struct_field(self, field)
+    }
+
+    fn fold_enum_variant(&mut self, variant: EnumVariant) -> EnumVariant {
+        walk_enum_variant(self, variant)
+    }
+
+    fn fold_variant_payload(&mut self, payload: VariantPayload) -> VariantPayload {
+        walk_variant_payload(self, payload)
+    }
+
+    fn fold_type(&mut self, ty: Type) -> Type {
+        walk_type(self, ty)
+    }
+
+    fn fold_literal(&mut self, literal: LiteralValue) -> LiteralValue {
+        literal
+    }
+
+    fn fold_visibility(&mut self, visibility: Visibility) -> Visibility {
+        visibility
+    }
+
+    fn fold_type_parameter(&mut self, type_parameter: TypeParameter) -> TypeParameter {
+        type_parameter
+    }
+}
+
+fn fold_statements<F: Folder + ?Sized>(
+    folder: &mut F,
+    statements: Vec<Statement>,
+) -> Vec<Statement> {
+    statements
+        .into_iter()
+        .map(|statement| folder.fold_statement(statement))
+        .collect()
+}
+
+fn fold_expressions<F: Folder + ?Sized>(
+    folder: &mut F,
+    expressions: Vec<Expression>,
+) -> Vec<Expression> {
+    expressions
+        .into_iter()
+        .map(|expression| folder.fold_expression(expression))
+        .collect()
+}
+
+/// Fold a boxed [`Expression`], reusing its allocation
+#[allow(clippy::boxed_local)]
+fn fold_boxed<F: Folder + ?Sized>(
+    folder: &mut F,
+    mut expression: Box<Expression>,
+) -> Box<Expression> {
+    *expression = folder.fold_expression(*expression);
+    expression
+}
+
+fn fold_attributes<F: Folder + ?Sized>(
+    folder: &mut F,
+    attributes: Vec<Attribute>,
+) -> Vec<Attribute> {
+    attributes
+        .into_iter()
+        .map(|attribute| folder.fold_attribute(attribute))
+        .collect()
+}
+
+fn fold_types<F: Folder + ?Sized>(folder: &mut F, types: Vec<Type>) -> Vec<Type> {
+    types.into_iter().map(|ty| folder.fold_type(ty)).collect()
+}
+
+fn fold_methods<F: Folder + ?Sized>(folder: &mut F, methods: Vec<Method>) -> Vec<Method> {
+    methods
+        .into_iter()
+        .map(|method| folder.fold_method(method))
+        .collect()
+}
+
+fn fold_patterns<F: Folder + ?Sized>(folder: &mut F, patterns: Vec<Pattern>) -> Vec<Pattern> {
+    patterns
+        .into_iter()
+        .map(|pattern| folder.fold_pattern(pattern))
+        .collect()
+}
+
+pub fn walk_program<F: Folder + ?Sized>(folder: &mut F, program: Program) -> Program {
+    Program::new(fold_statements(folder, program.statements))
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn walk_statement<F: Folder + ?Sized>(folder: &mut F, statement: Statement) -> Statement {
+    match statement {
+        Statement::LetDeclaration {
+            attributes,
+            mutable,
+            pattern,
+            type_annotation,
+            initializer,
+            span,
+        } => Statement::LetDeclaration {
+            attributes: fold_attributes(folder, attributes),
+            mutable,
+            pattern: folder.fold_pattern(pattern),
+            type_annotation: type_annotation.map(|ty| folder.fold_type(ty)),
+            initializer: initializer.map(|initializer| folder.fold_expression(initializer)),
+            span,
+        },
+        Statement::ConstDeclaration {
+            visibility,
+            name,
+            type_annotation,
+            value,
+            span,
+        } => Statement::ConstDeclaration {
+            visibility: folder.fold_visibility(visibility),
+            name,
+            type_annotation: folder.fold_type(type_annotation),
+            value: folder.fold_expression(value),
+            span,
+        },
+        Statement::FunctionDeclaration {
+            attributes,
+            visibility,
+            name,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+        } => Statement::FunctionDeclaration {
+            attributes: fold_attributes(folder, attributes),
+            visibility: folder.fold_visibility(visibility),
+            name,
+            type_parameters: type_parameters
+                .into_iter()
+                .map(|type_parameter| folder.fold_type_parameter(type_parameter))
+                .collect(),
+            parameters: parameters
+                .into_iter()
+                .map(|parameter| folder.fold_parameter(parameter))
+                .collect(),
+            return_type: return_type.map(|ty| folder.fold_type(ty)),
+            body: folder.fold_block(body),
+        },
+        Statement::StructDeclaration {
+            visibility,
+            name,
+            fields,
+        } => Statement::StructDeclaration {
+            visibility: folder.fold_visibility(visibility),
+            name,
+            fields: fields
+                .into_iter()
+                .map(|field| folder.fold_struct_field(field))
+                .collect(),
+        },
+        Statement::EnumDeclaration {
+            visibility,
+            name,
+            variants,
+        } => Statement::EnumDeclaration {
+            visibility: folder.fold_visibility(visibility),
+            name,
+            variants: variants
+                .into_iter()
+                .map(|variant| folder.fold_enum_variant(variant))
+                .collect(),
+        },
+        Statement::TraitDeclaration {
+            visibility,
+            name,
+            methods,
+            span,
+        } => Statement::TraitDeclaration {
+            visibility: folder.fold_visibility(visibility),
+            name,
+            methods: fold_methods(folder, methods),
+            span,
+        },
+        Statement::ImplDeclaration {
+            trait_name,
+            target,
+            methods,
+            span,
+        } => Statement::ImplDeclaration {
+            trait_name,
+            target,
+            methods: fold_methods(folder, methods),
+            span,
+        },
+        Statement::Return(value) => {
+            Statement::Return(value.map(|value| folder.fold_expression(value)))
+        }
+        Statement::While { condition, body } => Statement::While {
+            condition: folder.fold_expression(condition),
+            body: fold_statements(folder, body),
+        },
+        Statement::For {
+            initializer,
+            condition,
+            increment,
+            body,
+        } => Statement::For {
+            initializer: initializer
+                .map(|initializer| Box::new(folder.fold_statement(*initializer))),
+            condition: condition.map(|condition| folder.fold_expression(condition)),
+            increment: increment.map(|increment| folder.fold_expression(increment)),
+            body: fold_statements(folder, body),
+        },
+        Statement::Block(body) => Statement::Block(fold_statements(folder, body)),
+        Statement::Expression(expression) => {
+            Statement::Expression(folder.fold_expression(expression))
+        }
+        Statement::Import { .. } | Statement::Break | Statement::Continue | Statement::Error(_) => {
+            statement
+        }
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn walk_expression<F: Folder + ?Sized>(folder: &mut F, expression: Expression) -> Expression {
+    match expression {
+        Expression::Literal(literal) => Expression::Literal(folder.fold_literal(literal)),
+        Expression::BinaryOp {
+            left,
+            operator,
+            right,
+        } => Expression::BinaryOp {
+            left: fold_boxed(folder, left),
+            operator,
+            right: fold_boxed(folder, right),
+        },
+        Expression::UnaryOp { operator, operand } => Expression::UnaryOp {
+            operator,
+            operand: fold_boxed(folder, operand),
+        },
+        Expression::Assignment {
+            target,
+            value,
+            span,
+        } => Expression::Assignment {
+            target: fold_boxed(folder, target),
+            value: fold_boxed(folder, value),
+            span,
+        },
+        Expression::Call { callee, arguments } => Expression::Call {
+            callee: fold_boxed(folder, callee),
+            arguments: fold_expressions(folder, arguments),
+        },
+        Expression::Index { target, index } => Expression::Index {
+            target: fold_boxed(folder, target),
+            index: fold_boxed(folder, index),
+        },
+        Expression::MemberAccess {
+            target,
+            member,
+            span,
+        } => Expression::MemberAccess {
+            target: fold_boxed(folder, target),
+            member,
+            span,
+        },
+        Expression::Grouped(inner) => Expression::Grouped(fold_boxed(folder, inner)),
+        Expression::Array(elements) => Expression::Array(fold_expressions(folder, elements)),
+        Expression::Tuple(elements) => Expression::Tuple(fold_expressions(folder, elements)),
+        Expression::Map(entries) => Expression::Map(
+            entries
+                .into_iter()
+                .map(|entry| folder.fold_map_entry(entry))
+                .collect(),
+        ),
+        Expression::InterpolatedString { parts } => Expression::InterpolatedString {
+            parts: parts
+                .into_iter()
+                .map(|part| folder.fold_string_part(part))
+                .collect(),
+        },
+        Expression::Match {
+            scrutinee,
+            arms,
+            span,
+        } => Expression::Match {
+            scrutinee: fold_boxed(folder, scrutinee),
+            arms: arms
+                .into_iter()
+                .map(|arm| folder.fold_match_arm(arm))
+                .collect(),
+            span,
+        },
+        Expression::StructLiteral { name, fields, base } => Expression::StructLiteral {
+            name,
+            fields: fields
+                .into_iter()
+                .map(|field| folder.fold_field_init(field))
+                .collect(),
+            base: base.map(|base| fold_boxed(folder, base)),
+        },
+        Expression::Lambda {
+            parameters,
+            return_type,
+            body,
+        } => Expression::Lambda {
+            parameters: parameters
+                .into_iter()
+                .map(|parameter| folder.fold_parameter(parameter))
+                .collect(),
+            return_type: return_type.map(|ty| folder.fold_type(ty)),
+            body: folder.fold_lambda_body(body),
+        },
+        Expression::Block(block) => Expression::Block(folder.fold_block(block)),
+        Expression::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => Expression::If {
+            condition: fold_boxed(folder, condition),
+            then_branch: folder.fold_block(then_branch),
+            else_branch: else_branch.map(|else_branch| fold_boxed(folder, else_branch)),
+        },
+        Expression::Generic {
+            name,
+            arguments,
+            span,
+        } => Expression::Generic {
+            name,
+            arguments: fold_types(folder, arguments),
+            span,
+        },
+        Expression::Identifier { .. } | Expression::Error(_) => expression,
+    }
+}
+
+pub fn walk_block<F: Folder + ?Sized>(folder: &mut F, block: Block) -> Block {
+    Block {
+        statements: fold_statements(folder, block.statements),
+        tail: block.tail.map(|tail| fold_boxed(folder, tail)),
+    }
+}
+
+pub fn walk_lambda_body<F: Folder + ?Sized>(folder: &mut F, body: LambdaBody) -> LambdaBody {
+    match body {
+        LambdaBody::Expression(expression) => {
+            LambdaBody::Expression(fold_boxed(folder, expression))
+        }
+        LambdaBody::Block(block) => LambdaBody::Block(folder.fold_block(block)),
+    }
+}
+
+pub fn walk_string_part<F: Folder + ?Sized>(folder: &mut F, part: StringPart) -> StringPart {
+    match part {
+        StringPart::Text(_) => part,
+        StringPart::Expression(expression) => {
+            StringPart::Expression(fold_boxed(folder, expression))
+        }
+    }
+}
+
+pub fn walk_match_arm<F: Folder + ?Sized>(folder: &mut F, arm: MatchArm) -> MatchArm {
+    MatchArm {
+        pattern: folder.fold_pattern(arm.pattern),
+        guard: arm.guard.map(|guard| folder.fold_expression(guard)),
+        body: folder.fold_expression(arm.body),
+        span: arm.span,
+    }
+}
+
+pub fn walk_map_entry<F: Folder + ?Sized>(folder: &mut F, entry: MapEntry) -> MapEntry {
+    MapEntry {
+        key: folder.fold_expression(entry.key),
+        value: folder.fold_expression(entry.value),
+    }
+}
+
+pub fn walk_field_init<F: Folder + ?Sized>(folder: &mut F, field: FieldInit) -> FieldInit {
+    FieldInit {
+        name: field.name,
+        value: folder.fold_expression(field.value),
+    }
+}
+
+pub fn walk_pattern<F: Folder + ?Sized>(folder: &mut F, pattern: Pattern) -> Pattern {
+    match pattern {
+        Pattern::Tuple(elements) => Pattern::Tuple(fold_patterns(folder, elements)),
+        Pattern::Array(elements) => Pattern::Array(fold_patterns(folder, elements)),
+        Pattern::TupleStruct { name, elements } => Pattern::TupleStruct {
+            name,
+            elements: fold_patterns(folder, elements),
+        },
+        Pattern::Literal(literal) => Pattern::Literal(folder.fold_literal(literal)),
+        Pattern::Struct { name, fields, rest } => Pattern::Struct {
+            name,
+            fields: fields
+                .into_iter()
+                .map(|field| folder.fold_field_pattern(field))
+                .collect(),
+            rest,
+        },
+        Pattern::Wildcard | Pattern::Binding { .. } | Pattern::Path(_) | Pattern::Rest { .. } => {
+            pattern
+        }
+    }
+}
+
+pub fn walk_field_pattern<F: Folder + ?Sized>(folder: &mut F, field: FieldPattern) -> FieldPattern {
+    FieldPattern {
+        name: field.name,
+        pattern: folder.fold_pattern(field.pattern),
+    }
+}
+
+pub fn walk_attribute<F: Folder + ?Sized>(folder: &mut F, attribute: Attribute) -> Attribute {
+    Attribute {
+        arguments: attribute
+            .arguments
+            .into_iter()
+            .map(|argument| folder.fold_literal(argument))
+            .collect(),
+        ..attribute
+    }
+}
+
+pub fn walk_parameter<F: Folder + ?Sized>(folder: &mut F, parameter: Parameter) -> Parameter {
+    Parameter {
+        pattern: folder.fold_pattern(parameter.pattern),
+        type_annotation: parameter.type_annotation.map(|ty| folder.fold_type(ty)),
+        ..parameter
+    }
+}
+
+pub fn walk_method<F: Folder + ?Sized>(folder: &mut F, method: Method) -> Method {
+    Method {
+        parameters: method
+            .parameters
+            .into_iter()
+            .map(|parameter| folder.fold_parameter(parameter))
+            .collect(),
+        return_type: method.return_type.map(|ty| folder.fold_type(ty)),
+        body: method.body.map(|body| folder.fold_block(body)),
+        ..method
+    }
+}
+
+pub fn walk_struct_field<F: Folder + ?Sized>(folder: &mut F, field: StructField) -> StructField {
+    StructField {
+        name: field.name,
+        type_annotation: folder.fold_type(field.type_annotation),
+    }
+}
+
+pub fn walk_enum_variant<F: Folder + ?Sized>(folder: &mut F, variant: EnumVariant) -> EnumVariant {
+    EnumVariant {
+        name: variant.name,
+        payload: folder.fold_variant_payload(variant.payload),
+    }
+}
+
+pub fn walk_variant_payload<F: Folder + ?Sized>(
+    folder: &mut F,
+    payload: VariantPayload,
+) -> VariantPayload {
+    match payload {
+        VariantPayload::Unit => VariantPayload::Unit,
+        VariantPayload::Tuple(types) => VariantPayload::Tuple(fold_types(folder, types)),
+        VariantPayload::Struct(fields) => VariantPayload::Struct(
+            fields
+                .into_iter()
+                .map(|field| folder.fold_struct_field(field))
+                .collect(),
+        ),
+    }
+}
+
+pub fn walk_type<F: Folder + ?Sized>(folder: &mut F, ty: Type) -> Type {
+    match ty {
+        Type::Function {
+            parameters,
+            return_type,
+        } => Type::Function {
+            parameters: fold_types(folder, parameters),
+            return_type: Box::new(folder.fold_type(*return_type)),
+        },
+        Type::Array(element) => Type::Array(Box::new(folder.fold_type(*element))),
+        Type::Tuple(elements) => Type::Tuple(fold_types(folder, elements)),
+        Type::Map { key, value } => Type::Map {
+            key: Box::new(folder.fold_type(*key)),
+            value: Box::new(folder.fold_type(*value)),
+        },
+        Type::Optional(element) => Type::Optional(Box::new(folder.fold_type(*element))),
+        Type::Generic { name, arguments } => Type::Generic {
+            name,
+            arguments: fold_types(folder, arguments),
+        },
+        Type::Named(_) | Type::Unit => ty,
+    }
+}
@@ -1,3 +1,15 @@
+#![forbid(unsafe_code)]
+
+pub mod cst;
 pub mod errors;
+pub mod fold;
+pub mod limits;
 pub mod nodes;
 pub mod parser;
+pub mod precedence;
+pub mod print;
+pub mod source;
+pub mod visit;
+pub mod visit_mut;
+
+pub use source::{Diagnostic, Parsed, parse_expression, parse_program, parse_type};
@@ -0,0 +1,28 @@
+/// Upper bounds on the input the parser accepts, so pathological input is reported as an
+/// error instead of exhausting the stack or memory
+///
+/// Use [`CarbideParser::with_limits`][`crate::parser::CarbideParser::with_limits`] to parse
+/// with limits other than the [defaults][`ParserLimits::default`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserLimits {
+    /// How deeply expressions, types, patterns and blocks can nest, where each operator in
+    /// a chain like `a + b + c` counts as a level
+    pub max_depth: usize,
+    /// How many parameters a function or lambda can have
+    pub max_parameters: usize,
+    /// How many arguments a call can have
+    pub max_arguments: usize,
+    /// How many tokens the input can have
+    pub max_tokens: usize,
+}
+
+impl Default for ParserLimits {
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_parameters: 255,
+            max_arguments: 255,
+            max_tokens: 1_000_000,
+        }
+    }
+}
@@ -1,4 +1,5 @@
 use carbide_lexer::operators::{BinaryOperators, UnaryOperators};
+use carbide_lexer::tokens::Span;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Type {
@@ -11,6 +12,14 @@ pub enum Type {
     },
     /// Array type: [`element_type`]
     Array(Box<Type>),
+    /// Tuple type: (`type`, `type`, ...)
+    Tuple(Vec<Type>),
+    /// Map type: {`key_type`: `value_type`}
+    Map { key: Box<Type>, value: Box<Type> },
+    /// Optional type: `type`?
+    Optional(Box<Type>),
+    /// Generic type application: `name`<`type`, ...>
+    Generic { name: String, arguments: Vec<Type> },
     /// Unit type
     Unit,
 }
@@ -35,8 +44,19 @@ pub enum Expression {
     /// Literal value
     Literal(LiteralValue),
 
-    /// Identifier reference
-    Identifier(String),
+    /// Identifier reference, possibly qualified like `Shape::Circle`
+    Identifier {
+        name: String,
+        /// Span of the whole, possibly qualified, name
+        span: Span,
+    },
+
+    /// Generic function with explicit type arguments: `name`::<`type`, ...>
+    Generic {
+        name: String,
+        arguments: Vec<Type>,
+        span: Span,
+    },
 
     /// Binary operation: left op right
     BinaryOp {
@@ -55,6 +75,7 @@ pub enum Expression {
     Assignment {
         target: Box<Expression>,
         value: Box<Expression>,
+        span: Span,
     },
 
     /// Function call: callee(args)
@@ -69,10 +90,13 @@ pub enum Expression {
         index: Box<Expression>,
     },
 
-    /// Member access: target.member
+    /// Member access: target.member, which calls a method when it's the callee of a
+    /// [`Call`][`Expression::Call`], like `c.area()`
     MemberAccess {
         target: Box<Expression>,
         member: String,
+        /// Span of the member name
+        span: Span,
     },
 
     /// Grouped expression: (expr)
@@ -81,8 +105,95 @@ pub enum Expression {
     /// Array literal: [expr, expr, ...]
     Array(Vec<Expression>),
 
+    /// Tuple literal: (expr, expr, ...)
+    Tuple(Vec<Expression>),
+
+    /// Map literal: { key: value, ... }
+    Map(Vec<MapEntry>),
+
     /// Interpolated string with expressions
     InterpolatedString { parts: Vec<StringPart> },
+
+    /// Match: match scrutinee { pattern => expr, ... }
+    Match {
+        scrutinee: Box<Expression>,
+        arms: Vec<MatchArm>,
+        /// Span of `match scrutinee`
+        span: Span,
+    },
+
+    /// Struct literal: Name { field: value, ..base }
+    StructLiteral {
+        name: String,
+        fields: Vec<FieldInit>,
+        base: Option<Box<Expression>>,
+    },
+
+    /// Lambda: |params| -> `return_type` => body
+    Lambda {
+        parameters: Vec<Parameter>,
+        return_type: Option<Type>,
+        body: LambdaBody,
+    },
+
+    /// Block expression: { statements; tail }
+    Block(Block),
+
+    /// If expression: if condition { ... } else { ... }
+    If {
+        condition: Box<Expression>,
+        then_branch: Block,
+        /// Either a [`Block`][`Expression::Block`] or another [`If`][`Expression::If`]
+        /// for `else if`
+        else_branch: Option<Box<Expression>>,
+    },
+
+    /// Placeholder for an expression that failed to parse, covering the skipped tokens
+    Error(Span),
+}
+
+impl Expression {
+    #[must_use]
+    pub fn identifier(name: impl Into<String>, span: Span) -> Self {
+        Self::Identifier {
+            name: name.into(),
+            span,
+        }
+    }
+
+    /// Check if this expression ends in a `}`, so it doesn't need a `;` to be used as
+    /// a statement
+    #[must_use]
+    pub fn is_block_like(&self) -> bool {
+        matches!(self, Self::Match { .. } | Self::Block(_) | Self::If { .. })
+    }
+
+    /// Check if this expression is a place that can be assigned to, like `x`, `xs[0]`,
+    /// `p.x` or a parenthesized place like `(x)`
+    #[must_use]
+    pub fn is_place(&self) -> bool {
+        match self {
+            Self::Identifier { .. } | Self::Index { .. } | Self::MemberAccess { .. } => true,
+            Self::Grouped(inner) => inner.is_place(),
+            _ => false,
+        }
+    }
+}
+
+/// A `{ ... }` block whose value is its tail expression
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Block {
+    pub statements: Vec<Statement>,
+    /// Trailing expression without a `;`, or `None` if the block has no value
+    pub tail: Option<Box<Expression>>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum LambdaBody {
+    /// A single expression, like `|x| => x * 2`
+    Expression(Box<Expression>),
+    /// A block, like `|x| => { x * 2 }`, whose tail expression is returned
+    Block(Block),
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -92,38 +203,294 @@ pub enum StringPart {
 }
 
 #[derive(Debug, Clone, PartialEq)]
-pub struct Parameter {
+pub struct MatchArm {
+    pub pattern: Pattern,
+    pub guard: Option<Expression>,
+    pub body: Expression,
+    /// Span of the arm's pattern
+    pub span: Span,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Pattern {
+    /// Wildcard: `_`
+    Wildcard,
+
+    /// Literal value, like `1` or `"text"`
+    Literal(LiteralValue),
+
+    /// Binding that matches anything: `name`
+    Binding {
+        name: String,
+        /// Span of the name
+        span: Span,
+    },
+
+    /// Tuple destructuring: (pattern, pattern, ...)
+    Tuple(Vec<Pattern>),
+
+    /// Array destructuring: [pattern, ..rest, pattern]
+    Array(Vec<Pattern>),
+
+    /// The rest of an array: `..` or `..name`, only allowed once in an array pattern
+    Rest {
+        name: Option<String>,
+        /// Span of the name, or of the `..` without one
+        span: Span,
+    },
+
+    /// Enum variant without a payload: `Shape::Empty`
+    Path(String),
+
+    /// Enum variant with a tuple payload: `Shape::Circle`(pattern, ...)
+    TupleStruct {
+        name: String,
+        elements: Vec<Pattern>,
+    },
+
+    /// Struct or enum variant with named fields: Name { field: pattern, .. }
+    Struct {
+        name: String,
+        fields: Vec<FieldPattern>,
+        /// Whether the remaining fields are ignored with `..`
+        rest: bool,
+    },
+}
+
+impl Pattern {
+    #[must_use]
+    pub fn binding(name: impl Into<String>, span: Span) -> Self {
+        Self::Binding {
+            name: name.into(),
+            span,
+        }
+    }
+
+    /// Get the names bound by this pattern, in order
+    #[must_use]
+    pub fn bindings(&self) -> Vec<&str> {
+        self.binding_spans()
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect()
+    }
+
+    /// Get the names bound by this pattern together with the span of each name, in order
+    #[must_use]
+    pub fn binding_spans(&self) -> Vec<(&str, &Span)> {
+        let mut names = Vec::new();
+        self.collect_bindings(&mut names);
+        names
+    }
+
+    fn collect_bindings<'a>(&'a self, names: &mut Vec<(&'a str, &'a Span)>) {
+        match self {
+            Self::Binding { name, span }
+            | Self::Rest {
+                name: Some(name),
+                span,
+            } => names.push((name, span)),
+            Self::Tuple(elements) | Self::Array(elements) | Self::TupleStruct { elements, .. } => {
+                for element in elements {
+                    element.collect_bindings(names);
+                }
+            }
+            Self::Struct { fields, .. } => {
+                for field in fields {
+                    field.pattern.collect_bindings(names);
+                }
+            }
+            Self::Wildcard | Self::Literal(_) | Self::Path(_) | Self::Rest { name: None, .. } => {}
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldPattern {
+    pub name: String,
+    pub pattern: Pattern,
+}
+
+/// A `key: value` entry in a map literal
+#[derive(Debug, Clone, PartialEq)]
+pub struct MapEntry {
+    pub key: Expression,
+    pub value: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldInit {
+    pub name: String,
+    pub value: Expression,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct StructField {
+    pub name: String,
+    pub type_annotation: Type,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnumVariant {
     pub name: String,
+    pub payload: VariantPayload,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantPayload {
+    /// No payload, like `Empty`
+    Unit,
+    /// Positional payload, like `Circle(float)`
+    Tuple(Vec<Type>),
+    /// Named fields, like `Rect { w: float, h: float }`
+    Struct(Vec<StructField>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub mutable: bool,
+    pub pattern: Pattern,
     pub type_annotation: Option<Type>,
+    /// Span of the pattern
+    pub span: Span,
+}
+
+/// A type parameter of a generic function, like `T: Ord + Show` in `fn max<T: Ord + Show>`
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeParameter {
+    pub name: String,
+    /// Names of the bounds after the `:`
+    pub bounds: Vec<String>,
+    /// Span of the name
+    pub span: Span,
+}
+
+/// A method in a trait or impl block, like `fn area(self) -> float { ... }`
+///
+/// Methods that take `self` first are called on a value, like `c.area()`, and the others on
+/// the type, like `Circle::new(1.0)`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Method {
+    pub name: String,
+    pub parameters: Vec<Parameter>,
+    pub return_type: Option<Type>,
+    /// The body, whose tail expression is returned. Only trait methods can leave it out,
+    /// like `fn area(self) -> float;`, and impls of the trait then have to provide it
+    pub body: Option<Block>,
+    /// Span of the name
+    pub span: Span,
+}
+
+impl Method {
+    /// Check if the first parameter is `self`, so the method is called on a value
+    #[must_use]
+    pub fn takes_self(&self) -> bool {
+        self.parameters.first().is_some_and(
+            |parameter| matches!(&parameter.pattern, Pattern::Binding { name, .. } if name == "self"),
+        )
+    }
+}
+
+/// An attribute on a declaration, like `#[inline]` or `#[deprecated("use g")]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute {
+    pub name: String,
+    /// Literal arguments, like `"use g"` in `#[deprecated("use g")]`
+    pub arguments: Vec<LiteralValue>,
+    /// Span of the whole attribute, from `#` to `]`
+    pub span: Span,
+}
+
+/// Whether an item can be imported from other modules
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum Visibility {
+    #[default]
+    Private,
+    /// Marked with `pub`
+    Public,
 }
 
 #[derive(Debug, Clone, PartialEq)]
 pub enum Statement {
-    /// Variable declaration, like `let name: type = value;`
+    /// Variable declaration, like `let name: type = value;`, `let mut name = value;` or
+    /// `let (a, b) = value;`
     LetDeclaration {
-        name: String,
+        attributes: Vec<Attribute>,
+        mutable: bool,
+        pattern: Pattern,
         type_annotation: Option<Type>,
         initializer: Option<Expression>,
+        /// Span of the pattern
+        span: Span,
+    },
+
+    /// Constant declaration, like `const NAME: type = value;`
+    ConstDeclaration {
+        visibility: Visibility,
+        name: String,
+        type_annotation: Type,
+        value: Expression,
+        /// Span of the name
+        span: Span,
     },
 
-    /// Function declaration
+    /// Function declaration, where the tail expression of the body is returned
     FunctionDeclaration {
+        attributes: Vec<Attribute>,
+        visibility: Visibility,
         name: String,
+        type_parameters: Vec<TypeParameter>,
         parameters: Vec<Parameter>,
         return_type: Option<Type>,
-        body: Vec<Statement>,
+        body: Block,
     },
 
-    /// Return statement, like `return expr;`
-    Return(Option<Expression>),
+    /// Struct declaration, like `struct Point { x: float, y: float }`
+    StructDeclaration {
+        visibility: Visibility,
+        name: String,
+        fields: Vec<StructField>,
+    },
 
-    /// If statement
-    If {
-        condition: Expression,
-        then_branch: Vec<Statement>,
-        else_branch: Option<Vec<Statement>>,
+    /// Enum declaration, like `enum Shape { Circle(float), Empty }`
+    EnumDeclaration {
+        visibility: Visibility,
+        name: String,
+        variants: Vec<EnumVariant>,
+    },
+
+    /// Trait declaration, like `trait Shape { fn area(self) -> float; }`
+    TraitDeclaration {
+        visibility: Visibility,
+        name: String,
+        methods: Vec<Method>,
+        /// Span of the name
+        span: Span,
     },
 
+    /// Impl block, like `impl Shape for Circle { ... }`, or `impl Circle { ... }` for
+    /// methods that aren't part of a trait
+    ImplDeclaration {
+        trait_name: Option<String>,
+        /// Name of the type the methods belong to
+        target: String,
+        methods: Vec<Method>,
+        /// Span from `impl` to the end of the target
+        span: Span,
+    },
+
+    /// Import declaration, like `import util.strings;` or `import util.strings.{trim, split};`
+    Import {
+        /// Module path segments, like `["util", "strings"]`
+        path: Vec<String>,
+        /// Items imported with `.{ ... }`, or `None` to import the module itself
+        items: Option<Vec<String>>,
+        span: Span,
+    },
+
+    /// Return statement, like `return expr;`
+    Return(Option<Expression>),
+
     /// While loop
     While {
         condition: Expression,
@@ -149,6 +516,9 @@ pub enum Statement {
 
     /// `continue` statement
     Continue,
+
+    /// Placeholder for a statement that failed to parse, covering the skipped tokens
+    Error(Span),
 }
 
 #[derive(Debug, Clone, PartialEq)]
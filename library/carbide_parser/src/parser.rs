@@ -1,13 +1,76 @@
 use carbide_lexer::keywords::Keywords;
 use carbide_lexer::operators::BinaryOperators;
-use carbide_lexer::tokens::{SourceLocation, Token, Tokens};
-
-use crate::errors::CarbideParserError;
-use crate::nodes::{Expression, LiteralValue, Parameter, Statement, StringPart, Type};
+use carbide_lexer::tokens::{SourceLocation, Span, Token, Tokens};
+
+use crate::cst::builder::CstBuilder;
+use crate::cst::green::{Checkpoint, GreenNode};
+use crate::cst::lower::lower;
+use crate::cst::{SyntaxKind, SyntaxNode};
+use crate::errors::{CarbideParserError, Expected, ExpectedSet, TokenSummary};
+use crate::limits::ParserLimits;
+use crate::nodes::{
+    Attribute, Block, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody, LiteralValue,
+    MapEntry, MatchArm, Method, Parameter, Pattern, Statement, StringPart, StructField, Type,
+    TypeParameter, VariantPayload, Visibility,
+};
+use crate::precedence::{Fixity, Operator, Precedence, UnaryFixity};
 
 pub struct CarbideParser<'a> {
     tokens: Vec<Token<'a>>,
     pos: usize,
+    /// Number of loops enclosing the current position
+    loop_depth: usize,
+    /// Number of functions and lambdas enclosing the current position
+    function_depth: usize,
+    /// Number of blocks enclosing the current position
+    block_depth: usize,
+    /// Number of nested expressions, types, patterns and blocks enclosing the current
+    /// position, checked against [`ParserLimits::max_depth`]
+    depth: usize,
+    /// Deepest nesting reached since the current expression or type started, which grows
+    /// as operators wrap it without recursing
+    deepest: usize,
+    /// Bounds on the input, reported as errors when it goes past them
+    limits: ParserLimits,
+    /// Whether `Name {` starts a struct literal and `{` starts a map literal, which is
+    /// not the case in conditions like `while ready { ... }`
+    struct_literals_allowed: bool,
+    /// Errors that don't stop the current statement from being parsed
+    errors: Vec<CarbideParserError>,
+    /// Everything tried at the current token without matching it, reported if parsing
+    /// fails there
+    expected: ExpectedSet,
+    /// Builds the CST alongside the AST, when parsing with [`CarbideParser::parse_cst`]
+    cst: Option<CstBuilder<'a>>,
+}
+
+/// The items that can follow `pub`
+const PUB_ITEMS: [Expected; 5] = [
+    Expected::Token("fn"),
+    Expected::Token("struct"),
+    Expected::Token("enum"),
+    Expected::Token("const"),
+    Expected::Token("trait"),
+];
+
+/// An item inside a `{ ... }` block
+enum BlockItem {
+    Statement(Statement),
+    /// Trailing expression without a `;`
+    Tail(Expression),
+    /// The closing `}`, or the end of the input
+    End,
+}
+
+/// Where a statement starts, for recovering from an error inside it
+#[derive(Debug, Clone, Copy)]
+struct Marker {
+    /// Index of the first token
+    pos: usize,
+    /// Offset of the first token, for building [`Spans`][`Span`]
+    offset: u64,
+    /// Where the statement's CST node starts
+    checkpoint: Checkpoint,
 }
 
 /// Result type for parsing
@@ -31,10 +94,73 @@ impl ParseResult {
     }
 }
 
+/// Result type for parsing a single node, like with
+/// [`CarbideParser::parse_single_expression`]
+#[derive(Debug, Clone)]
+pub struct NodeResult<T> {
+    /// The node, or `None` if it failed to parse
+    pub node: Option<T>,
+    pub errors: Vec<Box<CarbideParserError>>,
+}
+
+impl<T> NodeResult<T> {
+    /// Check if parsing succeeded without errors
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
+/// Result type for parsing into a lossless CST
+#[derive(Debug, Clone)]
+pub struct CstParseResult {
+    pub green: GreenNode,
+    pub errors: Vec<Box<CarbideParserError>>,
+}
+
+impl CstParseResult {
+    /// Get the root [`SyntaxNode`] of the tree
+    #[must_use]
+    pub fn syntax(&self) -> SyntaxNode {
+        SyntaxNode::new_root(self.green.clone())
+    }
+
+    /// Lower the tree to the AST that [`CarbideParser::parse`] produces
+    #[must_use]
+    pub fn lower(&self) -> Vec<Statement> {
+        lower(&self.syntax())
+    }
+
+    /// Check if parsing succeeded without errors
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.errors.is_empty()
+    }
+}
+
 impl<'a> CarbideParser<'a> {
     #[must_use]
     pub fn new(tokens: Vec<Token<'a>>) -> Self {
-        Self { tokens, pos: 0 }
+        Self::with_limits(tokens, ParserLimits::default())
+    }
+
+    /// Create a [`CarbideParser`] that reports input going past `limits` as errors
+    #[must_use]
+    pub fn with_limits(tokens: Vec<Token<'a>>, limits: ParserLimits) -> Self {
+        Self {
+            tokens,
+            pos: 0,
+            loop_depth: 0,
+            function_depth: 0,
+            block_depth: 0,
+            depth: 0,
+            deepest: 0,
+            limits,
+            struct_literals_allowed: true,
+            errors: Vec::new(),
+            expected: ExpectedSet::new(),
+            cst: None,
+        }
     }
 
     /// Check if we're at the end of the input
@@ -49,14 +175,14 @@ impl<'a> CarbideParser<'a> {
         self.tokens.get(self.pos)
     }
 
+    /// Get the last consumed [`Token`]
     #[inline]
     fn last(&self) -> Option<&Token<'a>> {
-        self.tokens.get(self.pos - 1)
+        self.pos.checked_sub(1).and_then(|i| self.tokens.get(i))
     }
 
     /// Peek ahead by `n` tokens
     #[inline]
-    #[allow(dead_code)]
     fn peek_ahead(&self, n: usize) -> Option<&Token<'a>> {
         self.tokens.get(self.pos + n)
     }
@@ -64,19 +190,73 @@ impl<'a> CarbideParser<'a> {
     /// Consume and return the current token
     #[inline]
     fn advance(&mut self) -> Option<&Token<'a>> {
-        if self.pos < self.tokens.len() {
-            let token = &self.tokens[self.pos];
-            self.pos += 1;
-            Some(token)
-        } else {
-            None
+        let token = self.tokens.get(self.pos)?;
+        self.expected.clear();
+        if let Some(cst) = &mut self.cst {
+            cst.token(token);
+        }
+
+        self.pos += 1;
+        Some(token)
+    }
+
+    /// Get the offset where the current token starts, for building [`Spans`][`Span`]
+    fn current_offset(&self) -> u64 {
+        self.peek()
+            .map(|t| t.span.start)
+            .or_else(|| self.tokens.last().map(|t| t.span.end))
+            .unwrap_or_default()
+    }
+
+    /// Get the [`Span`] from `start` to the end of the last consumed token
+    fn span_from(&self, start: u64) -> Span {
+        let end = self
+            .tokens
+            .get(self.pos.saturating_sub(1))
+            .map_or(start, |t| t.span.end);
+        start..end.max(start)
+    }
+
+    /// Get a [`Checkpoint`] for a CST node starting at the current token
+    fn checkpoint(&mut self) -> Checkpoint {
+        let next = self.current_offset();
+        self.cst
+            .as_mut()
+            .map_or_else(Checkpoint::default, |cst| cst.checkpoint(next))
+    }
+
+    /// Wrap the CST built since `checkpoint` in a node of `kind`
+    fn finish_node(&mut self, checkpoint: Checkpoint, kind: SyntaxKind) {
+        if let Some(cst) = &mut self.cst {
+            cst.wrap(checkpoint, kind);
+        }
+    }
+
+    /// Get a [`Marker`] for a statement starting at the current token
+    fn marker(&mut self) -> Marker {
+        Marker {
+            pos: self.pos,
+            offset: self.current_offset(),
+            checkpoint: self.checkpoint(),
         }
     }
 
     /// Get current source location for error reporting
     fn current_location(&self) -> SourceLocation {
+        self.peek().or_else(|| self.last()).map_or(
+            SourceLocation {
+                line: 1,
+                column: 1,
+                offset: 0,
+            },
+            |t| t.end,
+        )
+    }
+
+    /// Get the location where the current token starts, or the end of the input
+    fn current_start(&self) -> SourceLocation {
         self.peek()
-            .map_or(unsafe { self.last().unwrap_unchecked().end }, |i| i.end)
+            .map_or_else(|| self.current_location(), |t| t.start)
     }
 
     /// Check if current token matches a specific token type pattern
@@ -98,6 +278,16 @@ impl<'a> CarbideParser<'a> {
         }
     }
 
+    /// Consume the current token if it matches `pattern`, otherwise remember that `token`
+    /// could have been there
+    fn eat(&mut self, pattern: impl Fn(&Tokens) -> bool, token: &'static str) -> bool {
+        let matched = self.match_token(pattern);
+        if !matched {
+            self.expected.insert(Expected::Token(token));
+        }
+        matched
+    }
+
     /// Expect a specific [`Token`] and consume it
     ///
     /// # Errors
@@ -105,41 +295,232 @@ impl<'a> CarbideParser<'a> {
     fn expect(
         &mut self,
         pattern: impl Fn(&Tokens) -> bool,
-        expected: &str,
+        token: &'static str,
     ) -> Result<&Token<'a>, Box<CarbideParserError>> {
-        if let Some(token) = self.peek() {
-            if pattern(&token.token_type) {
-                Ok(unsafe { self.advance().unwrap_unchecked() })
-            } else {
-                Err(Box::new(CarbideParserError::UnexpectedToken {
-                    expected: expected.to_string(),
-                    found: unsafe { std::mem::transmute::<Token<'_>, Token<'_>>(token.clone()) },
-                }))
-            }
+        if !self.check(pattern) {
+            return Err(Box::new(self.unexpected([Expected::Token(token)])));
+        }
+
+        self.advance();
+        Ok(&self.tokens[self.pos - 1])
+    }
+
+    /// Run `parse` one nesting level deeper, failing instead if that goes past
+    /// [`ParserLimits::max_depth`]
+    ///
+    /// Every recursive path through the parser goes through here, so deeply nested input
+    /// is reported instead of overflowing the stack
+    ///
+    /// # Errors
+    /// Returns `Err` if the nesting is too deep or `parse` fails
+    fn nested<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Box<CarbideParserError>>,
+    ) -> Result<T, Box<CarbideParserError>> {
+        if self.depth >= self.limits.max_depth {
+            return Err(Box::new(CarbideParserError::NestingTooDeep(
+                self.current_start(),
+            )));
+        }
+
+        self.depth += 1;
+        self.deepest = self.deepest.max(self.depth);
+        let result = parse(self);
+        self.depth -= 1;
+        result
+    }
+
+    /// Run `parse` on an expression or type that can be wrapped by [`Self::wrap`], keeping
+    /// track of how deep it goes separately from the nodes parsed before it
+    ///
+    /// # Errors
+    /// Returns `Err` if `parse` fails
+    fn measured<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Box<CarbideParserError>>,
+    ) -> Result<T, Box<CarbideParserError>> {
+        let enclosing = std::mem::replace(&mut self.deepest, self.depth);
+        let result = parse(self);
+        self.deepest = self.deepest.max(enclosing);
+        result
+    }
+
+    /// Account for the node parsed last being wrapped in another one, like `a` in `a + b`,
+    /// which pushes everything in it one level deeper
+    ///
+    /// Chains like `a + b + c` build deep trees in a loop rather than by recursing, so
+    /// they're checked against [`ParserLimits::max_depth`] here
+    ///
+    /// # Errors
+    /// Returns `Err` if the nesting is too deep
+    fn wrap(&mut self) -> Result<(), Box<CarbideParserError>> {
+        if self.deepest >= self.limits.max_depth {
+            return Err(Box::new(CarbideParserError::NestingTooDeep(
+                self.current_start(),
+            )));
+        }
+
+        self.deepest += 1;
+        Ok(())
+    }
+
+    /// Build the error for having more tokens than [`ParserLimits::max_tokens`], if there
+    /// are
+    fn token_limit_error(&self) -> Option<CarbideParserError> {
+        self.tokens
+            .get(self.limits.max_tokens)
+            .map(|token| CarbideParserError::TooManyTokens(token.start))
+    }
+
+    /// Build the error for finding the current token, or the end of the input, instead of
+    /// `expected` or anything else tried there
+    fn unexpected(&self, expected: impl IntoIterator<Item = Expected>) -> CarbideParserError {
+        let mut set = self.expected.clone();
+        set.extend(expected);
+
+        match self.peek() {
+            Some(token) => CarbideParserError::UnexpectedToken {
+                expected: set,
+                found: TokenSummary::from(token),
+            },
+            None => CarbideParserError::UnexpectedEOF {
+                location: self.current_location(),
+                expected: set,
+            },
+        }
+    }
+
+    /// Expect the `;` that ends a statement, reporting a missing one just past the end of
+    /// the previous token
+    ///
+    /// When the next token is on a later line, the statement is kept as if the `;` was
+    /// there. Otherwise it's unclear where the statement was meant to end, so this fails
+    /// with everything that could have come next, like `;` or an operator
+    ///
+    /// # Errors
+    /// Returns `Err` if the `;` is missing and the next token is on the same line
+    fn expect_semicolon(&mut self) -> Result<(), Box<CarbideParserError>> {
+        if self.eat(|t| matches!(t, Tokens::Semicolon), ";") {
+            return Ok(());
+        }
+
+        let Some(previous) = self.last() else {
+            return Err(Box::new(self.unexpected([])));
+        };
+
+        if self
+            .peek()
+            .is_none_or(|next| next.start.line > previous.end.line)
+        {
+            self.errors
+                .push(CarbideParserError::MissingSemicolon(previous.end));
+            // The statement ends here, so nothing tried for it applies to the next one
+            self.expected.clear();
+            Ok(())
         } else {
-            Err(Box::new(CarbideParserError::UnexpectedEOF(
-                self.current_location(),
-            )))
+            Err(Box::new(self.unexpected([])))
         }
     }
 
-    /// Synchronize parser state after an error by advancing to next statement
-    fn synchronize(&mut self) {
-        self.advance();
+    /// Synchronize parser state after an error by advancing to the next statement
+    ///
+    /// `from` is the index of the failed statement's first token. Brackets it opened before
+    /// the error, and brackets opened while skipping, are skipped as a whole, so a `;` or
+    /// keyword inside them doesn't end the statement. Inside a block, this stops before
+    /// the `}` that closes it
+    fn synchronize(&mut self, from: usize) {
+        let start = self.pos;
+        let mut depth = self.tokens[from..start]
+            .iter()
+            .fold(0usize, |depth, token| match token.token_type {
+                Tokens::LeftBrace | Tokens::LeftParen | Tokens::LeftBracket => depth + 1,
+                Tokens::RightBrace | Tokens::RightParen | Tokens::RightBracket => {
+                    depth.saturating_sub(1)
+                }
+                _ => depth,
+            });
 
-        while !self.is_eof() {
-            if let Some(prev) = self.tokens.get(self.pos.saturating_sub(1))
-                && matches!(prev.token_type, Tokens::Semicolon)
-            {
-                return;
+        while let Some(token) = self.peek() {
+            match &token.token_type {
+                Tokens::LeftBrace | Tokens::LeftParen | Tokens::LeftBracket => depth += 1,
+                Tokens::RightBrace if depth == 0 && self.block_depth > 0 => return,
+                Tokens::RightBrace | Tokens::RightParen | Tokens::RightBracket => {
+                    depth = depth.saturating_sub(1);
+
+                    // A closed `{ ... }` ends items like `fn f() { ... }`, along with the `;`
+                    // after it in `let x = if a { ... };`, unless an `else` continues it
+                    if depth == 0
+                        && matches!(token.token_type, Tokens::RightBrace)
+                        && !self.peek_ahead(1).is_some_and(|t| {
+                            matches!(t.token_type, Tokens::Keyword(Keywords::Else))
+                        })
+                    {
+                        self.advance();
+                        self.match_token(|t| matches!(t, Tokens::Semicolon));
+                        return;
+                    }
+                }
+                // `else if` continues the `if` before it
+                Tokens::Keyword(Keywords::If)
+                    if self.last().is_some_and(|t| {
+                        matches!(t.token_type, Tokens::Keyword(Keywords::Else))
+                    }) => {}
+                Tokens::Semicolon if depth == 0 => {
+                    self.advance();
+                    return;
+                }
+                Tokens::Keyword(kw) if depth == 0 && self.pos > start => match kw {
+                    Keywords::Fn
+                    | Keywords::Let
+                    | Keywords::Return
+                    | Keywords::While
+                    | Keywords::For
+                    | Keywords::Struct
+                    | Keywords::Enum
+                    | Keywords::Match
+                    | Keywords::Import
+                    | Keywords::Pub
+                    | Keywords::Const
+                    | Keywords::Trait
+                    | Keywords::Impl
+                    | Keywords::If => return,
+                    Keywords::Break | Keywords::Continue | Keywords::Else | Keywords::Mut => {}
+                },
+                _ => {}
             }
 
-            if let Some(token) = self.peek()
-                && let Tokens::Keyword(kw) = &token.token_type
-            {
-                match kw {
-                    Keywords::Fn | Keywords::Let | Keywords::Return => return,
+            self.advance();
+        }
+    }
+
+    /// Recover from `error` in the statement starting at `marker`, returning a
+    /// [`Statement::Error`] in its place
+    fn recover_statement(&mut self, marker: Marker, error: CarbideParserError) -> Statement {
+        self.errors.push(error);
+        self.synchronize(marker.pos);
+        self.finish_node(marker.checkpoint, SyntaxKind::Error);
+        Statement::Error(self.span_from(marker.offset))
+    }
+
+    /// Skip the rest of a comma separated item that failed to parse, stopping before the
+    /// `,` or closing bracket that ends it
+    fn skip_list_item(&mut self) {
+        let mut depth = 0usize;
+
+        while let Some(token) = self.peek() {
+            match &token.token_type {
+                Tokens::LeftBrace | Tokens::LeftParen | Tokens::LeftBracket => depth += 1,
+                Tokens::Comma
+                | Tokens::Semicolon
+                | Tokens::RightBrace
+                | Tokens::RightParen
+                | Tokens::RightBracket
+                    if depth == 0 =>
+                {
+                    return;
                 }
+                Tokens::RightBrace | Tokens::RightParen | Tokens::RightBracket => depth -= 1,
+                _ => {}
             }
 
             self.advance();
@@ -147,18 +528,31 @@ impl<'a> CarbideParser<'a> {
     }
 
     /// Parse tokens into an AST with error recovery
+    ///
+    /// Statements that fail to parse are kept as [`Statement::Error`] placeholders
     pub fn parse(&mut self) -> ParseResult {
+        if let Some(error) = self.token_limit_error() {
+            return ParseResult {
+                ast: Vec::new(),
+                errors: vec![Box::new(error)],
+            };
+        }
+
         let mut statements = Vec::new();
         let mut errors = Vec::new();
 
         while !self.is_eof() {
+            let marker = self.marker();
+
             match self.parse_statement() {
                 Ok(stmt) => statements.push(stmt),
                 Err(e) => {
-                    errors.push(e);
-                    self.synchronize();
+                    let statement = self.recover_statement(marker, *e);
+                    statements.push(statement);
                 }
             }
+
+            errors.extend(self.errors.drain(..).map(Box::new));
         }
 
         ParseResult {
@@ -167,6 +561,26 @@ impl<'a> CarbideParser<'a> {
         }
     }
 
+    /// Parse tokens into a lossless CST, with the same error recovery as
+    /// [`CarbideParser::parse`]
+    ///
+    /// `src` is the source the tokens were lexed from, which fills in the whitespace and
+    /// comments between them
+    pub fn parse_cst(&mut self, src: &'a str) -> CstParseResult {
+        self.cst = Some(CstBuilder::new(src));
+        let result = self.parse();
+
+        let green = self.cst.take().map_or_else(
+            || GreenNode::new(SyntaxKind::SourceFile, Vec::new()),
+            CstBuilder::finish,
+        );
+
+        CstParseResult {
+            green,
+            errors: result.errors,
+        }
+    }
+
     /// Strict parsing that fails on first error
     ///
     /// # Errors
@@ -180,6 +594,43 @@ impl<'a> CarbideParser<'a> {
             Ok(result.ast)
         }
     }
+
+    /// Parse tokens holding a single [`Expression`], with nothing after it
+    pub fn parse_single_expression(&mut self) -> NodeResult<Expression> {
+        self.parse_single(Self::parse_expression)
+    }
+
+    /// Parse tokens holding a single [`Type`], with nothing after it
+    pub fn parse_single_type(&mut self) -> NodeResult<Type> {
+        self.parse_single(Self::parse_type)
+    }
+
+    /// Parse a single node with `parse`, and report any tokens left after it
+    fn parse_single<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Box<CarbideParserError>>,
+    ) -> NodeResult<T> {
+        if let Some(error) = self.token_limit_error() {
+            return NodeResult {
+                node: None,
+                errors: vec![Box::new(error)],
+            };
+        }
+
+        let node = parse(self).and_then(|node| {
+            if self.is_eof() {
+                Ok(node)
+            } else {
+                Err(Box::new(
+                    self.unexpected([Expected::Kind("the end of the input")]),
+                ))
+            }
+        });
+
+        let mut errors: Vec<_> = self.errors.drain(..).map(Box::new).collect();
+        let node = node.map_err(|e| errors.push(e)).ok();
+        NodeResult { node, errors }
+    }
 }
 
 impl CarbideParser<'_> {
@@ -188,29 +639,143 @@ impl CarbideParser<'_> {
     /// # Errors
     /// Returns `Err` if parsing the type fails
     fn parse_type(&mut self) -> Result<Type, Box<CarbideParserError>> {
+        self.measured(|parser| {
+            let checkpoint = parser.checkpoint();
+            let mut ty = parser.nested(Self::parse_base_type)?;
+
+            while parser.check(|t| matches!(t, Tokens::Question)) {
+                parser.wrap()?;
+                parser.advance();
+                parser.finish_node(checkpoint, SyntaxKind::OptionalType);
+                ty = Type::Optional(Box::new(ty));
+            }
+
+            Ok(ty)
+        })
+    }
+
+    /// Attempt to parse a type without any `?` suffixes
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the type fails
+    fn parse_base_type(&mut self) -> Result<Type, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+
         if let Some(token) = self.peek() {
             match &token.token_type {
-                Tokens::TypeIdentifier(name) | Tokens::Identifier(name) => {
+                Tokens::TypeIdentifier(name) => {
                     let type_name = (*name).to_string();
                     self.advance();
+                    self.finish_node(checkpoint, SyntaxKind::NamedType);
+                    Ok(Type::Named(type_name))
+                }
+                Tokens::Identifier(name) => {
+                    let type_name = (*name).to_string();
+                    self.advance();
+
+                    if self
+                        .match_token(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Lt)))
+                    {
+                        let arguments = self.parse_type_list(|t| {
+                            matches!(t, Tokens::BinaryOperator(BinaryOperators::Gt))
+                        })?;
+                        self.expect(
+                            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Gt)),
+                            ">",
+                        )?;
+                        self.finish_node(checkpoint, SyntaxKind::GenericType);
+                        return Ok(Type::Generic {
+                            name: type_name,
+                            arguments,
+                        });
+                    }
+
+                    self.finish_node(checkpoint, SyntaxKind::NamedType);
                     Ok(Type::Named(type_name))
                 }
                 Tokens::LeftBracket => {
                     self.advance();
                     let element_type = self.parse_type()?;
                     self.expect(|t| matches!(t, Tokens::RightBracket), "]")?;
+                    self.finish_node(checkpoint, SyntaxKind::ArrayType);
                     Ok(Type::Array(Box::new(element_type)))
                 }
-                _ => Err(Box::new(CarbideParserError::UnexpectedToken {
-                    expected: "type".to_string(),
-                    found: unsafe { std::mem::transmute::<Token<'_>, Token<'_>>(token.clone()) },
-                })),
+                Tokens::LeftParen => self.parse_parenthesized_type(),
+                Tokens::LeftBrace => {
+                    self.advance();
+                    let key = self.parse_type()?;
+                    self.expect(|t| matches!(t, Tokens::Colon), ":")?;
+                    let value = self.parse_type()?;
+                    self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+                    self.finish_node(checkpoint, SyntaxKind::MapType);
+                    Ok(Type::Map {
+                        key: Box::new(key),
+                        value: Box::new(value),
+                    })
+                }
+                _ => Err(Box::new(self.unexpected([Expected::Kind("a type")]))),
             }
         } else {
-            Err(Box::new(CarbideParserError::UnexpectedEOF(
-                self.current_location(),
-            )))
+            Err(Box::new(self.unexpected([Expected::Kind("a type")])))
+        }
+    }
+
+    /// Attempt to parse a type starting with `(`: unit `()`, a tuple `(int, float)`,
+    /// a parenthesized type `(int)`, or a function type `(int, string) -> bool`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the type fails
+    fn parse_parenthesized_type(&mut self) -> Result<Type, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::LeftParen), "(")?;
+
+        let mut elements = self.parse_type_list(|t| matches!(t, Tokens::RightParen))?;
+        // `(int,)` is a one element tuple, while `(int)` is just `int`
+        let trailing_comma = self
+            .last()
+            .is_some_and(|t| matches!(t.token_type, Tokens::Comma));
+
+        self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+
+        if self.eat(|t| matches!(t, Tokens::ThinArrow), "->") {
+            let return_type = self.parse_type()?;
+            self.finish_node(checkpoint, SyntaxKind::FunctionType);
+            return Ok(Type::Function {
+                parameters: elements,
+                return_type: Box::new(return_type),
+            });
+        }
+
+        let (kind, ty) = match elements.len() {
+            0 => (SyntaxKind::UnitType, Type::Unit),
+            1 if !trailing_comma => (SyntaxKind::ParenType, elements.remove(0)),
+            _ => (SyntaxKind::TupleType, Type::Tuple(elements)),
+        };
+        self.finish_node(checkpoint, kind);
+
+        Ok(ty)
+    }
+
+    /// Attempt to parse a comma separated list of types, stopping before `close`
+    ///
+    /// A trailing comma is allowed
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the types fail
+    fn parse_type_list(
+        &mut self,
+        close: impl Fn(&Tokens) -> bool,
+    ) -> Result<Vec<Type>, Box<CarbideParserError>> {
+        let mut types = Vec::new();
+
+        while !self.check(&close) {
+            types.push(self.parse_type()?);
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
+            }
         }
+
+        Ok(types)
     }
 
     /// Attempt to parse a [`Statement`]
@@ -218,93 +783,520 @@ impl CarbideParser<'_> {
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
     fn parse_statement(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+
         if let Some(token) = self.peek() {
             match &token.token_type {
-                Tokens::Keyword(Keywords::Let) => self.parse_let_statement(),
-                Tokens::Keyword(Keywords::Fn) => self.parse_function_declaration(),
+                Tokens::Keyword(Keywords::Let) => self.parse_let_statement(Vec::new(), checkpoint),
+                Tokens::Keyword(Keywords::Const) => {
+                    self.parse_const_declaration(Visibility::Private, checkpoint)
+                }
+                // `fn(...)` starts a lambda, not a declaration
+                Tokens::Keyword(Keywords::Fn)
+                    if !self
+                        .peek_ahead(1)
+                        .is_some_and(|t| matches!(t.token_type, Tokens::LeftParen)) =>
+                {
+                    self.parse_function_declaration(Vec::new(), Visibility::Private, checkpoint)
+                }
                 Tokens::Keyword(Keywords::Return) => self.parse_return(),
+                Tokens::Keyword(Keywords::Struct) => {
+                    self.parse_struct_declaration(Visibility::Private, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Enum) => {
+                    self.parse_enum_declaration(Visibility::Private, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Trait) => {
+                    self.parse_trait_declaration(Visibility::Private, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Impl) => self.parse_impl_declaration(checkpoint),
+                Tokens::Keyword(Keywords::Pub) => self.parse_pub_item(Vec::new(), checkpoint),
+                Tokens::Hash => self.parse_attributed(checkpoint),
+                Tokens::Keyword(Keywords::Import) => self.parse_import(),
+                Tokens::Keyword(Keywords::While) => self.parse_while(),
+                Tokens::Keyword(Keywords::For) => self.parse_for(),
+                Tokens::Keyword(kw @ (Keywords::Break | Keywords::Continue)) => {
+                    let keyword = *kw;
+                    self.parse_loop_control(keyword)
+                }
                 Tokens::LeftBrace => self.parse_block_statement(),
                 _ => self.parse_expression_statement(),
             }
         } else {
-            Err(Box::new(CarbideParserError::UnexpectedEOF(
-                self.current_location(),
-            )))
+            Err(Box::new(self.unexpected([Expected::Kind("a statement")])))
         }
     }
 
-    /// Attempt to parse a `let` [`Statement`]
+    /// Attempt to parse an item marked with `pub`, like `pub fn name() {}`
+    ///
+    /// `checkpoint` is where the item, including its `pub` and `attributes`, starts
     ///
     /// # Errors
-    /// Returns `Err` if parsing the tokens fail
-    fn parse_let_statement(&mut self) -> Result<Statement, Box<CarbideParserError>> {
-        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Let)), "let")?;
-
-        let name_token = self.expect(|t| matches!(t, Tokens::Identifier(_)), "identifier")?;
-
-        let name = if let Tokens::Identifier(n) = &name_token.token_type {
-            (*n).to_string()
-        } else {
-            return Err(Box::new(CarbideParserError::ExpectedIdentifier(unsafe {
-                std::mem::transmute::<Token<'_>, Token<'_>>(name_token.clone())
-            })));
-        };
-
-        // Parse optional type annotation
-        let type_annotation = if self.match_token(|t| matches!(t, Tokens::Colon)) {
-            Some(self.parse_type()?)
-        } else {
-            None
-        };
+    /// Returns `Err` if parsing the tokens fail, if `pub` isn't followed by an item, or if
+    /// there are attributes on an item other than a function
+    fn parse_pub_item(
+        &mut self,
+        attributes: Vec<Attribute>,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        let visibility = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Pub)), "pub")?;
+        self.finish_node(visibility, SyntaxKind::Visibility);
 
-        let initializer =
-            if self.match_token(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Eq))) {
-                match self.parse_expression() {
-                    Ok(expr) => Some(expr),
-                    Err(_) => {
-                        return Err(Box::new(CarbideParserError::InvalidAssignmentTarget(
-                            self.current_location(),
-                        )));
-                    }
+        if let Some(token) = self.peek() {
+            match &token.token_type {
+                Tokens::Keyword(Keywords::Fn) => {
+                    self.parse_function_declaration(attributes, Visibility::Public, checkpoint)
                 }
-            } else {
-                None
-            };
-
-        self.expect(|t| matches!(t, Tokens::Semicolon), ";")?;
-
-        Ok(Statement::LetDeclaration {
-            name,
-            type_annotation,
-            initializer,
-        })
+                _ if !attributes.is_empty() => Err(Box::new(
+                    CarbideParserError::MisplacedAttribute(TokenSummary::from(token)),
+                )),
+                Tokens::Keyword(Keywords::Struct) => {
+                    self.parse_struct_declaration(Visibility::Public, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Enum) => {
+                    self.parse_enum_declaration(Visibility::Public, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Const) => {
+                    self.parse_const_declaration(Visibility::Public, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Trait) => {
+                    self.parse_trait_declaration(Visibility::Public, checkpoint)
+                }
+                _ => Err(Box::new(self.unexpected(PUB_ITEMS))),
+            }
+        } else {
+            Err(Box::new(self.unexpected(PUB_ITEMS)))
+        }
     }
 
-    /// Attempt to parse a block [`Statement`]
+    /// Attempt to parse an `import` [`Statement`], like `import util.strings.{trim, split};`
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_block_statement(&mut self) -> Result<Statement, Box<CarbideParserError>> {
-        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
-
-        let mut statements = Vec::new();
+    fn parse_import(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let start = self.current_offset();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Import)), "import")?;
+
+        let mut path = vec![self.parse_identifier("a module name")?];
+        let mut items = None;
+
+        while self.match_token(|t| matches!(t, Tokens::Period)) {
+            if self.match_token(|t| matches!(t, Tokens::LeftBrace)) {
+                let mut names = Vec::new();
+                while !self.check(|t| matches!(t, Tokens::RightBrace)) {
+                    names.push(self.parse_identifier("an imported item")?);
+                    if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                        break;
+                    }
+                }
+                self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+                items = Some(names);
+                break;
+            }
 
-        while !self.is_eof() && !self.check(|t| matches!(t, Tokens::RightBrace)) {
-            statements.push(self.parse_statement()?);
+            path.push(self.parse_identifier("a module name")?);
         }
 
-        self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+        let span = self.span_from(start);
+        self.expect(|t| matches!(t, Tokens::Semicolon), ";")?;
+        self.finish_node(checkpoint, SyntaxKind::ImportDecl);
 
-        Ok(Statement::Block(statements))
+        Ok(Statement::Import { path, items, span })
     }
 
-    /// Attempt to parse an expression [`Statement`]
+    /// Attempt to parse a declaration with attributes, like `#[inline] fn name() {}`
+    ///
+    /// `checkpoint` is where the first attribute starts
     ///
     /// # Errors
-    /// Returns `Err` if parsing the tokens fail
-    fn parse_expression_statement(&mut self) -> Result<Statement, Box<CarbideParserError>> {
-        let expr = self.parse_expression()?;
-        self.expect(|t| matches!(t, Tokens::Semicolon), ";")?;
+    /// Returns `Err` if parsing the tokens fail, or if the attributes aren't followed by a
+    /// function or `let` declaration
+    fn parse_attributed(
+        &mut self,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        let attributes = self.parse_attributes()?;
+
+        if let Some(token) = self.peek() {
+            match &token.token_type {
+                Tokens::Keyword(Keywords::Let) => self.parse_let_statement(attributes, checkpoint),
+                Tokens::Keyword(Keywords::Fn)
+                    if !self
+                        .peek_ahead(1)
+                        .is_some_and(|t| matches!(t.token_type, Tokens::LeftParen)) =>
+                {
+                    self.parse_function_declaration(attributes, Visibility::Private, checkpoint)
+                }
+                Tokens::Keyword(Keywords::Pub) => self.parse_pub_item(attributes, checkpoint),
+                _ => Err(Box::new(CarbideParserError::MisplacedAttribute(
+                    TokenSummary::from(token),
+                ))),
+            }
+        } else {
+            Err(Box::new(self.unexpected([
+                Expected::Token("fn"),
+                Expected::Token("let"),
+                Expected::Token("pub"),
+            ])))
+        }
+    }
+
+    /// Attempt to parse the [`Attributes`][`Attribute`] before a declaration
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_attributes(&mut self) -> Result<Vec<Attribute>, Box<CarbideParserError>> {
+        let mut attributes = Vec::new();
+        while self.check(|t| matches!(t, Tokens::Hash)) {
+            attributes.push(self.parse_attribute()?);
+        }
+        Ok(attributes)
+    }
+
+    /// Attempt to parse an [`Attribute`], like `#[inline]` or `#[deprecated("use g")]`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_attribute(&mut self) -> Result<Attribute, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let start = self.current_offset();
+        self.expect(|t| matches!(t, Tokens::Hash), "#")?;
+        self.expect(|t| matches!(t, Tokens::LeftBracket), "[")?;
+
+        let name = self.parse_identifier("an attribute name")?;
+
+        let mut arguments = Vec::new();
+        if self.eat(|t| matches!(t, Tokens::LeftParen), "(") {
+            while !self.check(|t| matches!(t, Tokens::RightParen)) {
+                arguments.push(self.parse_attribute_argument()?);
+                if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                    break;
+                }
+            }
+            self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+        }
+
+        self.expect(|t| matches!(t, Tokens::RightBracket), "]")?;
+        self.finish_node(checkpoint, SyntaxKind::Attribute);
+
+        Ok(Attribute {
+            name,
+            arguments,
+            span: self.span_from(start),
+        })
+    }
+
+    /// Attempt to parse an argument of an [`Attribute`], which must be a literal
+    ///
+    /// # Errors
+    /// Returns `Err` if the next token isn't a literal
+    fn parse_attribute_argument(&mut self) -> Result<LiteralValue, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let value = match self.peek().map(|t| &t.token_type) {
+            Some(
+                Tokens::IntLiteral(val) | Tokens::HexLiteral(val) | Tokens::BinaryLiteral(val),
+            ) => LiteralValue::Int(*val),
+            Some(Tokens::FloatLiteral(val)) => LiteralValue::Float(*val),
+            Some(Tokens::StringLiteral(s)) => LiteralValue::String(s.clone()),
+            Some(Tokens::Identifier(name)) if *name == "true" || *name == "false" => {
+                LiteralValue::Bool(*name == "true")
+            }
+            _ => return Err(Box::new(self.unexpected([Expected::Kind("a literal")]))),
+        };
+
+        self.advance();
+        self.finish_node(checkpoint, SyntaxKind::Literal);
+        Ok(value)
+    }
+
+    /// Attempt to parse a `let` [`Statement`]
+    ///
+    /// `checkpoint` is where the statement, including its `attributes`, starts
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_let_statement(
+        &mut self,
+        attributes: Vec<Attribute>,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Let)), "let")?;
+        let mutable = self.match_token(|t| matches!(t, Tokens::Keyword(Keywords::Mut)));
+
+        let pattern_start = self.current_offset();
+        let pattern = self.parse_pattern()?;
+        let span = self.span_from(pattern_start);
+
+        // Parse optional type annotation
+        let type_annotation = if self.eat(|t| matches!(t, Tokens::Colon), ":") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        let initializer = if self.eat(
+            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Eq)),
+            "=",
+        ) {
+            Some(self.parse_expression()?)
+        } else {
+            None
+        };
+
+        self.expect_semicolon()?;
+        self.finish_node(checkpoint, SyntaxKind::LetStmt);
+
+        Ok(Statement::LetDeclaration {
+            attributes,
+            mutable,
+            pattern,
+            type_annotation,
+            initializer,
+            span,
+        })
+    }
+
+    /// Attempt to parse a `const` [`Statement`], like `const NAME: type = value;`
+    ///
+    /// Constants are only allowed at the top level, but are still parsed elsewhere
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_const_declaration(
+        &mut self,
+        visibility: Visibility,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        let keyword = self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Const)), "const")?;
+        let location = keyword.start;
+
+        if self.block_depth > 0 {
+            self.errors
+                .push(CarbideParserError::ConstOutsideTopLevel(location));
+        }
+
+        let name_start = self.current_offset();
+        let name = self.parse_identifier("a constant name")?;
+        let span = self.span_from(name_start);
+
+        self.expect(|t| matches!(t, Tokens::Colon), ":")?;
+        let type_annotation = self.parse_type()?;
+
+        self.expect(
+            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Eq)),
+            "=",
+        )?;
+        let value = self.parse_expression()?;
+        self.expect_semicolon()?;
+        self.finish_node(checkpoint, SyntaxKind::ConstDecl);
+
+        Ok(Statement::ConstDeclaration {
+            visibility,
+            name,
+            type_annotation,
+            value,
+            span,
+        })
+    }
+
+    /// Attempt to parse a block [`Statement`]
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_block_statement(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let block = self.parse_block()?;
+
+        // A block with a value is kept as an expression, so it can be a tail expression
+        if block.tail.is_some() {
+            self.finish_node(checkpoint, SyntaxKind::ExprStmt);
+            Ok(Statement::Expression(Expression::Block(block)))
+        } else {
+            Ok(Statement::Block(block.statements))
+        }
+    }
+
+    /// Attempt to parse a `{ ... }` [`Block`]
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_block(&mut self) -> Result<Block, Box<CarbideParserError>> {
+        self.with_struct_literals(true, |parser| {
+            let checkpoint = parser.checkpoint();
+            parser.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
+
+            parser.block_depth += 1;
+            let block = parser.nested(|parser| parser.parse_block_contents(None));
+            parser.block_depth -= 1;
+            let block = block?;
+
+            parser.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+            parser.finish_node(checkpoint, SyntaxKind::Block);
+
+            Ok(block)
+        })
+    }
+
+    /// Attempt to parse the statements and tail expression of a block, up to its closing `}`
+    ///
+    /// `first` is an expression already parsed at the start of the block, along with where
+    /// it started
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_block_contents(
+        &mut self,
+        first: Option<(Marker, Expression)>,
+    ) -> Result<Block, Box<CarbideParserError>> {
+        let mut statements = Vec::new();
+        let mut next = first;
+
+        loop {
+            let (marker, first) = match next.take() {
+                Some((marker, expr)) => (marker, Some(expr)),
+                None => (self.marker(), None),
+            };
+
+            match self.parse_block_item(first, marker.checkpoint) {
+                Ok(BlockItem::Statement(statement)) => statements.push(statement),
+                Ok(BlockItem::Tail(expr)) => {
+                    return Ok(Block {
+                        statements,
+                        tail: Some(Box::new(expr)),
+                    });
+                }
+                Ok(BlockItem::End) => {
+                    return Ok(Block {
+                        statements,
+                        tail: None,
+                    });
+                }
+                // Recover within the block, so one bad statement doesn't lose the rest
+                Err(error) => {
+                    let statement = self.recover_statement(marker, *error);
+                    statements.push(statement);
+                }
+            }
+        }
+    }
+
+    /// Attempt to parse the next statement or the tail expression of a block
+    ///
+    /// `first` is an expression already parsed at the start of the item, which starts at
+    /// `checkpoint`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_block_item(
+        &mut self,
+        first: Option<Expression>,
+        checkpoint: Checkpoint,
+    ) -> Result<BlockItem, Box<CarbideParserError>> {
+        let expr = if let Some(expr) = first {
+            expr
+        } else {
+            if self.is_eof() || self.check(|t| matches!(t, Tokens::RightBrace)) {
+                return Ok(BlockItem::End);
+            }
+
+            if self.check(|t| matches!(t, Tokens::LeftBrace)) {
+                let block = self.parse_block()?;
+                if block.tail.is_none() {
+                    return Ok(BlockItem::Statement(Statement::Block(block.statements)));
+                }
+
+                // A nested block with a value can be the tail of this one too
+                Expression::Block(block)
+            } else if self.at_expression_statement() {
+                self.parse_statement_expression()?
+            } else {
+                return Ok(BlockItem::Statement(self.parse_statement()?));
+            }
+        };
+
+        if self.check(|t| matches!(t, Tokens::RightBrace)) {
+            Ok(BlockItem::Tail(expr))
+        } else {
+            Ok(BlockItem::Statement(
+                self.finish_expression_statement(expr, checkpoint)?,
+            ))
+        }
+    }
+
+    /// Check if the next token starts an expression statement, rather than a declaration,
+    /// block or other statement
+    fn at_expression_statement(&self) -> bool {
+        match self.peek().map(|t| &t.token_type) {
+            Some(Tokens::Keyword(Keywords::Fn)) => self
+                .peek_ahead(1)
+                .is_some_and(|t| matches!(t.token_type, Tokens::LeftParen)),
+            Some(
+                Tokens::Keyword(
+                    Keywords::Let
+                    | Keywords::Const
+                    | Keywords::Return
+                    | Keywords::Struct
+                    | Keywords::Enum
+                    | Keywords::Trait
+                    | Keywords::Impl
+                    | Keywords::Pub
+                    | Keywords::Import
+                    | Keywords::While
+                    | Keywords::For
+                    | Keywords::Break
+                    | Keywords::Continue,
+                )
+                | Tokens::LeftBrace
+                | Tokens::Hash,
+            ) => false,
+            _ => true,
+        }
+    }
+
+    /// Attempt to parse an expression [`Statement`]
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_expression_statement(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let expr = self.parse_statement_expression()?;
+        self.finish_expression_statement(expr, checkpoint)
+    }
+
+    /// Attempt to parse the [`Expression`] of an expression statement
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_statement_expression(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        // `if` and `match` end at their `}` at the start of a statement, so
+        // `if a {} (b, c);` is two statements rather than a call
+        match self.peek().map(|t| &t.token_type) {
+            Some(Tokens::Keyword(Keywords::If)) => self.parse_if(),
+            Some(Tokens::Keyword(Keywords::Match)) => self.parse_match(),
+            _ => self.parse_expression(),
+        }
+    }
+
+    /// Attempt to parse the `;` after the [`Expression`] of an expression statement, which
+    /// starts at `checkpoint`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn finish_expression_statement(
+        &mut self,
+        expr: Expression,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        // Expressions ending in `}` don't need a `;` to be used as statements
+        if expr.is_block_like() {
+            self.match_token(|t| matches!(t, Tokens::Semicolon));
+        } else {
+            self.expect_semicolon()?;
+        }
+        self.finish_node(checkpoint, SyntaxKind::ExprStmt);
+
         Ok(Statement::Expression(expr))
     }
 
@@ -312,410 +1304,1517 @@ impl CarbideParser<'_> {
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_expression(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        self.parse_assignment()
+    fn parse_expression(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        self.parse_expression_bp(Precedence::Lowest.binding_power())
+    }
+
+    /// Attempt to parse an [`Expression`] that can't be a struct literal, like the
+    /// condition in `while ready { ... }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_condition(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        self.with_struct_literals(false, Self::parse_expression)
+    }
+
+    /// Attempt to parse an [`Expression`] inside delimiters like `( ... )`, where struct
+    /// literals are unambiguous again
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_delimited_expression(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        self.with_struct_literals(true, Self::parse_expression)
+    }
+
+    /// Run `parse` with struct literals allowed or disallowed
+    ///
+    /// # Errors
+    /// Returns `Err` if `parse` fails
+    fn with_struct_literals<T>(
+        &mut self,
+        allowed: bool,
+        parse: impl FnOnce(&mut Self) -> Result<T, Box<CarbideParserError>>,
+    ) -> Result<T, Box<CarbideParserError>> {
+        let enclosing = std::mem::replace(&mut self.struct_literals_allowed, allowed);
+        let result = parse(self);
+        self.struct_literals_allowed = enclosing;
+        result
+    }
+
+    /// Attempt to parse an [`Expression`] whose operators bind at least as tightly as `min_bp`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_expression_bp(&mut self, min_bp: u8) -> Result<Expression, Box<CarbideParserError>> {
+        self.measured(|parser| {
+            let start = parser.current_offset();
+            let checkpoint = parser.checkpoint();
+            let left = parser.nested(Self::parse_prefix)?;
+            parser.parse_operators(left, min_bp, start, checkpoint)
+        })
+    }
+
+    /// Attempt to apply the operators that bind at least as tightly as `min_bp` to `left`,
+    /// which starts at `start` and `checkpoint`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_operators(
+        &mut self,
+        mut left: Expression,
+        min_bp: u8,
+        start: u64,
+        checkpoint: Checkpoint,
+    ) -> Result<Expression, Box<CarbideParserError>> {
+        while let Some(token) = self.peek() {
+            match &token.token_type {
+                Tokens::LeftParen | Tokens::LeftBracket | Tokens::Period => {
+                    if Precedence::Postfix.binding_power() < min_bp {
+                        break;
+                    }
+                    self.wrap()?;
+                    left = self.nested(|parser| parser.parse_postfix(left, checkpoint))?;
+                }
+                Tokens::UnaryOperator(op) if op.fixity() == Fixity::Postfix => {
+                    let operator = *op;
+                    if operator.binding_power().0 < min_bp {
+                        break;
+                    }
+                    self.wrap()?;
+                    self.advance();
+                    self.finish_node(checkpoint, SyntaxKind::PostfixExpr);
+                    left = Expression::UnaryOp {
+                        operator,
+                        operand: Box::new(left),
+                    };
+                }
+                Tokens::BinaryOperator(op) => {
+                    let operator = *op;
+                    let (left_bp, right_bp) = operator.binding_power();
+                    if left_bp < min_bp {
+                        break;
+                    }
+
+                    // The assignment is kept, so the rest of the statement is still checked
+                    let assignment = operator == BinaryOperators::Eq;
+                    if assignment && !left.is_place() {
+                        self.errors
+                            .push(CarbideParserError::InvalidAssignmentTarget(
+                                self.span_from(start),
+                            ));
+                    }
+
+                    self.wrap()?;
+                    self.advance();
+                    let right = self.nested(|parser| parser.parse_expression_bp(right_bp))?;
+
+                    self.finish_node(
+                        checkpoint,
+                        if assignment {
+                            SyntaxKind::AssignExpr
+                        } else {
+                            SyntaxKind::BinaryExpr
+                        },
+                    );
+
+                    left = if assignment {
+                        Expression::Assignment {
+                            target: Box::new(left),
+                            value: Box::new(right),
+                            span: self.span_from(start),
+                        }
+                    } else {
+                        Expression::BinaryOp {
+                            left: Box::new(left),
+                            operator,
+                            right: Box::new(right),
+                        }
+                    };
+                }
+                _ => break,
+            }
+        }
+
+        // Whatever stopped the loop, an operator could have continued the expression here
+        self.expected.insert(Expected::Kind("an operator"));
+        Ok(left)
+    }
+
+    /// Attempt to parse a prefix operation, or a primary [`Expression`] if there is none
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_prefix(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+
+        if let Some(token) = self.peek()
+            && let Tokens::UnaryOperator(op) = &token.token_type
+            && op.fixity() == Fixity::Prefix
+        {
+            let operator = *op;
+            self.advance();
+            let operand = self.parse_expression_bp(operator.binding_power().1)?;
+            self.finish_node(checkpoint, SyntaxKind::PrefixExpr);
+            return Ok(Expression::UnaryOp {
+                operator,
+                operand: Box::new(operand),
+            });
+        }
+
+        self.parse_primary()
+    }
+
+    /// Attempt to parse a call, index or member access on `target`, which starts at
+    /// `checkpoint`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_postfix(
+        &mut self,
+        target: Expression,
+        checkpoint: Checkpoint,
+    ) -> Result<Expression, Box<CarbideParserError>> {
+        if self.check(|t| matches!(t, Tokens::LeftParen)) {
+            self.finish_call(target, checkpoint)
+        } else if self.match_token(|t| matches!(t, Tokens::LeftBracket)) {
+            let index = self.parse_delimited_expression()?;
+            self.expect(|t| matches!(t, Tokens::RightBracket), "]")?;
+            self.finish_node(checkpoint, SyntaxKind::IndexExpr);
+            Ok(Expression::Index {
+                target: Box::new(target),
+                index: Box::new(index),
+            })
+        } else {
+            self.expect(|t| matches!(t, Tokens::Period), ".")?;
+            let member_start = self.current_offset();
+            let member = self.parse_identifier("a property name")?;
+            let span = self.span_from(member_start);
+            self.finish_node(checkpoint, SyntaxKind::FieldExpr);
+
+            Ok(Expression::MemberAccess {
+                target: Box::new(target),
+                member,
+                span,
+            })
+        }
+    }
+}
+
+impl CarbideParser<'_> {
+    /// Attempt to parse a function declaration
+    ///
+    /// `checkpoint` is where the declaration, including its `attributes` and `pub`, starts
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_function_declaration(
+        &mut self,
+        attributes: Vec<Attribute>,
+        visibility: Visibility,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Fn)), "fn")?;
+
+        let name = self.parse_identifier("a function name")?;
+
+        let type_parameters =
+            if self.check(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Lt))) {
+                self.parse_type_parameters()?
+            } else {
+                Vec::new()
+            };
+
+        self.expect(|t| matches!(t, Tokens::LeftParen), "(")?;
+        let parameters = self.parse_parameters(|t| matches!(t, Tokens::RightParen))?;
+        self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+
+        // Parse optional return type annotation
+        let return_type = if self.eat(|t| matches!(t, Tokens::ThinArrow), "->") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        let body = if self.check(|t| matches!(t, Tokens::LeftBrace)) {
+            self.in_function_scope(Self::parse_block)?
+        } else {
+            return Err(Box::new(self.unexpected([Expected::Token("{")])));
+        };
+        self.finish_node(checkpoint, SyntaxKind::FnDecl);
+
+        Ok(Statement::FunctionDeclaration {
+            attributes,
+            visibility,
+            name,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+        })
+    }
+
+    /// Attempt to parse the `<T: Bound + Other, U>` [`TypeParameters`][`TypeParameter`] of a
+    /// generic function
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_type_parameters(&mut self) -> Result<Vec<TypeParameter>, Box<CarbideParserError>> {
+        let list = self.checkpoint();
+        self.expect(
+            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Lt)),
+            "<",
+        )?;
+
+        let mut type_parameters = Vec::new();
+        while !self.check(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Gt))) {
+            let checkpoint = self.checkpoint();
+            let name_start = self.current_offset();
+            let name = self.parse_identifier("a type parameter name")?;
+            let span = self.span_from(name_start);
+
+            let mut bounds = Vec::new();
+            if self.eat(|t| matches!(t, Tokens::Colon), ":") {
+                loop {
+                    bounds.push(self.parse_identifier("a bound")?);
+                    if !self
+                        .match_token(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Plus)))
+                    {
+                        break;
+                    }
+                }
+            }
+            self.finish_node(checkpoint, SyntaxKind::TypeParam);
+
+            type_parameters.push(TypeParameter { name, bounds, span });
+
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
+            }
+        }
+
+        self.expect(
+            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Gt)),
+            ">",
+        )?;
+        self.finish_node(list, SyntaxKind::TypeParamList);
+
+        Ok(type_parameters)
+    }
+
+    /// Attempt to parse a comma separated list of [`Parameters`][`Parameter`], stopping
+    /// before `close`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_parameters(
+        &mut self,
+        close: impl Fn(&Tokens) -> bool,
+    ) -> Result<Vec<Parameter>, Box<CarbideParserError>> {
+        let list = self.checkpoint();
+        let mut parameters = Vec::new();
+        if !self.check(close) {
+            loop {
+                if parameters.len() == self.limits.max_parameters {
+                    self.errors
+                        .push(CarbideParserError::TooManyParameters(self.current_start()));
+                }
+
+                let checkpoint = self.checkpoint();
+                let mutable = self.match_token(|t| matches!(t, Tokens::Keyword(Keywords::Mut)));
+
+                let pattern_start = self.current_offset();
+                let pattern = self.parse_pattern()?;
+                let span = self.span_from(pattern_start);
+
+                // Parse optional type annotation for parameter
+                let type_annotation = if self.eat(|t| matches!(t, Tokens::Colon), ":") {
+                    Some(self.parse_type()?)
+                } else {
+                    None
+                };
+                self.finish_node(checkpoint, SyntaxKind::Param);
+
+                parameters.push(Parameter {
+                    mutable,
+                    pattern,
+                    type_annotation,
+                    span,
+                });
+
+                if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                    break;
+                }
+            }
+        }
+        self.finish_node(list, SyntaxKind::ParamList);
+
+        Ok(parameters)
+    }
+
+    /// Run `parse` on the body of a function or lambda
+    ///
+    /// `break`/`continue` can't reach loops outside of the function, so the enclosing loops
+    /// are hidden while parsing it
+    ///
+    /// # Errors
+    /// Returns `Err` if `parse` fails
+    fn in_function_scope<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, Box<CarbideParserError>>,
+    ) -> Result<T, Box<CarbideParserError>> {
+        let enclosing_loops = std::mem::take(&mut self.loop_depth);
+        self.function_depth += 1;
+        let result = parse(self);
+        self.function_depth -= 1;
+        self.loop_depth = enclosing_loops;
+        result
+    }
+
+    /// Attempt to parse a lambda [`Expression`], like `|a: int, b| => a + b` or
+    /// `fn(x) -> int => { ... }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_lambda(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let parameters =
+            if self.match_token(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::OrOr))) {
+                // `||` is lexed as a single operator
+                Vec::new()
+            } else if self.match_token(|t| matches!(t, Tokens::Keyword(Keywords::Fn))) {
+                self.expect(|t| matches!(t, Tokens::LeftParen), "(")?;
+                let parameters = self.parse_parameters(|t| matches!(t, Tokens::RightParen))?;
+                self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+                parameters
+            } else {
+                self.expect(|t| matches!(t, Tokens::Pipe), "|")?;
+                let parameters = self.parse_parameters(|t| matches!(t, Tokens::Pipe))?;
+                self.expect(|t| matches!(t, Tokens::Pipe), "|")?;
+                parameters
+            };
+
+        let return_type = if self.eat(|t| matches!(t, Tokens::ThinArrow), "->") {
+            Some(self.parse_type()?)
+        } else {
+            None
+        };
+
+        self.expect(|t| matches!(t, Tokens::FatArrow), "=>")?;
+
+        let body = self.in_function_scope(|parser| {
+            if parser.check(|t| matches!(t, Tokens::LeftBrace)) {
+                Ok(LambdaBody::Block(parser.parse_block()?))
+            } else {
+                Ok(LambdaBody::Expression(Box::new(parser.parse_expression()?)))
+            }
+        })?;
+        self.finish_node(checkpoint, SyntaxKind::LambdaExpr);
+
+        Ok(Expression::Lambda {
+            parameters,
+            return_type,
+            body,
+        })
+    }
+}
+
+impl CarbideParser<'_> {
+    /// Attempt to parse the arguments of a call to `callee`, which starts at `checkpoint`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn finish_call(
+        &mut self,
+        callee: Expression,
+        checkpoint: Checkpoint,
+    ) -> Result<Expression, Box<CarbideParserError>> {
+        let list = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::LeftParen), "(")?;
+        let mut arguments = Vec::new();
+
+        if !self.check(|t| matches!(t, Tokens::RightParen)) {
+            loop {
+                if arguments.len() == self.limits.max_arguments {
+                    self.errors
+                        .push(CarbideParserError::TooManyArguments(self.current_start()));
+                }
+
+                let marker = self.marker();
+                match self.parse_delimited_expression() {
+                    Ok(argument) => arguments.push(argument),
+                    // Recover within the arguments, so `f(a, +, b)` still has `a` and `b`
+                    Err(error) => {
+                        self.errors.push(*error);
+                        self.skip_list_item();
+                        self.finish_node(marker.checkpoint, SyntaxKind::Error);
+                        arguments.push(Expression::Error(self.span_from(marker.offset)));
+                    }
+                }
+                if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                    break;
+                }
+            }
+        }
+
+        self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+        self.finish_node(list, SyntaxKind::ArgList);
+        self.finish_node(checkpoint, SyntaxKind::CallExpr);
+
+        Ok(Expression::Call {
+            callee: Box::new(callee),
+            arguments,
+        })
+    }
+
+    /// Attempt to parse a primary [`Expression`]
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    #[allow(clippy::too_many_lines)]
+    fn parse_primary(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+
+        if let Some(token) = self.peek() {
+            match &token.token_type {
+                Tokens::FloatLiteral(val) => {
+                    let value = *val;
+                    self.advance();
+                    self.finish_node(checkpoint, SyntaxKind::Literal);
+                    Ok(Expression::Literal(LiteralValue::Float(value)))
+                }
+                Tokens::StringLiteral(s) => {
+                    let value = s.clone();
+                    self.advance();
+                    self.finish_node(checkpoint, SyntaxKind::Literal);
+                    Ok(Expression::Literal(LiteralValue::String(value)))
+                }
+                Tokens::IntLiteral(val) | Tokens::HexLiteral(val) | Tokens::BinaryLiteral(val) => {
+                    let value = *val;
+                    self.advance();
+                    self.finish_node(checkpoint, SyntaxKind::Literal);
+                    Ok(Expression::Literal(LiteralValue::Int(value)))
+                }
+                Tokens::InterpolatedString(parts) => {
+                    let parts = parts.clone();
+                    let string_parts = self.parse_interpolated_string(&parts)?;
+                    self.advance();
+                    self.finish_node(checkpoint, SyntaxKind::Literal);
+                    Ok(Expression::InterpolatedString {
+                        parts: string_parts,
+                    })
+                }
+                Tokens::Identifier(name) => {
+                    let ident = (*name).to_string();
+                    let start = self.current_offset();
+                    self.advance();
+
+                    if ident == "true" || ident == "false" {
+                        self.finish_node(checkpoint, SyntaxKind::Literal);
+                        return Ok(Expression::Literal(LiteralValue::Bool(ident == "true")));
+                    }
+
+                    let ident = self.parse_qualified_name(ident)?;
+
+                    // Only `::<` is left after the qualified name
+                    if self.match_token(|t| matches!(t, Tokens::ColonColon)) {
+                        return self.parse_type_arguments(ident, start, checkpoint);
+                    }
+
+                    if self.struct_literals_allowed
+                        && self.check(|t| matches!(t, Tokens::LeftBrace))
+                    {
+                        return self.parse_struct_literal(ident, checkpoint);
+                    }
+
+                    self.finish_node(checkpoint, SyntaxKind::PathExpr);
+                    Ok(Expression::Identifier {
+                        name: ident,
+                        span: self.span_from(start),
+                    })
+                }
+                Tokens::Pipe
+                | Tokens::BinaryOperator(BinaryOperators::OrOr)
+                | Tokens::Keyword(Keywords::Fn) => self.parse_lambda(),
+                Tokens::Keyword(Keywords::Match) => self.parse_match(),
+                Tokens::Keyword(Keywords::If) => self.parse_if(),
+                Tokens::LeftParen => {
+                    self.advance();
+
+                    let mut elements = Vec::new();
+                    let mut trailing_comma = false;
+                    while !self.check(|t| matches!(t, Tokens::RightParen)) {
+                        elements.push(self.parse_delimited_expression()?);
+                        trailing_comma = self.eat(|t| matches!(t, Tokens::Comma), ",");
+                        if !trailing_comma {
+                            break;
+                        }
+                    }
+
+                    self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+
+                    // `(expr)` is a grouping, while `(expr,)` is a one element tuple
+                    if elements.len() == 1 && !trailing_comma {
+                        self.finish_node(checkpoint, SyntaxKind::ParenExpr);
+                        Ok(Expression::Grouped(Box::new(elements.remove(0))))
+                    } else {
+                        self.finish_node(checkpoint, SyntaxKind::TupleExpr);
+                        Ok(Expression::Tuple(elements))
+                    }
+                }
+                Tokens::LeftBracket => {
+                    self.advance();
+                    let mut elements = Vec::new();
+
+                    if !self.check(|t| matches!(t, Tokens::RightBracket)) {
+                        loop {
+                            elements.push(self.parse_delimited_expression()?);
+                            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                                break;
+                            }
+                        }
+                    }
+
+                    self.expect(|t| matches!(t, Tokens::RightBracket), "]")?;
+                    self.finish_node(checkpoint, SyntaxKind::ArrayExpr);
+                    Ok(Expression::Array(elements))
+                }
+                Tokens::LeftBrace if self.struct_literals_allowed => self.parse_brace_expression(),
+                _ => Err(Box::new(self.unexpected([Expected::Kind("an expression")]))),
+            }
+        } else {
+            Err(Box::new(self.unexpected([Expected::Kind("an expression")])))
+        }
+    }
+
+    /// Attempt to parse a map literal, like `{ "a": 1, key: value }`, or a block
+    /// [`Expression`], like `{ let t = f(); t * 2 }`
+    ///
+    /// `{}` is an empty map in expression position, but still an empty block at the start
+    /// of a statement
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_brace_expression(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        self.with_struct_literals(true, |parser| {
+            let checkpoint = parser.checkpoint();
+            parser.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
+
+            if parser.match_token(|t| matches!(t, Tokens::RightBrace)) {
+                parser.finish_node(checkpoint, SyntaxKind::MapExpr);
+                return Ok(Expression::Map(Vec::new()));
+            }
+
+            parser.block_depth += 1;
+            let expr = parser.parse_brace_contents();
+            parser.block_depth -= 1;
+            let expr = expr?;
+
+            parser.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+            parser.finish_node(
+                checkpoint,
+                if matches!(expr, Expression::Map(_)) {
+                    SyntaxKind::MapExpr
+                } else {
+                    SyntaxKind::Block
+                },
+            );
+
+            Ok(expr)
+        })
+    }
+
+    /// Attempt to parse the contents of a non-empty map literal or block [`Expression`], up
+    /// to its closing `}`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_brace_contents(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        if !self.at_expression_statement() {
+            return Ok(Expression::Block(self.parse_block_contents(None)?));
+        }
+
+        // A leading expression is the first key of a map when followed by `:`, otherwise
+        // it starts the first statement of a block
+        let marker = self.marker();
+        let first = self.parse_statement_expression()?;
+        if !self.eat(|t| matches!(t, Tokens::Colon), ":") {
+            return Ok(Expression::Block(
+                self.parse_block_contents(Some((marker, first)))?,
+            ));
+        }
+
+        let value = self.parse_delimited_expression()?;
+        self.finish_node(marker.checkpoint, SyntaxKind::MapEntry);
+
+        let mut entries = vec![MapEntry { key: first, value }];
+        while self.eat(|t| matches!(t, Tokens::Comma), ",")
+            && !self.check(|t| matches!(t, Tokens::RightBrace))
+        {
+            let checkpoint = self.checkpoint();
+            let key = self.parse_delimited_expression()?;
+            self.expect(|t| matches!(t, Tokens::Colon), ":")?;
+            let value = self.parse_delimited_expression()?;
+            self.finish_node(checkpoint, SyntaxKind::MapEntry);
+
+            entries.push(MapEntry { key, value });
+        }
+
+        Ok(Expression::Map(entries))
+    }
+
+    /// Attempt to parse an `if` [`Expression`], like `if a { 1 } else { 2 }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_if(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::If)), "if")?;
+
+        let condition = self.parse_condition()?;
+        let then_branch = self.parse_block()?;
+
+        let else_branch = if self.eat(|t| matches!(t, Tokens::Keyword(Keywords::Else)), "else") {
+            if self.check(|t| matches!(t, Tokens::Keyword(Keywords::If))) {
+                Some(Box::new(self.nested(Self::parse_if)?))
+            } else {
+                Some(Box::new(Expression::Block(self.parse_block()?)))
+            }
+        } else {
+            None
+        };
+        self.finish_node(checkpoint, SyntaxKind::IfExpr);
+
+        Ok(Expression::If {
+            condition: Box::new(condition),
+            then_branch,
+            else_branch,
+        })
+    }
+
+    /// Attempt to parse the parts of an interpolated string
+    ///
+    /// Each interpolation is parsed on its own, with the same limits and at the same depth
+    /// as the string. Its errors are kept with the others, and one that doesn't parse, or
+    /// has tokens left after its expression, is kept as an [`Expression::Error`]
+    ///
+    /// # Errors
+    /// Returns `Err` if an interpolation fails to lex
+    pub(crate) fn parse_interpolated_string(
+        &mut self,
+        parts: &[carbide_lexer::tokens::StringPart],
+    ) -> Result<Vec<StringPart>, Box<CarbideParserError>> {
+        let mut result = Vec::new();
+
+        for part in parts {
+            match part {
+                carbide_lexer::tokens::StringPart::Text(text) => {
+                    result.push(StringPart::Text(text.clone()));
+                }
+                carbide_lexer::tokens::StringPart::Interpolation { code, start } => {
+                    let mut lexer = carbide_lexer::lexer::CarbideLexer::from_src(code);
+                    let mut tokens = lexer
+                        .lex_strict()
+                        .map_err(|_| CarbideParserError::ExpectedExpression(*start))?;
+                    for token in &mut tokens {
+                        Self::relocate(token, *start);
+                    }
+
+                    let mut mini_parser = CarbideParser::with_limits(tokens, self.limits);
+                    mini_parser.depth = self.depth;
+                    let NodeResult { node, errors } = mini_parser.parse_single_expression();
+                    self.errors.extend(errors.into_iter().map(|error| *error));
+
+                    let expr = node.unwrap_or_else(|| {
+                        Expression::Error(start.offset..start.offset + code.len() as u64)
+                    });
+                    result.push(StringPart::Expression(Box::new(expr)));
+                }
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Move a [`Token`] lexed on its own to where its source starts, at `start`
+    fn relocate(token: &mut Token<'_>, start: SourceLocation) {
+        let shift = |location: &mut SourceLocation| {
+            if location.line == 1 {
+                location.column += start.column - 1;
+            }
+            location.line += start.line - 1;
+            location.offset += start.offset;
+        };
+        shift(&mut token.start);
+        shift(&mut token.end);
+        token.span = token.span.start + start.offset..token.span.end + start.offset;
+    }
+}
+
+impl CarbideParser<'_> {
+    /// Attempt to parse a return statement, like `return expr;` or a bare `return;`
+    ///
+    /// The `;` of a bare return can be left out when it ends its block, as in `{ return }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    pub fn parse_return(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let location = self
+            .expect(|t| matches!(t, Tokens::Keyword(Keywords::Return)), "return")?
+            .start;
+
+        if self.function_depth == 0 {
+            self.errors
+                .push(CarbideParserError::ReturnOutsideFunction(location));
+        }
+
+        let return_expr = if self.check(|t| matches!(t, Tokens::RightBrace))
+            || self.eat(|t| matches!(t, Tokens::Semicolon), ";")
+        {
+            None
+        } else {
+            let value = self.parse_expression()?;
+            self.expect_semicolon()?;
+            Some(value)
+        };
+        self.finish_node(checkpoint, SyntaxKind::ReturnStmt);
+
+        Ok(Statement::Return(return_expr))
+    }
+}
+
+impl CarbideParser<'_> {
+    /// Attempt to parse a loop body, tracking that we're inside a loop
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_loop_body(&mut self) -> Result<Vec<Statement>, Box<CarbideParserError>> {
+        self.loop_depth += 1;
+        let body = self.parse_block();
+        self.loop_depth -= 1;
+
+        // The value of a loop body is discarded
+        let Block {
+            mut statements,
+            tail,
+        } = body?;
+        statements.extend(tail.map(|tail| Statement::Expression(*tail)));
+
+        Ok(statements)
+    }
+
+    /// Attempt to parse a `while` loop, like `while cond { ... }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_while(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::While)), "while")?;
+
+        let condition = self.parse_condition()?;
+        let body = self.parse_loop_body()?;
+        self.finish_node(checkpoint, SyntaxKind::WhileStmt);
+
+        Ok(Statement::While { condition, body })
+    }
+
+    /// Attempt to parse a C-style `for` loop, like `for (init; cond; step) { ... }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_for(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::For)), "for")?;
+        self.expect(|t| matches!(t, Tokens::LeftParen), "(")?;
+
+        // Both `let` and expression statements consume their own `;`
+        let initializer = if self.match_token(|t| matches!(t, Tokens::Semicolon)) {
+            None
+        } else if self.check(|t| matches!(t, Tokens::Keyword(Keywords::Let))) {
+            let checkpoint = self.checkpoint();
+            Some(Box::new(self.parse_let_statement(Vec::new(), checkpoint)?))
+        } else {
+            Some(Box::new(self.parse_expression_statement()?))
+        };
+
+        let condition = if self.check(|t| matches!(t, Tokens::Semicolon)) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(|t| matches!(t, Tokens::Semicolon), ";")?;
+
+        let increment = if self.check(|t| matches!(t, Tokens::RightParen)) {
+            None
+        } else {
+            Some(self.parse_expression()?)
+        };
+        self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+
+        let body = self.parse_loop_body()?;
+        self.finish_node(checkpoint, SyntaxKind::ForStmt);
+
+        Ok(Statement::For {
+            initializer,
+            condition,
+            increment,
+            body,
+        })
+    }
+
+    /// Attempt to parse a `break;` or `continue;` statement
+    ///
+    /// Using either outside of a loop is reported, but still yields a [`Statement`]
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_loop_control(
+        &mut self,
+        keyword: Keywords,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let location = self
+            .expect(|t| *t == Tokens::Keyword(keyword), keyword.as_str())?
+            .start;
+
+        if self.loop_depth == 0 {
+            self.errors.push(if keyword == Keywords::Break {
+                CarbideParserError::BreakOutsideLoop(location)
+            } else {
+                CarbideParserError::ContinueOutsideLoop(location)
+            });
+        }
+
+        self.expect(|t| matches!(t, Tokens::Semicolon), ";")?;
+
+        Ok(if keyword == Keywords::Break {
+            self.finish_node(checkpoint, SyntaxKind::BreakStmt);
+            Statement::Break
+        } else {
+            self.finish_node(checkpoint, SyntaxKind::ContinueStmt);
+            Statement::Continue
+        })
+    }
+}
+
+impl CarbideParser<'_> {
+    /// Attempt to parse an identifier, returning its name
+    ///
+    /// # Errors
+    /// Returns `Err` if the current token isn't an identifier
+    fn parse_identifier(
+        &mut self,
+        expected: &'static str,
+    ) -> Result<String, Box<CarbideParserError>> {
+        let Some(Tokens::Identifier(name)) = self.peek().map(|t| &t.token_type) else {
+            return Err(Box::new(self.unexpected([Expected::Kind(expected)])));
+        };
+
+        let name = (*name).to_string();
+        self.advance();
+        Ok(name)
+    }
+
+    /// Attempt to parse a struct declaration, like `struct Point { x: float, y: float }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_struct_declaration(
+        &mut self,
+        visibility: Visibility,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Struct)), "struct")?;
+        let name = self.parse_identifier("a struct name")?;
+        let fields = self.parse_struct_fields()?;
+        self.finish_node(checkpoint, SyntaxKind::StructDecl);
+
+        Ok(Statement::StructDeclaration {
+            visibility,
+            name,
+            fields,
+        })
     }
 
-    /// Attempt to parse an assignment
+    /// Attempt to parse the `{ name: type, ... }` fields of a struct
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_assignment(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        let expr = self.parse_equality()?;
+    fn parse_struct_fields(&mut self) -> Result<Vec<StructField>, Box<CarbideParserError>> {
+        let list = self.checkpoint();
+        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
 
-        if self.match_token(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Eq))) {
-            let value = self.parse_assignment()?;
-            return Ok(Expression::Assignment {
-                target: Box::new(expr),
-                value: Box::new(value),
+        let mut fields = Vec::new();
+        while !self.check(|t| matches!(t, Tokens::RightBrace)) {
+            let checkpoint = self.checkpoint();
+            let name = self.parse_identifier("a field name")?;
+            self.expect(|t| matches!(t, Tokens::Colon), ":")?;
+            let type_annotation = self.parse_type()?;
+            self.finish_node(checkpoint, SyntaxKind::FieldDecl);
+
+            fields.push(StructField {
+                name,
+                type_annotation,
             });
+
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
+            }
         }
 
-        Ok(expr)
+        self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+        self.finish_node(list, SyntaxKind::FieldList);
+
+        Ok(fields)
     }
 
-    /// Attempt to parse an equality expression
+    /// Attempt to parse the `{ ... }` of a struct literal after its `name`, like
+    /// `{ x: 1.0, y }` or `{ x: 1.0, ..origin }`
+    ///
+    /// `checkpoint` is where the literal, including its name, starts
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_equality(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        let mut left = self.parse_comparison()?;
+    fn parse_struct_literal(
+        &mut self,
+        name: String,
+        checkpoint: Checkpoint,
+    ) -> Result<Expression, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
 
-        while let Some(token) = self.peek() {
-            if let Tokens::BinaryOperator(op) = &token.token_type {
-                match op {
-                    BinaryOperators::EqEq | BinaryOperators::NotEq => {
-                        let operator = *op;
-                        self.advance();
-                        let right = self.parse_comparison()?;
-                        left = Expression::BinaryOp {
-                            left: Box::new(left),
-                            operator,
-                            right: Box::new(right),
-                        };
-                    }
-                    _ => break,
+        let mut fields = Vec::new();
+        let mut base = None;
+
+        while !self.check(|t| matches!(t, Tokens::RightBrace)) {
+            if self.check(|t| matches!(t, Tokens::DotDot)) {
+                let start = self.current_offset();
+                self.advance();
+                base = Some(Box::new(self.parse_delimited_expression()?));
+
+                // The base struct has to come last, but the fields after it can still be
+                // parsed to keep going
+                if self.check(|t| matches!(t, Tokens::Comma))
+                    && !self
+                        .peek_ahead(1)
+                        .is_some_and(|t| matches!(t.token_type, Tokens::RightBrace))
+                {
+                    self.errors.push(CarbideParserError::MisplacedStructBase(
+                        self.span_from(start),
+                    ));
+                    self.advance();
+                    continue;
                 }
+                break;
+            }
+
+            let field_checkpoint = self.checkpoint();
+            let start = self.current_offset();
+            let field = self.parse_identifier("a field name")?;
+            let value = if self.eat(|t| matches!(t, Tokens::Colon), ":") {
+                self.parse_delimited_expression()?
             } else {
+                // Shorthand, `Point { x }` is `Point { x: x }`
+                Expression::Identifier {
+                    name: field.clone(),
+                    span: self.span_from(start),
+                }
+            };
+            self.finish_node(field_checkpoint, SyntaxKind::FieldInit);
+
+            fields.push(FieldInit { name: field, value });
+
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
                 break;
             }
         }
 
-        Ok(left)
+        self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+        self.finish_node(checkpoint, SyntaxKind::StructLit);
+
+        Ok(Expression::StructLiteral { name, fields, base })
+    }
+}
+
+impl CarbideParser<'_> {
+    /// Attempt to parse the `::Name` segments following `first`, returning the
+    /// qualified name, like `Shape::Circle`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_qualified_name(&mut self, first: String) -> Result<String, Box<CarbideParserError>> {
+        let mut name = first;
+
+        while self.check(|t| matches!(t, Tokens::ColonColon))
+            && !self.peek_ahead(1).is_some_and(|t| {
+                matches!(t.token_type, Tokens::BinaryOperator(BinaryOperators::Lt))
+            })
+        {
+            self.advance();
+            name.push_str("::");
+            name.push_str(&self.parse_identifier("a name")?);
+        }
+
+        Ok(name)
     }
 
-    /// Attempt to parse a comparison
+    /// Attempt to parse the `<type, ...>` after the `::` of a generic function `name`, which
+    /// starts at `start`
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_comparison(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        self.parse_term()
+    fn parse_type_arguments(
+        &mut self,
+        name: String,
+        start: u64,
+        checkpoint: Checkpoint,
+    ) -> Result<Expression, Box<CarbideParserError>> {
+        self.expect(
+            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Lt)),
+            "<",
+        )?;
+        let arguments =
+            self.parse_type_list(|t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Gt)))?;
+        self.expect(
+            |t| matches!(t, Tokens::BinaryOperator(BinaryOperators::Gt)),
+            ">",
+        )?;
+        self.finish_node(checkpoint, SyntaxKind::GenericExpr);
+
+        Ok(Expression::Generic {
+            name,
+            arguments,
+            span: self.span_from(start),
+        })
     }
 
-    /// Attempt to parse a term
+    /// Attempt to parse an enum declaration, like
+    /// `enum Shape { Circle(float), Rect { w: float, h: float }, Empty }`
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_term(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        let mut left = self.parse_factor()?;
+    fn parse_enum_declaration(
+        &mut self,
+        visibility: Visibility,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Enum)), "enum")?;
+        let name = self.parse_identifier("an enum name")?;
+        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
 
-        while let Some(token) = self.peek() {
-            if let Tokens::BinaryOperator(op) = &token.token_type {
-                match op {
-                    BinaryOperators::Plus | BinaryOperators::Minus => {
-                        let operator = *op;
-                        self.advance();
-                        let right = self.parse_factor()?;
-                        left = Expression::BinaryOp {
-                            left: Box::new(left),
-                            operator,
-                            right: Box::new(right),
-                        };
-                    }
-                    _ => break,
-                }
+        let mut variants = Vec::new();
+        while !self.check(|t| matches!(t, Tokens::RightBrace)) {
+            let variant_checkpoint = self.checkpoint();
+            let variant = self.parse_identifier("a variant name")?;
+
+            let payload = if self.match_token(|t| matches!(t, Tokens::LeftParen)) {
+                let types = self.parse_type_list(|t| matches!(t, Tokens::RightParen))?;
+                self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+                VariantPayload::Tuple(types)
+            } else if self.check(|t| matches!(t, Tokens::LeftBrace)) {
+                VariantPayload::Struct(self.parse_struct_fields()?)
             } else {
+                VariantPayload::Unit
+            };
+            self.finish_node(variant_checkpoint, SyntaxKind::Variant);
+
+            variants.push(EnumVariant {
+                name: variant,
+                payload,
+            });
+
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
                 break;
             }
         }
 
-        Ok(left)
+        self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+        self.finish_node(checkpoint, SyntaxKind::EnumDecl);
+
+        Ok(Statement::EnumDeclaration {
+            visibility,
+            name,
+            variants,
+        })
     }
 
-    /// Attempt to parse a binary operator
+    /// Attempt to parse a trait declaration, like `trait Shape { fn area(self) -> float; }`
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_factor(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        let mut left = self.parse_unary()?;
+    fn parse_trait_declaration(
+        &mut self,
+        visibility: Visibility,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Trait)), "trait")?;
+        let name_start = self.current_offset();
+        let name = self.parse_identifier("a trait name")?;
+        let span = self.span_from(name_start);
+        let methods = self.parse_methods(false)?;
+        self.finish_node(checkpoint, SyntaxKind::TraitDecl);
+
+        Ok(Statement::TraitDeclaration {
+            visibility,
+            name,
+            methods,
+            span,
+        })
+    }
 
-        while let Some(token) = self.peek() {
-            if let Tokens::BinaryOperator(op) = &token.token_type {
-                match op {
-                    BinaryOperators::Star | BinaryOperators::Slash => {
-                        let operator = *op;
-                        self.advance();
-                        let right = self.parse_unary()?;
-                        left = Expression::BinaryOp {
-                            left: Box::new(left),
-                            operator,
-                            right: Box::new(right),
-                        };
-                    }
-                    _ => break,
-                }
+    /// Attempt to parse an impl block, like `impl Shape for Circle { ... }` or
+    /// `impl Circle { ... }`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_impl_declaration(
+        &mut self,
+        checkpoint: Checkpoint,
+    ) -> Result<Statement, Box<CarbideParserError>> {
+        let start = self.current_offset();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Impl)), "impl")?;
+        let name = self.parse_identifier("a type or trait name")?;
+        let (trait_name, target) =
+            if self.eat(|t| matches!(t, Tokens::Keyword(Keywords::For)), "for") {
+                (Some(name), self.parse_identifier("a type name")?)
             } else {
-                break;
-            }
-        }
-
-        Ok(left)
+                (None, name)
+            };
+        let span = self.span_from(start);
+        let methods = self.parse_methods(true)?;
+        self.finish_node(checkpoint, SyntaxKind::ImplDecl);
+
+        Ok(Statement::ImplDeclaration {
+            trait_name,
+            target,
+            methods,
+            span,
+        })
     }
 
-    /// Attempt to parse a unary operation
+    /// Attempt to parse the `{ fn ...; fn ... { ... } }` [`Methods`][`Method`] of a trait
+    /// or impl, where only trait methods can leave out their body
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_unary(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        if let Some(token) = self.peek()
-            && let Tokens::UnaryOperator(op) = &token.token_type
-        {
-            let operator = *op;
-            self.advance();
-            let expr = self.parse_unary()?;
-            return Ok(Expression::UnaryOp {
-                operator,
-                operand: Box::new(expr),
-            });
+    fn parse_methods(
+        &mut self,
+        body_required: bool,
+    ) -> Result<Vec<Method>, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
+
+        let mut methods = Vec::new();
+        while !self.eat(|t| matches!(t, Tokens::RightBrace), "}") {
+            methods.push(self.parse_method(body_required)?);
         }
 
-        self.parse_call()
+        Ok(methods)
     }
-}
 
-impl CarbideParser<'_> {
-    /// Attempt to parse a function declaration
+    /// Attempt to parse a [`Method`], like `fn area(self) -> float { ... }`, or
+    /// `fn area(self) -> float;` unless `body_required` is set
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_function_declaration(&mut self) -> Result<Statement, Box<CarbideParserError>> {
+    fn parse_method(&mut self, body_required: bool) -> Result<Method, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
         self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Fn)), "fn")?;
-
-        let name_token = self.expect(|t| matches!(t, Tokens::Identifier(_)), "identifier")?;
-
-        let name = if let Tokens::Identifier(n) = &name_token.token_type {
-            (*n).to_string()
-        } else {
-            return Err(Box::new(CarbideParserError::ExpectedIdentifier(unsafe {
-                std::mem::transmute::<Token<'_>, Token<'_>>(name_token.clone())
-            })));
-        };
+        let name_start = self.current_offset();
+        let name = self.parse_identifier("a method name")?;
+        let span = self.span_from(name_start);
 
         self.expect(|t| matches!(t, Tokens::LeftParen), "(")?;
-
-        let mut parameters = Vec::new();
-        if !self.check(|t| matches!(t, Tokens::RightParen)) {
-            loop {
-                let param_token =
-                    self.expect(|t| matches!(t, Tokens::Identifier(_)), "parameter name")?;
-
-                let param_name = if let Tokens::Identifier(param) = &param_token.token_type {
-                    (*param).to_string()
-                } else {
-                    return Err(Box::new(CarbideParserError::ExpectedIdentifier(unsafe {
-                        std::mem::transmute::<Token<'_>, Token<'_>>(param_token.clone())
-                    })));
-                };
-
-                // Parse optional type annotation for parameter
-                let type_annotation = if self.match_token(|t| matches!(t, Tokens::Colon)) {
-                    Some(self.parse_type()?)
-                } else {
-                    None
-                };
-
-                parameters.push(Parameter {
-                    name: param_name,
-                    type_annotation,
-                });
-
-                if !self.match_token(|t| matches!(t, Tokens::Comma)) {
-                    break;
-                }
-            }
-        }
-
+        let parameters = self.parse_parameters(|t| matches!(t, Tokens::RightParen))?;
         self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
 
-        // Parse optional return type annotation
-        let return_type = if self.match_token(|t| matches!(t, Tokens::ThinArrow)) {
+        let return_type = if self.eat(|t| matches!(t, Tokens::ThinArrow), "->") {
             Some(self.parse_type()?)
         } else {
             None
         };
 
-        let body = if self.check(|t| matches!(t, Tokens::LeftBrace)) {
-            if let Statement::Block(stmts) = self.parse_block_statement()? {
-                stmts
-            } else {
-                Vec::new()
-            }
+        let body = if !body_required && self.eat(|t| matches!(t, Tokens::Semicolon), ";") {
+            None
+        } else if self.check(|t| matches!(t, Tokens::LeftBrace)) {
+            Some(self.in_function_scope(Self::parse_block)?)
         } else {
-            return Err(Box::new(CarbideParserError::UnexpectedToken {
-                expected: "function body".to_string(),
-                found: unsafe {
-                    std::mem::transmute::<Token<'_>, Token<'_>>(
-                        self.peek().unwrap_unchecked().clone(),
-                    )
-                },
-            }));
+            return Err(Box::new(self.unexpected([Expected::Token("{")])));
         };
+        self.finish_node(checkpoint, SyntaxKind::Method);
 
-        Ok(Statement::FunctionDeclaration {
+        Ok(Method {
             name,
             parameters,
             return_type,
             body,
+            span,
         })
     }
 
-    /// Attempt to parse a function call
+    /// Attempt to parse a match [`Expression`], like `match value { pattern => expr, ... }`
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_call(&mut self) -> Result<Expression, Box<CarbideParserError>> {
-        let mut expr = self.parse_primary()?;
+    fn parse_match(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+        let start = self.current_offset();
+        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Match)), "match")?;
+        let scrutinee = self.parse_condition()?;
+        let span = self.span_from(start);
 
-        loop {
-            if self.match_token(|t| matches!(t, Tokens::LeftParen)) {
-                expr = self.finish_call(expr)?;
-            } else if self.match_token(|t| matches!(t, Tokens::LeftBracket)) {
-                let index = self.parse_expression()?;
-                self.expect(|t| matches!(t, Tokens::RightBracket), "]")?;
-                expr = Expression::Index {
-                    target: Box::new(expr),
-                    index: Box::new(index),
-                };
-            } else if self.match_token(|t| matches!(t, Tokens::Period)) {
-                let member_token =
-                    self.expect(|t| matches!(t, Tokens::Identifier(_)), "property name")?;
-
-                if let Tokens::Identifier(name) = &member_token.token_type {
-                    expr = Expression::MemberAccess {
-                        target: Box::new(expr),
-                        member: (*name).to_string(),
-                    };
-                }
+        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
+
+        let mut arms = Vec::new();
+        while !self.check(|t| matches!(t, Tokens::RightBrace)) {
+            let arm_checkpoint = self.checkpoint();
+            let pattern_start = self.current_offset();
+            let pattern = self.parse_pattern()?;
+            let pattern_span = self.span_from(pattern_start);
+
+            let guard = if self.match_token(|t| matches!(t, Tokens::Keyword(Keywords::If))) {
+                Some(self.parse_delimited_expression()?)
             } else {
-                break;
-            }
-        }
+                None
+            };
 
-        Ok(expr)
-    }
-}
+            self.expect(|t| matches!(t, Tokens::FatArrow), "=>")?;
+            let body = self.parse_delimited_expression()?;
+            self.finish_node(arm_checkpoint, SyntaxKind::MatchArm);
 
-impl CarbideParser<'_> {
-    /// Attempt to parse a function call
-    ///
-    /// # Errors
-    /// Returns `Err` if parsing the tokens fail
-    fn finish_call(&mut self, callee: Expression) -> Result<Expression, Box<CarbideParserError>> {
-        let mut arguments = Vec::new();
+            arms.push(MatchArm {
+                pattern,
+                guard,
+                body,
+                span: pattern_span,
+            });
 
-        if !self.check(|t| matches!(t, Tokens::RightParen)) {
-            loop {
-                arguments.push(self.parse_expression()?);
-                if !self.match_token(|t| matches!(t, Tokens::Comma)) {
-                    break;
-                }
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
             }
         }
 
-        self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+        self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+        self.finish_node(checkpoint, SyntaxKind::MatchExpr);
 
-        Ok(Expression::Call {
-            callee: Box::new(callee),
-            arguments,
+        Ok(Expression::Match {
+            scrutinee: Box::new(scrutinee),
+            arms,
+            span,
         })
     }
+}
 
-    /// Attempt to parse a primary [`Expression`]
+impl CarbideParser<'_> {
+    /// Attempt to parse a [`Pattern`]
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_primary(&mut self) -> Result<Expression, Box<CarbideParserError>> {
+    fn parse_pattern(&mut self) -> Result<Pattern, Box<CarbideParserError>> {
+        let checkpoint = self.checkpoint();
+
         if let Some(token) = self.peek() {
             match &token.token_type {
-                Tokens::FloatLiteral(val) => {
+                Tokens::IntLiteral(val) | Tokens::HexLiteral(val) | Tokens::BinaryLiteral(val) => {
                     let value = *val;
                     self.advance();
-                    Ok(Expression::Literal(LiteralValue::Float(value)))
-                }
-                Tokens::StringLiteral(s) => {
-                    let value = s.clone();
-                    self.advance();
-                    Ok(Expression::Literal(LiteralValue::String(value)))
+                    self.finish_node(checkpoint, SyntaxKind::LiteralPat);
+                    Ok(Pattern::Literal(LiteralValue::Int(value)))
                 }
-                Tokens::IntLiteral(val) | Tokens::HexLiteral(val) | Tokens::BinaryLiteral(val) => {
+                Tokens::FloatLiteral(val) => {
                     let value = *val;
                     self.advance();
-                    Ok(Expression::Literal(LiteralValue::Int(value)))
+                    self.finish_node(checkpoint, SyntaxKind::LiteralPat);
+                    Ok(Pattern::Literal(LiteralValue::Float(value)))
                 }
-                Tokens::InterpolatedString(parts) => {
-                    let string_parts = self.parse_interpolated_string(parts)?;
+                Tokens::StringLiteral(s) => {
+                    let value = s.clone();
                     self.advance();
-                    Ok(Expression::InterpolatedString {
-                        parts: string_parts,
-                    })
+                    self.finish_node(checkpoint, SyntaxKind::LiteralPat);
+                    Ok(Pattern::Literal(LiteralValue::String(value)))
                 }
                 Tokens::Identifier(name) => {
                     let ident = (*name).to_string();
+                    let start = token.span.start;
                     self.advance();
 
-                    if ident == "true" || ident == "false" {
-                        return Ok(Expression::Literal(LiteralValue::Bool(ident == "true")));
+                    match ident.as_str() {
+                        "_" => {
+                            self.finish_node(checkpoint, SyntaxKind::WildcardPat);
+                            return Ok(Pattern::Wildcard);
+                        }
+                        "true" | "false" => {
+                            self.finish_node(checkpoint, SyntaxKind::LiteralPat);
+                            return Ok(Pattern::Literal(LiteralValue::Bool(ident == "true")));
+                        }
+                        _ => {}
                     }
 
-                    Ok(Expression::Identifier(ident))
+                    let qualified = self.check(|t| matches!(t, Tokens::ColonColon));
+                    let name = self.parse_qualified_name(ident)?;
+
+                    if self.match_token(|t| matches!(t, Tokens::LeftParen)) {
+                        let elements =
+                            self.parse_pattern_list(|t| matches!(t, Tokens::RightParen))?;
+                        self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
+                        self.finish_node(checkpoint, SyntaxKind::TupleStructPat);
+                        Ok(Pattern::TupleStruct { name, elements })
+                    } else if self.check(|t| matches!(t, Tokens::LeftBrace)) {
+                        self.parse_struct_pattern(name, checkpoint)
+                    } else if qualified {
+                        self.finish_node(checkpoint, SyntaxKind::PathPat);
+                        Ok(Pattern::Path(name))
+                    } else {
+                        self.finish_node(checkpoint, SyntaxKind::BindingPat);
+                        Ok(Pattern::Binding {
+                            name,
+                            span: self.span_from(start),
+                        })
+                    }
                 }
                 Tokens::LeftParen => {
                     self.advance();
-                    let expr = self.parse_expression()?;
+                    let mut elements =
+                        self.parse_pattern_list(|t| matches!(t, Tokens::RightParen))?;
+                    let trailing_comma = self
+                        .last()
+                        .is_some_and(|t| matches!(t.token_type, Tokens::Comma));
                     self.expect(|t| matches!(t, Tokens::RightParen), ")")?;
-                    Ok(Expression::Grouped(Box::new(expr)))
+
+                    if elements.len() == 1 && !trailing_comma {
+                        self.finish_node(checkpoint, SyntaxKind::ParenPat);
+                        Ok(elements.remove(0))
+                    } else {
+                        self.finish_node(checkpoint, SyntaxKind::TuplePat);
+                        Ok(Pattern::Tuple(elements))
+                    }
                 }
                 Tokens::LeftBracket => {
                     self.advance();
-                    let mut elements = Vec::new();
-
-                    if !self.check(|t| matches!(t, Tokens::RightBracket)) {
-                        loop {
-                            elements.push(self.parse_expression()?);
-                            if !self.match_token(|t| matches!(t, Tokens::Comma)) {
-                                break;
-                            }
-                        }
-                    }
-
+                    let elements = self.parse_array_pattern_elements()?;
                     self.expect(|t| matches!(t, Tokens::RightBracket), "]")?;
-                    Ok(Expression::Array(elements))
+                    self.finish_node(checkpoint, SyntaxKind::ArrayPat);
+                    Ok(Pattern::Array(elements))
                 }
-                _ => Err(Box::new(CarbideParserError::UnexpectedToken {
-                    expected: "expression".to_string(),
-                    found: unsafe { std::mem::transmute::<Token<'_>, Token<'_>>(token.clone()) },
-                })),
+                _ => Err(Box::new(self.unexpected([Expected::Kind("a pattern")]))),
             }
         } else {
-            Err(Box::new(CarbideParserError::UnexpectedEOF(
-                self.current_location(),
-            )))
+            Err(Box::new(self.unexpected([Expected::Kind("a pattern")])))
         }
     }
 
-    /// Attempt to parse an interpolated string
+    /// Attempt to parse a comma separated list of [`Patterns`][`Pattern`], stopping before `close`
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    fn parse_interpolated_string(
-        &self,
-        parts: &[carbide_lexer::tokens::StringPart],
-    ) -> Result<Vec<StringPart>, Box<CarbideParserError>> {
-        let mut result = Vec::new();
+    fn parse_pattern_list(
+        &mut self,
+        close: impl Fn(&Tokens) -> bool,
+    ) -> Result<Vec<Pattern>, Box<CarbideParserError>> {
+        let mut patterns = Vec::new();
 
-        for part in parts {
-            match part {
-                carbide_lexer::tokens::StringPart::Text(text) => {
-                    result.push(StringPart::Text(text.clone()));
-                }
-                carbide_lexer::tokens::StringPart::Interpolation(code) => {
-                    let mut lexer = carbide_lexer::lexer::CarbideLexer::from_src(code);
-                    let tokens = lexer.lex_strict().map_err(|_| {
-                        CarbideParserError::ExpectedExpression(self.current_location())
-                    })?;
+        while !self.check(&close) {
+            patterns.push(self.nested(Self::parse_pattern)?);
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
+            }
+        }
 
-                    let mut mini_parser = CarbideParser::new(tokens);
-                    let expr = mini_parser.parse_expression()?;
-                    result.push(StringPart::Expression(Box::new(expr)));
+        Ok(patterns)
+    }
+
+    /// Attempt to parse the elements of an array [`Pattern`], where one of them can be a
+    /// [rest][`Pattern::Rest`] like `..` or `..name`
+    ///
+    /// # Errors
+    /// Returns `Err` if parsing the tokens fail
+    fn parse_array_pattern_elements(&mut self) -> Result<Vec<Pattern>, Box<CarbideParserError>> {
+        let mut elements = Vec::new();
+        let mut has_rest = false;
+
+        while !self.check(|t| matches!(t, Tokens::RightBracket)) {
+            if let Some(token) = self.peek()
+                && matches!(token.token_type, Tokens::DotDot)
+            {
+                if has_rest {
+                    self.errors
+                        .push(CarbideParserError::MultipleRestPatterns(token.start));
                 }
+                has_rest = true;
+
+                let checkpoint = self.checkpoint();
+                let start = self.current_offset();
+                self.advance();
+                let mut span = self.span_from(start);
+                let name = if self.check(|t| matches!(t, Tokens::Identifier(_))) {
+                    let start = self.current_offset();
+                    let name = self.parse_identifier("a rest binding")?;
+                    span = self.span_from(start);
+                    Some(name)
+                } else {
+                    None
+                };
+                self.finish_node(checkpoint, SyntaxKind::RestPat);
+                elements.push(Pattern::Rest { name, span });
+            } else {
+                elements.push(self.nested(Self::parse_pattern)?);
+            }
+
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
             }
         }
 
-        Ok(result)
+        Ok(elements)
     }
-}
 
-impl CarbideParser<'_> {
-    /// Attempt to parse a return statement
+    /// Attempt to parse the `{ field: pattern, shorthand, .. }` of a struct [`Pattern`]
+    ///
+    /// `checkpoint` is where the pattern, including its name, starts
     ///
     /// # Errors
     /// Returns `Err` if parsing the tokens fail
-    pub fn parse_return(&mut self) -> Result<Statement, Box<CarbideParserError>> {
-        self.expect(|t| matches!(t, Tokens::Keyword(Keywords::Return)), "return")?;
+    fn parse_struct_pattern(
+        &mut self,
+        name: String,
+        checkpoint: Checkpoint,
+    ) -> Result<Pattern, Box<CarbideParserError>> {
+        self.expect(|t| matches!(t, Tokens::LeftBrace), "{")?;
 
-        let return_expr = self.parse_expression()?;
-        self.expect(|t| matches!(t, Tokens::Semicolon), ";")?;
+        let mut fields = Vec::new();
+        let mut rest = false;
+
+        while !self.check(|t| matches!(t, Tokens::RightBrace)) {
+            if self.match_token(|t| matches!(t, Tokens::DotDot)) {
+                rest = true;
+                break;
+            }
+
+            let field_checkpoint = self.checkpoint();
+            let start = self.current_offset();
+            let field = self.parse_identifier("a field name")?;
+            let pattern = if self.eat(|t| matches!(t, Tokens::Colon), ":") {
+                self.nested(Self::parse_pattern)?
+            } else {
+                // Shorthand, `Point { x }` binds `x`
+                Pattern::Binding {
+                    name: field.clone(),
+                    span: self.span_from(start),
+                }
+            };
+            self.finish_node(field_checkpoint, SyntaxKind::FieldPat);
+
+            fields.push(FieldPattern {
+                name: field,
+                pattern,
+            });
+
+            if !self.eat(|t| matches!(t, Tokens::Comma), ",") {
+                break;
+            }
+        }
+
+        self.expect(|t| matches!(t, Tokens::RightBrace), "}")?;
+        self.finish_node(checkpoint, SyntaxKind::StructPat);
 
-        Ok(Statement::Return(Some(return_expr)))
+        Ok(Pattern::Struct { name, fields, rest })
     }
 }
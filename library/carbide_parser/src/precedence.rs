@@ -0,0 +1,119 @@
+use carbide_lexer::operators::{BinaryOperators, UnaryOperators};
+
+/// How tightly an operator binds its operands, from loosest to tightest
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Precedence {
+    Lowest,
+    /// `=`
+    Assignment,
+    /// `||`
+    Or,
+    /// `&&`
+    And,
+    /// `==`, `!=`
+    Equality,
+    /// `<`, `>`, `<=`, `>=`
+    Comparison,
+    /// `+`, `-`
+    Term,
+    /// `*`, `/`, `%`
+    Factor,
+    /// Prefix operators, like `!`
+    Prefix,
+    /// `**`
+    Power,
+    /// Postfix operators, calls, indexing and member access
+    Postfix,
+}
+
+/// Which side an operator groups towards when chained, like `a = b = c`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
+/// Where a unary operator sits relative to its operand
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Fixity {
+    Prefix,
+    Postfix,
+}
+
+impl Precedence {
+    /// Get the binding power of this [`Precedence`] level
+    #[must_use]
+    pub fn binding_power(self) -> u8 {
+        // Leave a gap between levels so that associativity can be expressed as +1
+        self as u8 * 2
+    }
+}
+
+/// Operator information used by the Pratt expression parser
+pub trait Operator {
+    /// Get the [`Precedence`] of this operator
+    fn precedence(&self) -> Precedence;
+
+    /// Get the [`Associativity`] of this operator
+    fn associativity(&self) -> Associativity {
+        Associativity::Left
+    }
+
+    /// Get the `(left, right)` binding powers of this operator
+    ///
+    /// An operator keeps parsing to its right as long as the next operator's left binding
+    /// power is at least its own right binding power
+    fn binding_power(&self) -> (u8, u8) {
+        let power = self.precedence().binding_power();
+
+        match self.associativity() {
+            Associativity::Left => (power, power + 1),
+            Associativity::Right => (power + 1, power),
+        }
+    }
+}
+
+impl Operator for BinaryOperators {
+    fn precedence(&self) -> Precedence {
+        match self {
+            Self::Eq => Precedence::Assignment,
+            Self::OrOr => Precedence::Or,
+            Self::AndAnd => Precedence::And,
+            Self::EqEq | Self::NotEq => Precedence::Equality,
+            Self::Lt | Self::Gt | Self::LtEq | Self::GtEq => Precedence::Comparison,
+            Self::Plus | Self::Minus => Precedence::Term,
+            Self::Star | Self::Slash | Self::Percent => Precedence::Factor,
+            Self::StarStar => Precedence::Power,
+        }
+    }
+
+    fn associativity(&self) -> Associativity {
+        match self {
+            Self::Eq | Self::StarStar => Associativity::Right,
+            _ => Associativity::Left,
+        }
+    }
+}
+
+impl Operator for UnaryOperators {
+    fn precedence(&self) -> Precedence {
+        match self.fixity() {
+            Fixity::Prefix => Precedence::Prefix,
+            Fixity::Postfix => Precedence::Postfix,
+        }
+    }
+}
+
+/// Extension for unary operators that can appear on either side of their operand
+pub trait UnaryFixity {
+    /// Get the [`Fixity`] of this operator
+    fn fixity(&self) -> Fixity;
+}
+
+impl UnaryFixity for UnaryOperators {
+    fn fixity(&self) -> Fixity {
+        match self {
+            Self::Not => Fixity::Prefix,
+        }
+    }
+}
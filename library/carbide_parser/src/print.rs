@@ -0,0 +1,991 @@
+//! Pretty-printer from the AST back to Carbide source
+//!
+//! Parsing the output gives back the same AST, apart from spans. Parentheses are only added
+//! where precedence or the grammar needs them, while [`Expression::Grouped`] keeps its own
+//!
+//! Carbide has no negative number literals, and no way to write NaN or infinite floats, so
+//! ASTs holding such literals can't be printed and give a [`PrintError`] instead. Floats have
+//! no exponent syntax either, so they're always printed in full, like `1e300` as `1` followed
+//! by 300 zeros and `.0`
+
+use std::fmt::Write;
+
+use carbide_lexer::operators::{BinaryOperators, UnaryOperators};
+use thiserror::Error;
+
+use crate::nodes::{
+    Attribute, Block, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody, LiteralValue,
+    MapEntry, MatchArm, Method, Parameter, Pattern, Statement, StringPart, StructField, Type,
+    TypeParameter, VariantPayload, Visibility,
+};
+use crate::precedence::{Fixity, Operator, Precedence, UnaryFixity};
+
+const INDENT: &str = "    ";
+
+/// Why an AST couldn't be printed as Carbide source
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum PrintError {
+    /// A negative number, or a NaN or infinite float, which no literal can spell
+    #[error("{0:?} can't be written as a Carbide literal")]
+    UnwritableLiteral(LiteralValue),
+}
+
+/// Print `statements` as Carbide source, one per line
+///
+/// # Errors
+/// Returns `Err` if a literal in `statements` can't be written in Carbide
+pub fn print_program(statements: &[Statement]) -> Result<String, PrintError> {
+    let mut printer = Printer::default();
+    for statement in statements {
+        printer.statement(statement, false);
+        printer.out.push('\n');
+    }
+    printer.finish()
+}
+
+/// Print `statement` as Carbide source
+///
+/// # Errors
+/// Returns `Err` if a literal in `statement` can't be written in Carbide
+pub fn print_statement(statement: &Statement) -> Result<String, PrintError> {
+    let mut printer = Printer::default();
+    printer.statement(statement, false);
+    printer.finish()
+}
+
+/// Print `expression` as Carbide source
+///
+/// # Errors
+/// Returns `Err` if a literal in `expression` can't be written in Carbide
+pub fn print_expression(expression: &Expression) -> Result<String, PrintError> {
+    let mut printer = Printer::default();
+    printer.expression(expression, Context::default());
+    printer.finish()
+}
+
+/// Print `ty` as Carbide source, which always works since types hold no literals
+#[must_use]
+pub fn print_type(ty: &Type) -> String {
+    let mut printer = Printer::default();
+    printer.ty(ty);
+    printer.out
+}
+
+/// Print `pattern` as Carbide source
+///
+/// # Errors
+/// Returns `Err` if a literal in `pattern` can't be written in Carbide
+pub fn print_pattern(pattern: &Pattern) -> Result<String, PrintError> {
+    let mut printer = Printer::default();
+    printer.pattern(pattern);
+    printer.finish()
+}
+
+/// Where an expression is printed, which decides the parentheses it needs
+#[derive(Debug, Clone, Copy, Default)]
+struct Context {
+    /// Whether a `{` would end the expression, like in `while` conditions, so struct
+    /// literals, maps and blocks need parentheses
+    no_struct: bool,
+    /// What the expression can't start with
+    start: Start,
+    /// Whether an operator or postfix can follow the expression, which the body of a
+    /// lambda would take
+    trailing: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum Start {
+    #[default]
+    Any,
+    /// A `{` would start a block instead, like after the `=>` of a lambda
+    NoBrace,
+    /// A `{`, `if` or `match` would be parsed on its own, like at the start of a statement
+    NoBlockLike,
+}
+
+impl Context {
+    /// Context for a condition, like in `if` and `while`
+    const CONDITION: Self = Self {
+        no_struct: true,
+        start: Start::Any,
+        trailing: false,
+    };
+
+    /// Context for the start of an expression statement or block tail
+    fn statement(expression: &Expression) -> Self {
+        // A whole `if`, `match` or block is fine, it's only a problem when followed by
+        // an operator
+        let start = if matches!(
+            expression,
+            Expression::If { .. } | Expression::Match { .. } | Expression::Block(_)
+        ) {
+            Start::Any
+        } else {
+            Start::NoBlockLike
+        };
+
+        Self {
+            start,
+            ..Self::default()
+        }
+    }
+
+    /// Context for an operand that's followed by an operator or postfix
+    fn left(self) -> Self {
+        Self {
+            trailing: true,
+            ..self
+        }
+    }
+
+    /// Context for the operands after the first one, which can start with anything
+    fn rest(self) -> Self {
+        Self {
+            start: Start::Any,
+            ..self
+        }
+    }
+
+    fn needs_parens(self, expression: &Expression) -> bool {
+        match expression {
+            Expression::StructLiteral { .. } => self.no_struct,
+            Expression::Map(_) | Expression::Block(_) => self.no_struct || self.start != Start::Any,
+            Expression::If { .. } | Expression::Match { .. } => self.start == Start::NoBlockLike,
+            Expression::Lambda { .. } => self.trailing,
+            _ => false,
+        }
+    }
+}
+
+/// Get the `(left, right)` binding powers of `expression` as an operand, where `u8::MAX`
+/// means nothing can bind into it from that side
+fn binding_power(expression: &Expression) -> (u8, u8) {
+    match expression {
+        Expression::BinaryOp { operator, .. } => operator.binding_power(),
+        Expression::Assignment { .. } => BinaryOperators::Eq.binding_power(),
+        Expression::UnaryOp { operator, .. } => match operator.fixity() {
+            Fixity::Prefix => (u8::MAX, operator.binding_power().1),
+            Fixity::Postfix => (operator.binding_power().0, u8::MAX),
+        },
+        _ => (u8::MAX, u8::MAX),
+    }
+}
+
+/// Check if `operand` needs parentheses before an operator with a left binding power of
+/// `power`
+fn left_needs_parens(operand: &Expression, power: u8) -> bool {
+    binding_power(operand).1 <= power
+}
+
+/// Check if `operand` needs parentheses after an operator with a right binding power of
+/// `power`
+fn right_needs_parens(operand: &Expression, power: u8) -> bool {
+    binding_power(operand).0 < power
+}
+
+#[derive(Debug, Default)]
+struct Printer {
+    out: String,
+    indent: usize,
+    /// The first literal that couldn't be written, which fails the whole print
+    error: Option<PrintError>,
+}
+
+impl Printer {
+    fn finish(self) -> Result<String, PrintError> {
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(self.out),
+        }
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    /// Print `items` separated by `, `
+    fn comma_separated<T>(&mut self, items: &[T], mut print: impl FnMut(&mut Self, &T)) {
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                self.out.push_str(", ");
+            }
+            print(self, item);
+        }
+    }
+
+    /// Print each attribute on its own line before a declaration
+    fn attributes(&mut self, attributes: &[Attribute]) {
+        for attribute in attributes {
+            let _ = write!(self.out, "#[{}", attribute.name);
+            if !attribute.arguments.is_empty() {
+                self.out.push('(');
+                self.comma_separated(&attribute.arguments, Self::literal);
+                self.out.push(')');
+            }
+            self.out.push(']');
+            self.newline();
+        }
+    }
+
+    fn visibility(&mut self, visibility: Visibility) {
+        if visibility == Visibility::Public {
+            self.out.push_str("pub ");
+        }
+    }
+
+    /// Print `statement`, where `last_in_block` is whether it ends a `{ ... }` that has no
+    /// tail, so a block-like expression would become the tail without a `;`
+    #[allow(clippy::too_many_lines)]
+    fn statement(&mut self, statement: &Statement, last_in_block: bool) {
+        match statement {
+            Statement::LetDeclaration {
+                attributes,
+                mutable,
+                pattern,
+                type_annotation,
+                initializer,
+                ..
+            } => {
+                self.attributes(attributes);
+                self.out.push_str("let ");
+                if *mutable {
+                    self.out.push_str("mut ");
+                }
+                self.pattern(pattern);
+                if let Some(ty) = type_annotation {
+                    self.out.push_str(": ");
+                    self.ty(ty);
+                }
+                if let Some(initializer) = initializer {
+                    self.out.push_str(" = ");
+                    self.expression(initializer, Context::default());
+                }
+                self.out.push(';');
+            }
+            Statement::ConstDeclaration {
+                visibility,
+                name,
+                type_annotation,
+                value,
+                ..
+            } => {
+                self.visibility(*visibility);
+                let _ = write!(self.out, "const {name}: ");
+                self.ty(type_annotation);
+                self.out.push_str(" = ");
+                self.expression(value, Context::default());
+                self.out.push(';');
+            }
+            Statement::FunctionDeclaration {
+                attributes,
+                visibility,
+                name,
+                type_parameters,
+                parameters,
+                return_type,
+                body,
+            } => {
+                self.attributes(attributes);
+                self.visibility(*visibility);
+                let _ = write!(self.out, "fn {name}");
+                if !type_parameters.is_empty() {
+                    self.out.push('<');
+                    self.comma_separated(type_parameters, Self::type_parameter);
+                    self.out.push('>');
+                }
+                self.out.push('(');
+                self.comma_separated(parameters, Self::parameter);
+                self.out.push(')');
+                if let Some(ty) = return_type {
+                    self.out.push_str(" -> ");
+                    self.ty(ty);
+                }
+                self.out.push(' ');
+                self.block(&body.statements, body.tail.as_deref());
+            }
+            Statement::StructDeclaration {
+                visibility,
+                name,
+                fields,
+            } => {
+                self.visibility(*visibility);
+                let _ = write!(self.out, "struct {name} ");
+                self.struct_fields(fields);
+            }
+            Statement::EnumDeclaration {
+                visibility,
+                name,
+                variants,
+            } => {
+                self.visibility(*visibility);
+                let _ = write!(self.out, "enum {name} ");
+                self.lines(variants, Self::enum_variant);
+            }
+            Statement::TraitDeclaration {
+                visibility,
+                name,
+                methods,
+                ..
+            } => {
+                self.visibility(*visibility);
+                let _ = write!(self.out, "trait {name} ");
+                self.methods(methods);
+            }
+            Statement::ImplDeclaration {
+                trait_name,
+                target,
+                methods,
+                ..
+            } => {
+                self.out.push_str("impl ");
+                if let Some(trait_name) = trait_name {
+                    let _ = write!(self.out, "{trait_name} for ");
+                }
+                let _ = write!(self.out, "{target} ");
+                self.methods(methods);
+            }
+            Statement::Import { path, items, .. } => {
+                let _ = write!(self.out, "import {}", path.join("."));
+                if let Some(items) = items {
+                    let _ = write!(self.out, ".{{{}}}", items.join(", "));
+                }
+                self.out.push(';');
+            }
+            Statement::Return(value) => {
+                self.out.push_str("return");
+                if let Some(value) = value {
+                    self.out.push(' ');
+                    self.expression(value, Context::default());
+                }
+                self.out.push(';');
+            }
+            Statement::While { condition, body } => {
+                self.out.push_str("while ");
+                self.expression(condition, Context::CONDITION);
+                self.out.push(' ');
+                self.block(body, None);
+            }
+            Statement::For {
+                initializer,
+                condition,
+                increment,
+                body,
+            } => {
+                self.out.push_str("for (");
+                match initializer {
+                    Some(initializer) => self.statement(initializer, true),
+                    None => self.out.push(';'),
+                }
+                if let Some(condition) = condition {
+                    self.out.push(' ');
+                    self.expression(condition, Context::default());
+                }
+                self.out.push(';');
+                if let Some(increment) = increment {
+                    self.out.push(' ');
+                    self.expression(increment, Context::default());
+                }
+                self.out.push_str(") ");
+                self.block(body, None);
+            }
+            Statement::Block(body) => self.block(body, None),
+            Statement::Expression(expression) => {
+                self.expression(expression, Context::statement(expression));
+                if !expression.is_block_like() || last_in_block {
+                    self.out.push(';');
+                }
+            }
+            Statement::Break => self.out.push_str("break;"),
+            Statement::Continue => self.out.push_str("continue;"),
+            Statement::Error(_) => self.out.push_str("/* error */"),
+        }
+    }
+
+    /// Print a `{ ... }` block of `statements`, followed by `tail`
+    fn block(&mut self, statements: &[Statement], tail: Option<&Expression>) {
+        if statements.is_empty() && tail.is_none() {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push('{');
+        self.indent += 1;
+        for (i, statement) in statements.iter().enumerate() {
+            self.newline();
+            self.statement(statement, tail.is_none() && i + 1 == statements.len());
+        }
+        if let Some(tail) = tail {
+            self.newline();
+            self.expression(tail, Context::statement(tail));
+        }
+        self.indent -= 1;
+        self.newline();
+        self.out.push('}');
+    }
+
+    /// Print `items` in braces, one per line with a trailing comma
+    fn lines<T>(&mut self, items: &[T], mut print: impl FnMut(&mut Self, &T)) {
+        if items.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push('{');
+        self.indent += 1;
+        for item in items {
+            self.newline();
+            print(self, item);
+            self.out.push(',');
+        }
+        self.indent -= 1;
+        self.newline();
+        self.out.push('}');
+    }
+
+    /// Print the methods of a trait or impl in braces, one per line
+    fn methods(&mut self, methods: &[Method]) {
+        if methods.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push('{');
+        self.indent += 1;
+        for method in methods {
+            self.newline();
+            let _ = write!(self.out, "fn {}(", method.name);
+            self.comma_separated(&method.parameters, Self::parameter);
+            self.out.push(')');
+            if let Some(ty) = &method.return_type {
+                self.out.push_str(" -> ");
+                self.ty(ty);
+            }
+            match &method.body {
+                Some(body) => {
+                    self.out.push(' ');
+                    self.block(&body.statements, body.tail.as_deref());
+                }
+                None => self.out.push(';'),
+            }
+        }
+        self.indent -= 1;
+        self.newline();
+        self.out.push('}');
+    }
+
+    fn struct_fields(&mut self, fields: &[StructField]) {
+        self.lines(fields, |printer, field| {
+            let _ = write!(printer.out, "{}: ", field.name);
+            printer.ty(&field.type_annotation);
+        });
+    }
+
+    fn enum_variant(&mut self, variant: &EnumVariant) {
+        self.out.push_str(&variant.name);
+        match &variant.payload {
+            VariantPayload::Unit => {}
+            VariantPayload::Tuple(types) => {
+                self.out.push('(');
+                self.comma_separated(types, Self::ty);
+                self.out.push(')');
+            }
+            VariantPayload::Struct(fields) => {
+                self.out.push(' ');
+                self.struct_fields(fields);
+            }
+        }
+    }
+
+    fn parameter(&mut self, parameter: &Parameter) {
+        if parameter.mutable {
+            self.out.push_str("mut ");
+        }
+        self.pattern(&parameter.pattern);
+        if let Some(ty) = &parameter.type_annotation {
+            self.out.push_str(": ");
+            self.ty(ty);
+        }
+    }
+
+    fn type_parameter(&mut self, type_parameter: &TypeParameter) {
+        self.out.push_str(&type_parameter.name);
+        if !type_parameter.bounds.is_empty() {
+            let _ = write!(self.out, ": {}", type_parameter.bounds.join(" + "));
+        }
+    }
+
+    fn expression(&mut self, expression: &Expression, context: Context) {
+        if context.needs_parens(expression) {
+            self.parenthesized(expression);
+        } else {
+            self.unparenthesized(expression, context);
+        }
+    }
+
+    fn parenthesized(&mut self, expression: &Expression) {
+        self.out.push('(');
+        self.expression(expression, Context::default());
+        self.out.push(')');
+    }
+
+    /// Print `operand`, in parentheses if `parens` is set
+    fn operand(&mut self, operand: &Expression, parens: bool, context: Context) {
+        if parens {
+            self.parenthesized(operand);
+        } else {
+            self.expression(operand, context);
+        }
+    }
+
+    fn unparenthesized(&mut self, expression: &Expression, context: Context) {
+        match expression {
+            Expression::Literal(literal) => self.literal(literal),
+            Expression::Identifier { name, .. } => self.out.push_str(name),
+            Expression::Generic {
+                name, arguments, ..
+            } => {
+                let _ = write!(self.out, "{name}::<");
+                self.comma_separated(arguments, Self::ty);
+                self.out.push('>');
+            }
+            Expression::BinaryOp {
+                left,
+                operator,
+                right,
+            } => self.binary(
+                left,
+                operator.as_str(),
+                right,
+                operator.binding_power(),
+                context,
+            ),
+            Expression::Assignment { target, value, .. } => {
+                let power = BinaryOperators::Eq.binding_power();
+                self.binary(target, "=", value, power, context);
+            }
+            Expression::UnaryOp { operator, operand } => self.unary(*operator, operand, context),
+            Expression::Call { callee, arguments } => {
+                self.postfix_target(callee, context);
+                self.out.push('(');
+                self.comma_separated(arguments, |printer, argument| {
+                    printer.expression(argument, Context::default());
+                });
+                self.out.push(')');
+            }
+            Expression::Index { target, index } => {
+                self.postfix_target(target, context);
+                self.out.push('[');
+                self.expression(index, Context::default());
+                self.out.push(']');
+            }
+            Expression::MemberAccess { target, member, .. } => {
+                // `1.x` would be lexed as the float `1.`
+                if matches!(**target, Expression::Literal(LiteralValue::Int(_))) {
+                    self.parenthesized(target);
+                } else {
+                    self.postfix_target(target, context);
+                }
+                let _ = write!(self.out, ".{member}");
+            }
+            Expression::Grouped(inner) => self.parenthesized(inner),
+            Expression::Array(elements) => {
+                self.out.push('[');
+                self.comma_separated(elements, |printer, element| {
+                    printer.expression(element, Context::default());
+                });
+                self.out.push(']');
+            }
+            Expression::Tuple(elements) => {
+                self.out.push('(');
+                self.comma_separated(elements, |printer, element| {
+                    printer.expression(element, Context::default());
+                });
+                if elements.len() == 1 {
+                    self.out.push(',');
+                }
+                self.out.push(')');
+            }
+            Expression::Map(entries) => self.map(entries),
+            Expression::InterpolatedString { parts } => self.interpolated_string(parts),
+            Expression::Match {
+                scrutinee, arms, ..
+            } => {
+                self.out.push_str("match ");
+                self.expression(scrutinee, Context::CONDITION);
+                self.out.push(' ');
+                self.lines(arms, Self::match_arm);
+            }
+            Expression::StructLiteral { name, fields, base } => {
+                self.struct_literal(name, fields, base.as_deref());
+            }
+            Expression::Lambda {
+                parameters,
+                return_type,
+                body,
+            } => self.lambda(parameters, return_type.as_ref(), body, context),
+            Expression::Block(block) => self.block(&block.statements, block.tail.as_deref()),
+            Expression::If {
+                condition,
+                then_branch,
+                else_branch,
+            } => self.if_expression(condition, then_branch, else_branch.as_deref()),
+            Expression::Error(_) => self.out.push_str("/* error */"),
+        }
+    }
+
+    fn lambda(
+        &mut self,
+        parameters: &[Parameter],
+        return_type: Option<&Type>,
+        body: &LambdaBody,
+        context: Context,
+    ) {
+        if parameters.is_empty() {
+            self.out.push_str("||");
+        } else {
+            self.out.push('|');
+            self.comma_separated(parameters, Self::parameter);
+            self.out.push('|');
+        }
+        if let Some(ty) = return_type {
+            self.out.push_str(" -> ");
+            self.ty(ty);
+        }
+        self.out.push_str(" => ");
+
+        match body {
+            LambdaBody::Expression(body) => {
+                let context = Context {
+                    start: Start::NoBrace,
+                    ..context
+                };
+                self.expression(body, context);
+            }
+            LambdaBody::Block(block) => self.block(&block.statements, block.tail.as_deref()),
+        }
+    }
+
+    fn binary(
+        &mut self,
+        left: &Expression,
+        operator: &str,
+        right: &Expression,
+        (left_power, right_power): (u8, u8),
+        context: Context,
+    ) {
+        self.operand(left, left_needs_parens(left, left_power), context.left());
+        let _ = write!(self.out, " {operator} ");
+        self.operand(
+            right,
+            right_needs_parens(right, right_power),
+            context.rest(),
+        );
+    }
+
+    fn unary(&mut self, operator: UnaryOperators, operand: &Expression, context: Context) {
+        let (left_power, right_power) = operator.binding_power();
+
+        match operator.fixity() {
+            Fixity::Prefix => {
+                self.out.push_str(operator.as_str());
+                let parens = right_needs_parens(operand, right_power);
+                self.operand(operand, parens, context.rest());
+            }
+            Fixity::Postfix => {
+                let parens = left_needs_parens(operand, left_power);
+                self.operand(operand, parens, context.left());
+                self.out.push_str(operator.as_str());
+            }
+        }
+    }
+
+    /// Print the target of a call, index or member access
+    fn postfix_target(&mut self, target: &Expression, context: Context) {
+        let parens = left_needs_parens(target, Precedence::Postfix.binding_power());
+        self.operand(target, parens, context.left());
+    }
+
+    fn map(&mut self, entries: &[MapEntry]) {
+        if entries.is_empty() {
+            self.out.push_str("{}");
+            return;
+        }
+
+        self.out.push_str("{ ");
+        for (i, entry) in entries.iter().enumerate() {
+            let context = if i == 0 {
+                // The first key decides whether the braces are a map or a block
+                Context {
+                    start: if matches!(entry.key, Expression::If { .. } | Expression::Match { .. })
+                    {
+                        Start::Any
+                    } else {
+                        Start::NoBlockLike
+                    },
+                    ..Context::default()
+                }
+            } else {
+                self.out.push_str(", ");
+                Context::default()
+            };
+
+            self.expression(&entry.key, context);
+            self.out.push_str(": ");
+            self.expression(&entry.value, Context::default());
+        }
+        self.out.push_str(" }");
+    }
+
+    fn struct_literal(&mut self, name: &str, fields: &[FieldInit], base: Option<&Expression>) {
+        self.out.push_str(name);
+        if fields.is_empty() && base.is_none() {
+            self.out.push_str(" {}");
+            return;
+        }
+
+        self.out.push_str(" { ");
+        self.comma_separated(fields, |printer, field| {
+            printer.out.push_str(&field.name);
+            // `Point { x }` is short for `Point { x: x }`
+            if !matches!(&field.value, Expression::Identifier { name, .. } if *name == field.name) {
+                printer.out.push_str(": ");
+                printer.expression(&field.value, Context::default());
+            }
+        });
+        if let Some(base) = base {
+            if !fields.is_empty() {
+                self.out.push_str(", ");
+            }
+            self.out.push_str("..");
+            self.expression(base, Context::default());
+        }
+        self.out.push_str(" }");
+    }
+
+    fn match_arm(&mut self, arm: &MatchArm) {
+        self.pattern(&arm.pattern);
+        if let Some(guard) = &arm.guard {
+            self.out.push_str(" if ");
+            self.expression(guard, Context::default());
+        }
+        self.out.push_str(" => ");
+        self.expression(&arm.body, Context::default());
+    }
+
+    fn if_expression(
+        &mut self,
+        condition: &Expression,
+        then_branch: &Block,
+        else_branch: Option<&Expression>,
+    ) {
+        self.out.push_str("if ");
+        self.expression(condition, Context::CONDITION);
+        self.out.push(' ');
+        self.block(&then_branch.statements, then_branch.tail.as_deref());
+
+        match else_branch {
+            None => {}
+            Some(Expression::If {
+                condition,
+                then_branch,
+                else_branch,
+            }) => {
+                self.out.push_str(" else ");
+                self.if_expression(condition, then_branch, else_branch.as_deref());
+            }
+            Some(Expression::Block(block)) => {
+                self.out.push_str(" else ");
+                self.block(&block.statements, block.tail.as_deref());
+            }
+            // Anything else has to be wrapped in a block
+            Some(other) => {
+                self.out.push_str(" else ");
+                self.block(&[], Some(other));
+            }
+        }
+    }
+
+    fn literal(&mut self, literal: &LiteralValue) {
+        let unwritable = match literal {
+            LiteralValue::Int(value) => *value < 0,
+            LiteralValue::Float(value) => !value.is_finite() || value.is_sign_negative(),
+            LiteralValue::String(_) | LiteralValue::Bool(_) => false,
+        };
+        if unwritable {
+            self.error
+                .get_or_insert_with(|| PrintError::UnwritableLiteral(literal.clone()));
+            return;
+        }
+
+        match literal {
+            LiteralValue::Int(value) => {
+                let _ = write!(self.out, "{value}");
+            }
+            LiteralValue::Float(value) => {
+                // `Display` never uses an exponent, but leaves out the `.` for whole numbers
+                let text = value.to_string();
+                self.out.push_str(&text);
+                if !text.contains('.') {
+                    self.out.push_str(".0");
+                }
+            }
+            LiteralValue::String(value) => {
+                self.out.push('"');
+                self.string_text(value);
+                self.out.push('"');
+            }
+            LiteralValue::Bool(value) => {
+                let _ = write!(self.out, "{value}");
+            }
+        }
+    }
+
+    fn interpolated_string(&mut self, parts: &[StringPart]) {
+        self.out.push('"');
+        for part in parts {
+            match part {
+                StringPart::Text(text) => self.string_text(text),
+                StringPart::Expression(expression) => {
+                    self.out.push('{');
+                    self.expression(expression, Context::default());
+                    self.out.push('}');
+                }
+            }
+        }
+        self.out.push('"');
+    }
+
+    /// Print the escaped contents of a string literal
+    fn string_text(&mut self, text: &str) {
+        for ch in text.chars() {
+            match ch {
+                '"' => self.out.push_str("\\\""),
+                '\\' => self.out.push_str("\\\\"),
+                '\n' => self.out.push_str("\\n"),
+                '\t' => self.out.push_str("\\t"),
+                '\r' => self.out.push_str("\\r"),
+                '\0' => self.out.push_str("\\0"),
+                // There's no escape for a literal `{` yet, but this at least keeps it from
+                // starting an interpolation
+                '{' => self.out.push_str("\\{"),
+                _ => self.out.push(ch),
+            }
+        }
+    }
+
+    fn pattern(&mut self, pattern: &Pattern) {
+        match pattern {
+            Pattern::Wildcard => self.out.push('_'),
+            Pattern::Literal(literal) => self.literal(literal),
+            Pattern::Binding { name, .. } | Pattern::Path(name) => self.out.push_str(name),
+            Pattern::Tuple(elements) => {
+                self.out.push('(');
+                self.comma_separated(elements, Self::pattern);
+                if elements.len() == 1 {
+                    self.out.push(',');
+                }
+                self.out.push(')');
+            }
+            Pattern::Array(elements) => {
+                self.out.push('[');
+                self.comma_separated(elements, Self::pattern);
+                self.out.push(']');
+            }
+            Pattern::Rest { name, .. } => {
+                self.out.push_str("..");
+                if let Some(name) = name {
+                    self.out.push_str(name);
+                }
+            }
+            Pattern::TupleStruct { name, elements } => {
+                self.out.push_str(name);
+                self.out.push('(');
+                self.comma_separated(elements, Self::pattern);
+                self.out.push(')');
+            }
+            Pattern::Struct { name, fields, rest } => {
+                self.out.push_str(name);
+                if fields.is_empty() && !rest {
+                    self.out.push_str(" {}");
+                    return;
+                }
+
+                self.out.push_str(" { ");
+                self.comma_separated(fields, Self::field_pattern);
+                if *rest {
+                    if !fields.is_empty() {
+                        self.out.push_str(", ");
+                    }
+                    self.out.push_str("..");
+                }
+                self.out.push_str(" }");
+            }
+        }
+    }
+
+    fn field_pattern(&mut self, field: &FieldPattern) {
+        self.out.push_str(&field.name);
+        // `Point { x }` is short for `Point { x: x }`
+        if !matches!(&field.pattern, Pattern::Binding { name, .. } if *name == field.name) {
+            self.out.push_str(": ");
+            self.pattern(&field.pattern);
+        }
+    }
+
+    fn ty(&mut self, ty: &Type) {
+        match ty {
+            Type::Named(name) => self.out.push_str(name),
+            Type::Function {
+                parameters,
+                return_type,
+            } => {
+                self.out.push('(');
+                self.comma_separated(parameters, Self::ty);
+                self.out.push_str(") -> ");
+                self.ty(return_type);
+            }
+            Type::Array(element) => {
+                self.out.push('[');
+                self.ty(element);
+                self.out.push(']');
+            }
+            Type::Tuple(elements) => {
+                self.out.push('(');
+                self.comma_separated(elements, Self::ty);
+                if elements.len() == 1 {
+                    self.out.push(',');
+                }
+                self.out.push(')');
+            }
+            Type::Map { key, value } => {
+                self.out.push('{');
+                self.ty(key);
+                self.out.push_str(": ");
+                self.ty(value);
+                self.out.push('}');
+            }
+            Type::Optional(inner) => {
+                // The `?` would apply to the return type of a function type
+                if matches!(**inner, Type::Function { .. }) {
+                    self.out.push('(');
+                    self.ty(inner);
+                    self.out.push(')');
+                } else {
+                    self.ty(inner);
+                }
+                self.out.push('?');
+            }
+            Type::Generic { name, arguments } => {
+                self.out.push_str(name);
+                self.out.push('<');
+                self.comma_separated(arguments, Self::ty);
+                self.out.push('>');
+            }
+            Type::Unit => self.out.push_str("()"),
+        }
+    }
+}
@@ -0,0 +1,168 @@
+//! Parsing straight from source text
+//!
+//! [`parse_program`], [`parse_expression`] and [`parse_type`] lex and parse in one call,
+//! returning the node along with the lexer and parser errors as [`Diagnostics`][`Diagnostic`]
+
+use ariadne::Report;
+use carbide_errors::{codes::ErrCode, error::CarbideError, suggestion::Suggestion};
+use carbide_lexer::errors::{CarbideLexerError, ErrorSpan};
+use carbide_lexer::lexer::CarbideLexer;
+use carbide_lexer::tokens::Token;
+use thiserror::Error;
+
+use crate::errors::CarbideParserError;
+use crate::nodes::{Expression, Program, Type};
+use crate::parser::{CarbideParser, NodeResult};
+
+/// An error from either the lexer or the parser
+#[derive(Debug, Error, Clone, PartialEq)]
+pub enum Diagnostic {
+    #[error(transparent)]
+    Lexer(CarbideLexerError),
+
+    #[error(transparent)]
+    Parser(CarbideParserError),
+}
+
+impl From<CarbideLexerError> for Diagnostic {
+    fn from(error: CarbideLexerError) -> Self {
+        Self::Lexer(error)
+    }
+}
+
+impl From<CarbideParserError> for Diagnostic {
+    fn from(error: CarbideParserError) -> Self {
+        Self::Parser(error)
+    }
+}
+
+impl CarbideError for Diagnostic {
+    type Span = ErrorSpan;
+
+    fn code(&self) -> ErrCode {
+        match self {
+            Self::Lexer(error) => error.code(),
+            Self::Parser(error) => error.code(),
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            Self::Lexer(error) => error.message(),
+            Self::Parser(error) => error.message(),
+        }
+    }
+
+    fn report(&'_ self, file: &str, src: &str) -> Result<Report<'_, Self::Span>, Box<Self>> {
+        match self {
+            Self::Lexer(error) => error
+                .report(file, src)
+                .map_err(|error| Box::new(Self::Lexer(*error))),
+            Self::Parser(error) => error
+                .report(file, src)
+                .map_err(|error| Box::new(Self::Parser(*error))),
+        }
+    }
+
+    fn help(&self) -> Option<&'static str> {
+        match self {
+            Self::Lexer(error) => error.help(),
+            Self::Parser(error) => error.help(),
+        }
+    }
+
+    fn suggestions(&self) -> Vec<Suggestion> {
+        match self {
+            Self::Lexer(error) => error.suggestions(),
+            Self::Parser(error) => error.suggestions(),
+        }
+    }
+}
+
+/// A node parsed from source text, with every error found on the way
+///
+/// Lexer errors come first, followed by parser errors, each in source order
+#[derive(Debug, Clone)]
+pub struct Parsed<T> {
+    pub node: T,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl<T> Parsed<T> {
+    /// Check if lexing and parsing succeeded without errors
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.diagnostics.is_empty()
+    }
+
+    /// Check if there were any errors
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        !self.diagnostics.is_empty()
+    }
+}
+
+/// Lex `src`, keeping the lexer errors as [`Diagnostics`][`Diagnostic`]
+///
+/// The lexer recovers from errors, so the tokens are still parsed to report as many errors
+/// as possible in one go
+fn lex(src: &str) -> (Vec<Token<'_>>, Vec<Diagnostic>) {
+    let result = CarbideLexer::from_src(src).lex();
+    let diagnostics = result
+        .errors
+        .into_iter()
+        .map(|e| Diagnostic::Lexer(*e))
+        .collect();
+    (result.tokens, diagnostics)
+}
+
+/// Parse `src` into a [`Program`]
+///
+/// Statements that fail to parse are kept as [`Statement::Error`][`crate::nodes::Statement`]
+/// placeholders
+#[must_use]
+pub fn parse_program(src: &str) -> Parsed<Program> {
+    let (tokens, mut diagnostics) = lex(src);
+    let result = CarbideParser::new(tokens).parse();
+    diagnostics.extend(result.errors.into_iter().map(|e| Diagnostic::Parser(*e)));
+
+    Parsed {
+        node: Program::new(result.ast),
+        diagnostics,
+    }
+}
+
+/// Parse `src` as a single [`Expression`]
+///
+/// If it fails to parse, the node is an [`Expression::Error`] covering all of `src`
+#[must_use]
+pub fn parse_expression(src: &str) -> Parsed<Expression> {
+    let Parsed { node, diagnostics } = parse_node(src, CarbideParser::parse_single_expression);
+
+    Parsed {
+        node: node.unwrap_or(Expression::Error(0..src.len() as u64)),
+        diagnostics,
+    }
+}
+
+/// Parse `src` as a single [`Type`]
+///
+/// The node is `None` if it fails to parse
+#[must_use]
+pub fn parse_type(src: &str) -> Parsed<Option<Type>> {
+    parse_node(src, CarbideParser::parse_single_type)
+}
+
+fn parse_node<'a, T>(
+    src: &'a str,
+    parse: impl FnOnce(&mut CarbideParser<'a>) -> NodeResult<T>,
+) -> Parsed<Option<T>> {
+    let (tokens, mut diagnostics) = lex(src);
+    let result = parse(&mut CarbideParser::new(tokens));
+    diagnostics.extend(result.errors.into_iter().map(|e| Diagnostic::Parser(*e)));
+
+    Parsed {
+        node: result.node,
+        diagnostics,
+    }
+}
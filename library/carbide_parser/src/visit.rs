@@ -0,0 +1,477 @@
+//! Read-only traversal of the AST
+//!
+//! Each `visit_*` method of [`Visitor`] defaults to the matching `walk_*` function, which
+//! visits the node's children. Override the methods for the nodes a pass cares about, and
+//! call the `walk_*` function from the override to keep descending
+
+use crate::nodes::{
+    Attribute, Block, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody, LiteralValue,
+    MapEntry, MatchArm, Method, Parameter, Pattern, Program, Statement, StringPart, StructField,
+    Type, TypeParameter, VariantPayload, Visibility,
+};
+
+/// Visits the nodes of an AST borrowed for `'ast`
+pub trait Visitor<'ast> {
+    fn visit_program(&mut self, program: &'ast Program) {
+        walk_program(self, program);
+    }
+
+    fn visit_statement(&mut self, statement: &'ast Statement) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_expression(&mut self, expression: &'ast Expression) {
+        walk_expression(self, expression);
+    }
+
+    fn visit_block(&mut self, block: &'ast Block) {
+        walk_block(self, block);
+    }
+
+    fn visit_lambda_body(&mut self, body: &'ast LambdaBody) {
+        walk_lambda_body(self, body);
+    }
+
+    fn visit_string_part(&mut self, part: &'ast StringPart) {
+        walk_string_part(self, part);
+    }
+
+    fn visit_match_arm(&mut self, arm: &'ast MatchArm) {
+        walk_match_arm(self, arm);
+    }
+
+    fn visit_map_entry(&mut self, entry: &'ast MapEntry) {
+        walk_map_entry(self, entry);
+    }
+
+    fn visit_field_init(&mut self, field: &'ast FieldInit) {
+        walk_field_init(self, field);
+    }
+
+    fn visit_pattern(&mut self, pattern: &'ast Pattern) {
+        walk_pattern(self, pattern);
+    }
+
+    fn visit_field_pattern(&mut self, field: &'ast FieldPattern) {
+        walk_field_pattern(self, field);
+    }
+
+    fn visit_attribute(&mut self, attribute: &'ast Attribute) {
+        walk_attribute(self, attribute);
+    }
+
+    fn visit_parameter(&mut self, parameter: &'ast Parameter) {
+        walk_parameter(self, parameter);
+    }
+
+    fn visit_method(&mut self, method: &'ast Method) {
+        walk_method(self, method);
+    }
+
+    fn visit_struct_field(&mut self, field: &'ast StructField) {
+        walk_struct_field(self, field);
+    }
+
+    fn visit_enum_variant(&mut self, variant: &'ast EnumVariant) {
+        walk_enum_variant(self, variant);
+    }
+
+    fn visit_variant_payload(&mut self, payload: &'ast VariantPayload) {
+        walk_variant_payload(self, payload);
+    }
+
+    fn visit_type(&mut self, ty: &'ast Type) {
+        walk_type(self, ty);
+    }
+
+    fn visit_literal(&mut self, _literal: &'ast LiteralValue) {}
+
+    fn visit_visibility(&mut self, _visibility: &'ast Visibility) {}
+
+    fn visit_type_parameter(&mut self, _type_parameter: &'ast TypeParameter) {}
+}
+
+pub fn walk_program<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, program: &'ast Program) {
+    for statement in &program.statements {
+        visitor.visit_statement(statement);
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn walk_statement<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    statement: &'ast Statement,
+) {
+    match statement {
+        Statement::LetDeclaration {
+            attributes,
+            pattern,
+            type_annotation,
+            initializer,
+            ..
+        } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+            visitor.visit_pattern(pattern);
+            if let Some(ty) = type_annotation {
+                visitor.visit_type(ty);
+            }
+            if let Some(initializer) = initializer {
+                visitor.visit_expression(initializer);
+            }
+        }
+        Statement::ConstDeclaration {
+            visibility,
+            type_annotation,
+            value,
+            ..
+        } => {
+            visitor.visit_visibility(visibility);
+            visitor.visit_type(type_annotation);
+            visitor.visit_expression(value);
+        }
+        Statement::FunctionDeclaration {
+            attributes,
+            visibility,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+            ..
+        } => {
+            for attribute in attributes {
+                visitor.visit_attribute(attribute);
+            }
+            visitor.visit_visibility(visibility);
+            for type_parameter in type_parameters {
+                visitor.visit_type_parameter(type_parameter);
+            }
+            for parameter in parameters {
+                visitor.visit_parameter(parameter);
+            }
+            if let Some(ty) = return_type {
+                visitor.visit_type(ty);
+            }
+            visitor.visit_block(body);
+        }
+        Statement::StructDeclaration {
+            visibility, fields, ..
+        } => {
+            visitor.visit_visibility(visibility);
+            for field in fields {
+                visitor.visit_struct_field(field);
+            }
+        }
+        Statement::EnumDeclaration {
+            visibility,
+            variants,
+            ..
+        } => {
+            visitor.visit_visibility(visibility);
+            for variant in variants {
+                visitor.visit_enum_variant(variant);
+            }
+        }
+        Statement::TraitDeclaration {
+            visibility,
+            methods,
+            ..
+        } => {
+            visitor.visit_visibility(visibility);
+            for method in methods {
+                visitor.visit_method(method);
+            }
+        }
+        Statement::ImplDeclaration { methods, .. } => {
+            for method in methods {
+                visitor.visit_method(method);
+            }
+        }
+        Statement::Return(value) => {
+            if let Some(value) = value {
+                visitor.visit_expression(value);
+            }
+        }
+        Statement::While { condition, body } => {
+            visitor.visit_expression(condition);
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        Statement::For {
+            initializer,
+            condition,
+            increment,
+            body,
+        } => {
+            if let Some(initializer) = initializer {
+                visitor.visit_statement(initializer);
+            }
+            for expression in condition.iter().chain(increment) {
+                visitor.visit_expression(expression);
+            }
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        Statement::Block(body) => {
+            for statement in body {
+                visitor.visit_statement(statement);
+            }
+        }
+        Statement::Expression(expression) => visitor.visit_expression(expression),
+        Statement::Import { .. } | Statement::Break | Statement::Continue | Statement::Error(_) => {
+        }
+    }
+}
+
+pub fn walk_expression<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    expression: &'ast Expression,
+) {
+    match expression {
+        Expression::Literal(literal) => visitor.visit_literal(literal),
+        Expression::BinaryOp { left, right, .. } => {
+            visitor.visit_expression(left);
+            visitor.visit_expression(right);
+        }
+        Expression::UnaryOp { operand, .. } => visitor.visit_expression(operand),
+        Expression::Assignment { target, value, .. } => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(value);
+        }
+        Expression::Call { callee, arguments } => {
+            visitor.visit_expression(callee);
+            for argument in arguments {
+                visitor.visit_expression(argument);
+            }
+        }
+        Expression::Index { target, index } => {
+            visitor.visit_expression(target);
+            visitor.visit_expression(index);
+        }
+        Expression::MemberAccess { target, .. } => visitor.visit_expression(target),
+        Expression::Grouped(inner) => visitor.visit_expression(inner),
+        Expression::Array(elements) | Expression::Tuple(elements) => {
+            for element in elements {
+                visitor.visit_expression(element);
+            }
+        }
+        Expression::Map(entries) => {
+            for entry in entries {
+                visitor.visit_map_entry(entry);
+            }
+        }
+        Expression::InterpolatedString { parts } => {
+            for part in parts {
+                visitor.visit_string_part(part);
+            }
+        }
+        Expression::Match {
+            scrutinee, arms, ..
+        } => {
+            visitor.visit_expression(scrutinee);
+            for arm in arms {
+                visitor.visit_match_arm(arm);
+            }
+        }
+        Expression::StructLiteral { fields, base, .. } => {
+            for field in fields {
+                visitor.visit_field_init(field);
+            }
+            if let Some(base) = base {
+                visitor.visit_expression(base);
+            }
+        }
+        Expression::Lambda {
+            parameters,
+            return_type,
+            body,
+        } => {
+            for parameter in parameters {
+                visitor.visit_parameter(parameter);
+            }
+            if let Some(ty) = return_type {
+                visitor.visit_type(ty);
+            }
+            visitor.visit_lambda_body(body);
+        }
+        Expression::Block(block) => visitor.visit_block(block),
+        Expression::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_expression(condition);
+            visitor.visit_block(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_expression(else_branch);
+            }
+        }
+        Expression::Generic { arguments, .. } => {
+            for ty in arguments {
+                visitor.visit_type(ty);
+            }
+        }
+        Expression::Identifier { .. } | Expression::Error(_) => {}
+    }
+}
+
+pub fn walk_block<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, block: &'ast Block) {
+    for statement in &block.statements {
+        visitor.visit_statement(statement);
+    }
+    if let Some(tail) = &block.tail {
+        visitor.visit_expression(tail);
+    }
+}
+
+pub fn walk_lambda_body<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, body: &'ast LambdaBody) {
+    match body {
+        LambdaBody::Expression(expression) => visitor.visit_expression(expression),
+        LambdaBody::Block(block) => visitor.visit_block(block),
+    }
+}
+
+pub fn walk_string_part<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, part: &'ast StringPart) {
+    if let StringPart::Expression(expression) = part {
+        visitor.visit_expression(expression);
+    }
+}
+
+pub fn walk_match_arm<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, arm: &'ast MatchArm) {
+    visitor.visit_pattern(&arm.pattern);
+    if let Some(guard) = &arm.guard {
+        visitor.visit_expression(guard);
+    }
+    visitor.visit_expression(&arm.body);
+}
+
+pub fn walk_map_entry<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, entry: &'ast MapEntry) {
+    visitor.visit_expression(&entry.key);
+    visitor.visit_expression(&entry.value);
+}
+
+pub fn walk_field_init<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, field: &'ast FieldInit) {
+    visitor.visit_expression(&field.value);
+}
+
+pub fn walk_pattern<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, pattern: &'ast Pattern) {
+    match pattern {
+        Pattern::Literal(literal) => visitor.visit_literal(literal),
+        Pattern::Tuple(elements)
+        | Pattern::Array(elements)
+        | Pattern::TupleStruct { elements, .. } => {
+            for element in elements {
+                visitor.visit_pattern(element);
+            }
+        }
+        Pattern::Struct { fields, .. } => {
+            for field in fields {
+                visitor.visit_field_pattern(field);
+            }
+        }
+        Pattern::Wildcard | Pattern::Binding { .. } | Pattern::Path(_) | Pattern::Rest { .. } => {}
+    }
+}
+
+pub fn walk_field_pattern<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    field: &'ast FieldPattern,
+) {
+    visitor.visit_pattern(&field.pattern);
+}
+
+pub fn walk_attribute<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    attribute: &'ast Attribute,
+) {
+    for argument in &attribute.arguments {
+        visitor.visit_literal(argument);
+    }
+}
+
+pub fn walk_parameter<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    parameter: &'ast Parameter,
+) {
+    visitor.visit_pattern(&parameter.pattern);
+    if let Some(ty) = &parameter.type_annotation {
+        visitor.visit_type(ty);
+    }
+}
+
+pub fn walk_method<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, method: &'ast Method) {
+    for parameter in &method.parameters {
+        visitor.visit_parameter(parameter);
+    }
+    if let Some(ty) = &method.return_type {
+        visitor.visit_type(ty);
+    }
+    if let Some(body) = &method.body {
+        visitor.visit_block(body);
+    }
+}
+
+pub fn walk_struct_field<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    field: &'ast StructField,
+) {
+    visitor.visit_type(&field.type_annotation);
+}
+
+pub fn walk_enum_variant<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    variant: &'ast EnumVariant,
+) {
+    visitor.visit_variant_payload(&variant.payload);
+}
+
+pub fn walk_variant_payload<'ast, V: Visitor<'ast> + ?Sized>(
+    visitor: &mut V,
+    payload: &'ast VariantPayload,
+) {
+    match payload {
+        VariantPayload::Unit => {}
+        VariantPayload::Tuple(types) => {
+            for ty in types {
+                visitor.visit_type(ty);
+            }
+        }
+        VariantPayload::Struct(fields) => {
+            for field in fields {
+                visitor.visit_struct_field(field);
+            }
+        }
+    }
+}
+
+pub fn walk_type<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, ty: &'ast Type) {
+    match ty {
+        Type::Function {
+            parameters,
+            return_type,
+        } => {
+            for parameter in parameters {
+                visitor.visit_type(parameter);
+            }
+            visitor.visit_type(return_type);
+        }
+        Type::Array(element) | Type::Optional(element) => visitor.visit_type(element),
+        Type::Tuple(elements)
+        | Type::Generic {
+            arguments: elements,
+            ..
+        } => {
+            for element in elements {
+                visitor.visit_type(element);
+            }
+        }
+        Type::Map { key, value } => {
+            visitor.visit_type(key);
+            visitor.visit_type(value);
+        }
+        Type::Named(_) | Type::Unit => {}
+    }
+}
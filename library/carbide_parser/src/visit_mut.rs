@@ -0,0 +1,456 @@
+//! In-place traversal of the AST
+//!
+//! The mutable counterpart of [`Visitor`][`crate::visit::Visitor`]: each `visit_*_mut`
+//! method of [`VisitorMut`] defaults to the matching `walk_*_mut` function, so a pass can
+//! rewrite the nodes it cares about without rebuilding the rest of the tree
+
+use crate::nodes::{
+    Attribute, Block, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody, LiteralValue,
+    MapEntry, MatchArm, Method, Parameter, Pattern, Program, Statement, StringPart, StructField,
+    Type, TypeParameter, VariantPayload, Visibility,
+};
+
+/// Visits the nodes of an AST through mutable references
+pub trait VisitorMut {
+    fn visit_program_mut(&mut self, program: &mut Program) {
+        walk_program_mut(self, program);
+    }
+
+    fn visit_statement_mut(&mut self, statement: &mut Statement) {
+        walk_statement_mut(self, statement);
+    }
+
+    fn visit_expression_mut(&mut self, expression: &mut Expression) {
+        walk_expression_mut(self, expression);
+    }
+
+    fn visit_block_mut(&mut self, block: &mut Block) {
+        walk_block_mut(self, block);
+    }
+
+    fn visit_lambda_body_mut(&mut self, body: &mut LambdaBody) {
+        walk_lambda_body_mut(self, body);
+    }
+
+    fn visit_string_part_mut(&mut self, part: &mut StringPart) {
+        walk_string_part_mut(self, part);
+    }
+
+    fn visit_match_arm_mut(&mut self, arm: &mut MatchArm) {
+        walk_match_arm_mut(self, arm);
+    }
+
+    fn visit_map_entry_mut(&mut self, entry: &mut MapEntry) {
+        walk_map_entry_mut(self, entry);
+    }
+
+    fn visit_field_init_mut(&mut self, field: &mut FieldInit) {
+        walk_field_init_mut(self, field);
+    }
+
+    fn visit_pattern_mut(&mut self, pattern: &mut Pattern) {
+        walk_pattern_mut(self, pattern);
+    }
+
+    fn visit_field_pattern_mut(&mut self, field: &mut FieldPattern) {
+        walk_field_pattern_mut(self, field);
+    }
+
+    fn visit_attribute_mut(&mut self, attribute: &mut Attribute) {
+        walk_attribute_mut(self, attribute);
+    }
+
+    fn visit_parameter_mut(&mut self, parameter: &mut Parameter) {
+        walk_parameter_mut(self, parameter);
+    }
+
+    fn visit_method_mut(&mut self, method: &mut Method) {
+        walk_method_mut(self, method);
+    }
+
+    fn visit_struct_field_mut(&mut self, field: &mut StructField) {
+        walk_struct_field_mut(self, field);
+    }
+
+    fn visit_enum_variant_mut(&mut self, variant: &mut EnumVariant) {
+        walk_enum_variant_mut(self, variant);
+    }
+
+    fn visit_variant_payload_mut(&mut self, payload: &mut VariantPayload) {
+        walk_variant_payload_mut(self, payload);
+    }
+
+    fn visit_type_mut(&mut self, ty: &mut Type) {
+        walk_type_mut(self, ty);
+    }
+
+    fn visit_literal_mut(&mut self, _literal: &mut LiteralValue) {}
+
+    fn visit_visibility_mut(&mut self, _visibility: &mut Visibility) {}
+
+    fn visit_type_parameter_mut(&mut self, _type_parameter: &mut TypeParameter) {}
+}
+
+pub fn walk_program_mut<V: VisitorMut + ?Sized>(visitor: &mut V, program: &mut Program) {
+    for statement in &mut program.statements {
+        visitor.visit_statement_mut(statement);
+    }
+}
+
+#[allow(clippy::too_many_lines)]
+pub fn walk_statement_mut<V: VisitorMut + ?Sized>(visitor: &mut V, statement: &mut Statement) {
+    match statement {
+        Statement::LetDeclaration {
+            attributes,
+            pattern,
+            type_annotation,
+            initializer,
+            ..
+        } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+            visitor.visit_pattern_mut(pattern);
+            if let Some(ty) = type_annotation {
+                visitor.visit_type_mut(ty);
+            }
+            if let Some(initializer) = initializer {
+                visitor.visit_expression_mut(initializer);
+            }
+        }
+        Statement::ConstDeclaration {
+            visibility,
+            type_annotation,
+            value,
+            ..
+        } => {
+            visitor.visit_visibility_mut(visibility);
+            visitor.visit_type_mut(type_annotation);
+            visitor.visit_expression_mut(value);
+        }
+        Statement::FunctionDeclaration {
+            attributes,
+            visibility,
+            type_parameters,
+            parameters,
+            return_type,
+            body,
+            ..
+        } => {
+            for attribute in attributes {
+                visitor.visit_attribute_mut(attribute);
+            }
+            visitor.visit_visibility_mut(visibility);
+            for type_parameter in type_parameters {
+                visitor.visit_type_parameter_mut(type_parameter);
+            }
+            for parameter in parameters {
+                visitor.visit_parameter_mut(parameter);
+            }
+            if let Some(ty) = return_type {
+                visitor.visit_type_mut(ty);
+            }
+            visitor.visit_block_mut(body);
+        }
+        Statement::StructDeclaration {
+            visibility, fields, ..
+        } => {
+            visitor.visit_visibility_mut(visibility);
+            for field in fields {
+                visitor.visit_struct_field_mut(field);
+            }
+        }
+        Statement::EnumDeclaration {
+            visibility,
+            variants,
+            ..
+        } => {
+            visitor.visit_visibility_mut(visibility);
+            for variant in variants {
+                visitor.visit_enum_variant_mut(variant);
+            }
+        }
+        Statement::TraitDeclaration {
+            visibility,
+            methods,
+            ..
+        } => {
+            visitor.visit_visibility_mut(visibility);
+            for method in methods {
+                visitor.visit_method_mut(method);
+            }
+        }
+        Statement::ImplDeclaration { methods, .. } => {
+            for method in methods {
+                visitor.visit_method_mut(method);
+            }
+        }
+        Statement::Return(value) => {
+            if let Some(value) = value {
+                visitor.visit_expression_mut(value);
+            }
+        }
+        Statement::While { condition, body } => {
+            visitor.visit_expression_mut(condition);
+            for statement in body {
+                visitor.visit_statement_mut(statement);
+            }
+        }
+        Statement::For {
+            initializer,
+            condition,
+            increment,
+            body,
+        } => {
+            if let Some(initializer) = initializer {
+                visitor.visit_statement_mut(initializer);
+            }
+            for expression in condition.iter_mut().chain(increment) {
+                visitor.visit_expression_mut(expression);
+            }
+            for statement in body {
+                visitor.visit_statement_mut(statement);
+            }
+        }
+        Statement::Block(body) => {
+            for statement in body {
+                visitor.visit_statement_mut(statement);
+            }
+        }
+        Statement::Expression(expression) => visitor.visit_expression_mut(expression),
+        Statement::Import { .. } | Statement::Break | Statement::Continue | Statement::Error(_) => {
+        }
+    }
+}
+
+pub fn walk_expression_mut<V: VisitorMut + ?Sized>(visitor: &mut V, expression: &mut Expression) {
+    match expression {
+        Expression::Literal(literal) => visitor.visit_literal_mut(literal),
+        Expression::BinaryOp { left, right, .. } => {
+            visitor.visit_expression_mut(left);
+            visitor.visit_expression_mut(right);
+        }
+        Expression::UnaryOp { operand, .. } => visitor.visit_expression_mut(operand),
+        Expression::Assignment { target, value, .. } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_expression_mut(value);
+        }
+        Expression::Call { callee, arguments } => {
+            visitor.visit_expression_mut(callee);
+            for argument in arguments {
+                visitor.visit_expression_mut(argument);
+            }
+        }
+        Expression::Index { target, index } => {
+            visitor.visit_expression_mut(target);
+            visitor.visit_expression_mut(index);
+        }
+        Expression::MemberAccess { target, .. } => visitor.visit_expression_mut(target),
+        Expression::Grouped(inner) => visitor.visit_expression_mut(inner),
+        Expression::Array(elements) | Expression::Tuple(elements) => {
+            for element in elements {
+                visitor.visit_expression_mut(element);
+            }
+        }
+        Expression::Map(entries) => {
+            for entry in entries {
+                visitor.visit_map_entry_mut(entry);
+            }
+        }
+        Expression::InterpolatedString { parts } => {
+            for part in parts {
+                visitor.visit_string_part_mut(part);
+            }
+        }
+        Expression::Match {
+            scrutinee, arms, ..
+        } => {
+            visitor.visit_expression_mut(scrutinee);
+            for arm in arms {
+                visitor.visit_match_arm_mut(arm);
+            }
+        }
+        Expression::StructLiteral { fields, base, .. } => {
+            for field in fields {
+                visitor.visit_field_init_mut(field);
+            }
+            if let Some(base) = base {
+                visitor.visit_expression_mut(base);
+            }
+        }
+        Expression::Lambda {
+            parameters,
+            return_type,
+            body,
+        } => {
+            for parameter in parameters {
+                visitor.visit_parameter_mut(parameter);
+            }
+            if let Some(ty) = return_type {
+                visitor.visit_type_mut(ty);
+            }
+            visitor.visit_lambda_body_mut(body);
+        }
+        Expression::Block(block) => visitor.visit_block_mut(block),
+        Expression::If {
+            condition,
+            then_branch,
+            else_branch,
+        } => {
+            visitor.visit_expression_mut(condition);
+            visitor.visit_block_mut(then_branch);
+            if let Some(else_branch) = else_branch {
+                visitor.visit_expression_mut(else_branch);
+            }
+        }
+        Expression::Generic { arguments, .. } => {
+            for ty in arguments {
+                visitor.visit_type_mut(ty);
+            }
+        }
+        Expression::Identifier { .. } | Expression::Error(_) => {}
+    }
+}
+
+pub fn walk_block_mut<V: VisitorMut + ?Sized>(visitor: &mut V, block: &mut Block) {
+    for statement in &mut block.statements {
+        visitor.visit_statement_mut(statement);
+    }
+    if let Some(tail) = &mut block.tail {
+        visitor.visit_expression_mut(tail);
+    }
+}
+
+pub fn walk_lambda_body_mut<V: VisitorMut + ?Sized>(visitor: &mut V, body: &mut LambdaBody) {
+    match body {
+        LambdaBody::Expression(expression) => visitor.visit_expression_mut(expression),
+        LambdaBody::Block(block) => visitor.visit_block_mut(block),
+    }
+}
+
+pub fn walk_string_part_mut<V: VisitorMut + ?Sized>(visitor: &mut V, part: &mut StringPart) {
+    if let StringPart::Expression(expression) = part {
+        visitor.visit_expression_mut(expression);
+    }
+}
+
+pub fn walk_match_arm_mut<V: VisitorMut + ?Sized>(visitor: &mut V, arm: &mut MatchArm) {
+    visitor.visit_pattern_mut(&mut arm.pattern);
+    if let Some(guard) = &mut arm.guard {
+        visitor.visit_expression_mut(guard);
+    }
+    visitor.visit_expression_mut(&mut arm.body);
+}
+
+pub fn walk_map_entry_mut<V: VisitorMut + ?Sized>(visitor: &mut V, entry: &mut MapEntry) {
+    visitor.visit_expression_mut(&mut entry.key);
+    visitor.visit_expression_mut(&mut entry.value);
+}
+
+pub fn walk_field_init_mut<V: VisitorMut + ?Sized>(visitor: &mut V, field: &mut FieldInit) {
+    visitor.visit_expression_mut(&mut field.value);
+}
+
+pub fn walk_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, pattern: &mut Pattern) {
+    match pattern {
+        Pattern::Literal(literal) => visitor.visit_literal_mut(literal),
+        Pattern::Tuple(elements)
+        | Pattern::Array(elements)
+        | Pattern::TupleStruct { elements, .. } => {
+            for element in elements {
+                visitor.visit_pattern_mut(element);
+            }
+        }
+        Pattern::Struct { fields, .. } => {
+            for field in fields {
+                visitor.visit_field_pattern_mut(field);
+            }
+        }
+        Pattern::Wildcard | Pattern::Binding { .. } | Pattern::Path(_) | Pattern::Rest { .. } => {}
+    }
+}
+
+pub fn walk_field_pattern_mut<V: VisitorMut + ?Sized>(visitor: &mut V, field: &mut FieldPattern) {
+    visitor.visit_pattern_mut(&mut field.pattern);
+}
+
+pub fn walk_attribute_mut<V: VisitorMut + ?Sized>(visitor: &mut V, attribute: &mut Attribute) {
+    for argument in &mut attribute.arguments {
+        visitor.visit_literal_mut(argument);
+    }
+}
+
+pub fn walk_parameter_mut<V: VisitorMut + ?Sized>(visitor: &mut V, parameter: &mut Parameter) {
+    visitor.visit_pattern_mut(&mut parameter.pattern);
+    if let Some(ty) = &mut parameter.type_annotation {
+        visitor.visit_type_mut(ty);
+    }
+}
+
+pub fn walk_method_mut<V: VisitorMut + ?Sized>(visitor: &mut V, method: &mut Method) {
+    for parameter in &mut method.parameters {
+        visitor.visit_parameter_mut(parameter);
+    }
+    if let Some(ty) = &mut method.return_type {
+        visitor.visit_type_mut(ty);
+    }
+    if let Some(body) = &mut method.body {
+        visitor.visit_block_mut(body);
+    }
+}
+
+pub fn walk_struct_field_mut<V: VisitorMut + ?Sized>(visitor: &mut V, field: &mut StructField) {
+    visitor.visit_type_mut(&mut field.type_annotation);
+}
+
+pub fn walk_enum_variant_mut<V: VisitorMut + ?Sized>(visitor: &mut V, variant: &mut EnumVariant) {
+    visitor.visit_variant_payload_mut(&mut variant.payload);
+}
+
+pub fn walk_variant_payload_mut<V: VisitorMut + ?Sized>(
+    visitor: &mut V,
+    payload: &mut VariantPayload,
+) {
+    match payload {
+        VariantPayload::Unit => {}
+        VariantPayload::Tuple(types) => {
+            for ty in types {
+                visitor.visit_type_mut(ty);
+            }
+        }
+        VariantPayload::Struct(fields) => {
+            for field in fields {
+                visitor.visit_struct_field_mut(field);
+            }
+        }
+    }
+}
+
+pub fn walk_type_mut<V: VisitorMut + ?Sized>(visitor: &mut V, ty: &mut Type) {
+    match ty {
+        Type::Function {
+            parameters,
+            return_type,
+        } => {
+            for parameter in parameters {
+                visitor.visit_type_mut(parameter);
+            }
+            visitor.visit_type_mut(return_type);
+        }
+        Type::Array(element) | Type::Optional(element) => visitor.visit_type_mut(element),
+        Type::Tuple(elements)
+        | Type::Generic {
+            arguments: elements,
+            ..
+        } => {
+            for element in elements {
+                visitor.visit_type_mut(element);
+            }
+        }
+        Type::Map { key, value } => {
+            visitor.visit_type_mut(key);
+            visitor.visit_type_mut(value);
+        }
+        Type::Named(_) | Type::Unit => {}
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod assignments {
+    use carbide_errors::error::CarbideError;
+    use carbide_errors::reporter::ErrorReporter;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        errors::CarbideParserError,
+        nodes::{Expression, LiteralValue, Statement},
+        parser::{CarbideParser, ParseResult},
+    };
+
+    fn parse_src(src: &str) -> ParseResult {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        CarbideParser::new(result.tokens).parse()
+    }
+
+    #[test]
+    fn valid_targets() {
+        for src in [
+            "x = 1;",
+            "xs[0] = 1;",
+            "p.x = 1;",
+            "a.b[c].d = 1;",
+            "Shape::count = 1;",
+            "a = b = c;",
+            "f()[0] = 1;",
+            "f().x = 1;",
+            "(x) = 1;",
+            "((p.x)) = 1;",
+            "(xs)[0] = 1;",
+        ] {
+            let result = parse_src(src);
+            assert!(result.is_ok(), "{src}: {:?}", result.errors);
+        }
+    }
+
+    #[test]
+    fn invalid_targets() {
+        for (src, target) in [
+            ("1 + 2 = x;", 0..5),
+            ("f() = 3;", 0..3),
+            ("((f())) = 1;", 0..7),
+            ("!x = 1;", 0..2),
+            ("1 = x;", 0..1),
+            ("(a, b) = (b, a);", 0..6),
+            ("[a] = xs;", 0..3),
+            ("a = f() = 1;", 4..7),
+        ] {
+            let result = parse_src(src);
+            assert_eq!(
+                result
+                    .errors
+                    .iter()
+                    .map(|error| &**error)
+                    .collect::<Vec<_>>(),
+                [&CarbideParserError::InvalidAssignmentTarget(target)],
+                "{src}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_target_is_kept() {
+        let result = parse_src("f() = 3;\nlet y = 2;");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code().to_string(), "E1030");
+        assert_eq!(
+            result.errors[0].to_string(),
+            "Invalid assignment target at 0..3"
+        );
+        assert_eq!(
+            result.ast[0],
+            Statement::Expression(Expression::Assignment {
+                target: Box::new(Expression::Call {
+                    callee: Box::new(Expression::identifier("f", 0..1)),
+                    arguments: vec![],
+                }),
+                value: Box::new(Expression::Literal(LiteralValue::Int(3))),
+                span: 0..7,
+            })
+        );
+        assert!(matches!(result.ast[1], Statement::LetDeclaration { .. }));
+    }
+
+    #[test]
+    fn initializer_errors() {
+        for (src, code) in [
+            ("let x = ;", "E1002"),
+            ("let x = 1 +;", "E1002"),
+            ("let x = (1;", "E1002"),
+            ("let x = f() = 1;", "E1030"),
+        ] {
+            let result = parse_src(src);
+            assert_eq!(result.errors.len(), 1, "{src}: {:?}", result.errors);
+            assert_eq!(result.errors[0].code().to_string(), code, "{src}");
+        }
+    }
+
+    #[test]
+    fn report() {
+        let src = "let a = 1;\na + 1 = 2;";
+        let result = parse_src(src);
+
+        let mut reporter = ErrorReporter::new();
+        reporter.add_source("test.cb", src);
+        let report = reporter
+            .format_error("test.cb", &*result.errors[0])
+            .expect("Expected formatting to succeed");
+        assert!(report.contains("E1030"), "{report}");
+        assert!(report.contains("`a + 1` cannot be assigned to"), "{report}");
+    }
+}
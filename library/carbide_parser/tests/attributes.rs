@@ -0,0 +1,151 @@
+#[cfg(test)]
+mod attributes {
+    use carbide_errors::error::CarbideError;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Attribute, Block, LiteralValue, Statement, Visibility},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn attributes(statement: &Statement) -> &[Attribute] {
+        match statement {
+            Statement::LetDeclaration { attributes, .. }
+            | Statement::FunctionDeclaration { attributes, .. } => attributes,
+            other => panic!("Expected a declaration, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn function_attribute() {
+        let (_, result) = parse_src("#[inline] fn f() {}");
+        assert!(result.is_ok(), "{:?}", result.errors);
+        assert_eq!(
+            result.ast,
+            vec![Statement::FunctionDeclaration {
+                attributes: vec![Attribute {
+                    name: "inline".to_string(),
+                    arguments: vec![],
+                    span: 0..9,
+                }],
+                visibility: Visibility::Private,
+                name: "f".to_string(),
+                type_parameters: Vec::new(),
+                parameters: vec![],
+                return_type: None,
+                body: Block::default(),
+            }]
+        );
+    }
+
+    #[test]
+    fn attribute_arguments() {
+        let (_, result) = parse_src("#[deprecated(\"use g\", 2, true,)] let x = 1;");
+        assert!(result.is_ok(), "{:?}", result.errors);
+        assert_eq!(
+            attributes(&result.ast[0]),
+            [Attribute {
+                name: "deprecated".to_string(),
+                arguments: vec![
+                    LiteralValue::String("use g".to_string()),
+                    LiteralValue::Int(2),
+                    LiteralValue::Bool(true),
+                ],
+                span: 0..32,
+            }]
+        );
+    }
+
+    #[test]
+    fn several_attributes_on_a_public_function() {
+        let (_, result) = parse_src("#[test]\n#[inline()] pub fn f() {}");
+        assert!(result.is_ok(), "{:?}", result.errors);
+
+        let names: Vec<_> = attributes(&result.ast[0])
+            .iter()
+            .map(|attribute| attribute.name.as_str())
+            .collect();
+        assert_eq!(names, ["test", "inline"]);
+        assert!(matches!(
+            result.ast[0],
+            Statement::FunctionDeclaration {
+                visibility: Visibility::Public,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn attribute_inside_a_block() {
+        let (_, result) = parse_src("fn f() { #[deprecated] let x = 1; }");
+        assert!(result.is_ok(), "{:?}", result.errors);
+
+        let Statement::FunctionDeclaration { body, .. } = &result.ast[0] else {
+            panic!("Expected a function declaration, found {:?}", result.ast[0]);
+        };
+        assert_eq!(attributes(&body.statements[0])[0].name, "deprecated");
+    }
+
+    #[test]
+    fn attribute_on_a_struct() {
+        let (_, result) = parse_src("#[inline] struct P { x: int }\nlet y = 1;");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code().to_string(), "E1050");
+        assert!(matches!(result.ast[0], Statement::Error(_)));
+        assert!(matches!(result.ast[1], Statement::LetDeclaration { .. }));
+
+        let (_, result) = parse_src("#[inline] pub struct P { x: int }");
+        assert_eq!(result.errors[0].code().to_string(), "E1050");
+    }
+
+    #[test]
+    fn attribute_on_an_expression() {
+        let (_, result) = parse_src("#[inline] f();");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code().to_string(), "E1050");
+    }
+
+    #[test]
+    fn attribute_on_an_anonymous_function() {
+        let (_, result) = parse_src("#[inline] fn(x) => x;");
+        assert_eq!(result.errors[0].code().to_string(), "E1050");
+    }
+
+    #[test]
+    fn invalid_attribute_syntax() {
+        for src in [
+            "#inline fn f() {}",
+            "#[] fn f() {}",
+            "#[inline fn f() {}",
+            "#[deprecated(g)] fn f() {}",
+            "#[deprecated(1 + 2)] fn f() {}",
+            "#[deprecated(\"a\" \"b\")] fn f() {}",
+        ] {
+            let (_, result) = parse_src(src);
+            assert_eq!(result.errors.len(), 1, "{src}");
+            assert_eq!(result.errors[0].code().to_string(), "E1002", "{src}");
+        }
+    }
+
+    #[test]
+    fn attribute_at_end_of_input() {
+        let (_, result) = parse_src("#[inline]");
+        assert_eq!(result.errors[0].code().to_string(), "E1001");
+    }
+
+    #[test]
+    fn interpolated_attribute_argument() {
+        let (_, result) = parse_src("#[deprecated(\"use {g}\")] fn f() {}");
+        assert_eq!(result.errors[0].code().to_string(), "E1002");
+        assert!(matches!(result.ast[0], Statement::Error(_)));
+    }
+}
@@ -0,0 +1,210 @@
+#[cfg(test)]
+mod blocks {
+    use carbide_lexer::{lexer::CarbideLexer, operators::BinaryOperators};
+    use carbide_parser::{
+        nodes::{Block, Expression, LiteralValue, MapEntry, Pattern, Statement, Visibility},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn int(value: i64) -> Expression {
+        Expression::Literal(LiteralValue::Int(value))
+    }
+
+    fn block(statements: Vec<Statement>, tail: Option<Expression>) -> Block {
+        Block {
+            statements,
+            tail: tail.map(Box::new),
+        }
+    }
+
+    /// Parse `src` and get the initializer of its first `let`
+    fn initializer(src: &str) -> Expression {
+        let (_, result) = parse_src(src);
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        match result.ast.into_iter().next() {
+            Some(Statement::LetDeclaration {
+                initializer: Some(initializer),
+                ..
+            }) => initializer,
+            other => panic!("Expected a let declaration, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn if_expression() {
+        assert_eq!(
+            initializer("let y = if a { 1 } else { 2 };"),
+            Expression::If {
+                condition: Box::new(Expression::identifier("a", 11..12)),
+                then_branch: block(vec![], Some(int(1))),
+                else_branch: Some(Box::new(Expression::Block(block(vec![], Some(int(2)))))),
+            }
+        );
+    }
+
+    #[test]
+    fn else_if() {
+        let Expression::If { else_branch, .. } =
+            initializer("let y = if a { 1 } else if b { 2 } else { 3 };")
+        else {
+            panic!("Expected an if expression");
+        };
+        assert!(matches!(
+            else_branch.as_deref(),
+            Some(Expression::If {
+                else_branch: Some(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn block_expression() {
+        assert_eq!(
+            initializer("let z = { let t = f(); t * 2 };"),
+            Expression::Block(block(
+                vec![Statement::LetDeclaration {
+                    attributes: Vec::new(),
+                    mutable: false,
+                    pattern: Pattern::binding("t", 14..15),
+                    type_annotation: None,
+                    initializer: Some(Expression::Call {
+                        callee: Box::new(Expression::identifier("f", 18..19)),
+                        arguments: vec![],
+                    }),
+                    span: 14..15,
+                }],
+                Some(Expression::BinaryOp {
+                    left: Box::new(Expression::identifier("t", 23..24)),
+                    operator: BinaryOperators::Star,
+                    right: Box::new(int(2)),
+                }),
+            ))
+        );
+    }
+
+    #[test]
+    fn block_expression_starting_with_expression() {
+        assert_eq!(
+            initializer("let z = { f(); 1 };"),
+            Expression::Block(block(
+                vec![Statement::Expression(Expression::Call {
+                    callee: Box::new(Expression::identifier("f", 10..11)),
+                    arguments: vec![],
+                })],
+                Some(int(1)),
+            ))
+        );
+        assert_eq!(
+            initializer("let z = { x };"),
+            Expression::Block(block(vec![], Some(Expression::identifier("x", 10..11))))
+        );
+    }
+
+    #[test]
+    fn block_without_tail() {
+        assert_eq!(
+            initializer("let z = { f(); };"),
+            Expression::Block(block(
+                vec![Statement::Expression(Expression::Call {
+                    callee: Box::new(Expression::identifier("f", 10..11)),
+                    arguments: vec![],
+                })],
+                None,
+            ))
+        );
+    }
+
+    #[test]
+    fn map_still_parses() {
+        assert_eq!(
+            initializer("let m = { a + 1: 2, };"),
+            Expression::Map(vec![MapEntry {
+                key: Expression::BinaryOp {
+                    left: Box::new(Expression::identifier("a", 10..11)),
+                    operator: BinaryOperators::Plus,
+                    right: Box::new(int(1)),
+                },
+                value: int(2),
+            }])
+        );
+    }
+
+    #[test]
+    fn nested_block_tail() {
+        assert_eq!(
+            initializer("let z = { { 1 } };"),
+            Expression::Block(block(
+                vec![],
+                Some(Expression::Block(block(vec![], Some(int(1)))))
+            ))
+        );
+    }
+
+    #[test]
+    fn if_statement_without_semicolon() {
+        let (_, result) = parse_src("if a { f(); } else { g(); } (1, 2);");
+        assert!(result.is_ok());
+        assert_eq!(result.ast.len(), 2);
+        assert!(matches!(
+            result.ast[0],
+            Statement::Expression(Expression::If { .. })
+        ));
+    }
+
+    #[test]
+    fn function_returns_tail() {
+        let (_, result) = parse_src("fn double(x: int) -> int { let y = x; y * 2 }");
+        assert!(result.is_ok());
+        let Some(Statement::FunctionDeclaration {
+            visibility: Visibility::Private,
+            body,
+            ..
+        }) = result.ast.first()
+        else {
+            panic!("Expected a function declaration");
+        };
+        assert!(matches!(
+            body.statements.as_slice(),
+            [Statement::LetDeclaration { .. }]
+        ));
+        assert!(matches!(
+            body.tail.as_deref(),
+            Some(Expression::BinaryOp { .. })
+        ));
+    }
+
+    #[test]
+    fn function_tail_if() {
+        let (_, result) = parse_src("fn sign(x: int) -> int { if x < 0 { 0 } else { 1 } }");
+        assert!(result.is_ok());
+        assert!(matches!(
+            result.ast.first(),
+            Some(Statement::FunctionDeclaration { body, .. })
+                if body.statements.is_empty()
+                    && matches!(body.tail.as_deref(), Some(Expression::If { .. }))
+        ));
+    }
+
+    #[test]
+    fn missing_semicolon_before_tail() {
+        let (_, result) = parse_src("let z = { f() g() };");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn else_without_block() {
+        let (_, result) = parse_src("let y = if a { 1 } else 2;");
+        assert!(!result.is_ok());
+    }
+}
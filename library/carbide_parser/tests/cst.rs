@@ -0,0 +1,200 @@
+#[cfg(test)]
+mod cst {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        cst::{
+            AstNode, SyntaxKind, SyntaxNode,
+            views::{ArgList, Block, CallExpr, FnDecl, LetStmt, SourceFile},
+        },
+        nodes::Visibility,
+        parser::{CarbideParser, CstParseResult},
+    };
+
+    /// Sources covering every kind of node, with comments and errors mixed in
+    const CORPUS: &[&str] = &[
+        "// leading comment\nlet x = 1;   \n",
+        "pub fn add(a: int, /* inline */ mut b: int) -> int {\n    a + b // sum\n}",
+        "fn f() { let y: [int]? = [1, 2,]; y[0] = !y.done; return; }",
+        "const LIMIT: {string: (int, float)} = { \"a\": (1, 2.5), b: (0x1F, 0b10) };",
+        "pub struct Point { x: float, y: float }\nlet p = Point { x: 1.0, y };\nlet q = Point { ..p };",
+        "enum Shape { Circle(float), Rect { w: float, h: float }, Empty }",
+        "import util.strings.{trim, split};\nimport math;",
+        "let s = match shape {\n    Shape::Circle(r) if r > 0.0 => r,\n    Shape::Rect { w, h: (a, b), .. } => w,\n    (_) => 0.0,\n};",
+        "for (let i = 0; i < 10; i = i + 1) { if i == 5 { continue; } else if i == 6 { break; } }",
+        "for (;;) { { 1 } }\nwhile ready { step() }",
+        "let f = |a: int, b| -> int => { a * b };\nlet g = || => 1;\nlet h = fn(x) => x;",
+        "let t = (1,);\nlet u = (1);\nlet e = {};\nlet v = { let w = 2; w };\n{ g(); }",
+        "#[inline]\n#[deprecated(\"use g\", 2)] pub fn f() { #[test] let x = 1; }",
+        "fn first<T: Ord + Show, U,>(xs: [T]) -> T { xs[0] }\nlet x = util::first::<[int], U>([1]);",
+        "let (a, [b, ..rest]) = pair;\nlet P { x, .. } = p;\nfn f(mut (x, _): (int, int), [.., z]: [int]) {}",
+        "pub trait Shape {\n    fn area(self) -> float;\n    fn name(self) { \"shape\" }\n}\nimpl Shape for Circle { fn area(self) -> float { self.r * self.r } }\nimpl Circle {}\nlet a = c.area();",
+        "let msg = \"hi {name}!\";\nlet fun: (int, string) -> bool = check;",
+        "fn f() { let = 1; g(); }\nlet z = 1 +;\n}\nlet w = 2",
+        "f(1, let, 3);\nf(g(1 +), [2]);\nlet z = { a b };",
+        "let x = 1 /* unterminated",
+    ];
+
+    fn parse_cst(src: &str) -> CstParseResult {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+
+        let mut parser = CarbideParser::new(result.tokens);
+        parser.parse_cst(src)
+    }
+
+    /// Find the first node of `T` below `node`
+    fn find<T: AstNode>(node: &SyntaxNode) -> Option<T> {
+        node.children()
+            .find_map(|child| T::cast(child.clone()).or_else(|| find(&child)))
+    }
+
+    #[test]
+    fn lossless() {
+        for src in CORPUS {
+            let result = parse_cst(src);
+            assert_eq!(result.syntax().text(), *src);
+            assert_eq!(result.syntax().text_range(), 0..src.len() as u64);
+        }
+    }
+
+    #[test]
+    fn lowers_to_the_ast() {
+        for src in CORPUS {
+            let tokens = CarbideLexer::from_src(src).lex().tokens;
+            let ast = CarbideParser::new(tokens).parse();
+            let cst = parse_cst(src);
+
+            assert_eq!(cst.lower(), ast.ast, "{src}\n{}", cst.syntax().debug_tree());
+            assert_eq!(cst.errors, ast.errors, "{src}");
+        }
+    }
+
+    #[test]
+    fn function_accessors() {
+        let result = parse_cst("pub fn add(a: int, mut b: int) -> int { a + b }");
+        let root = SourceFile::cast(result.syntax()).expect("Expected a source file");
+
+        let function = root
+            .statements()
+            .find_map(FnDecl::cast)
+            .expect("Expected a function");
+        assert_eq!(function.visibility(), Visibility::Public);
+        assert_eq!(
+            function.name().map(|name| name.text().to_string()),
+            Some("add".to_string())
+        );
+        assert_eq!(
+            function.return_type().map(|ty| ty.text()),
+            Some("int".to_string())
+        );
+
+        let params: Vec<_> = function
+            .param_list()
+            .expect("Expected parameters")
+            .params()
+            .map(|param| (param.is_mutable(), param.syntax().text()))
+            .collect();
+        assert_eq!(
+            params,
+            vec![
+                (false, "a: int".to_string()),
+                (true, "mut b: int".to_string())
+            ]
+        );
+
+        let body = function.body().expect("Expected a body");
+        assert_eq!(body.statements().count(), 0);
+        assert_eq!(
+            body.tail().map(|tail| tail.kind()),
+            Some(SyntaxKind::BinaryExpr)
+        );
+    }
+
+    #[test]
+    fn let_accessors() {
+        let result = parse_cst("let mut total: int = price * 2;");
+        let statement = find::<LetStmt>(&result.syntax()).expect("Expected a let statement");
+
+        assert!(statement.is_mutable());
+        let pattern = statement.pattern().expect("Expected a pattern");
+        assert_eq!(pattern.kind(), SyntaxKind::BindingPat);
+        assert_eq!(
+            (pattern.text(), pattern.text_range()),
+            ("total".to_string(), 8..13)
+        );
+        assert_eq!(
+            statement.type_annotation().map(|ty| ty.kind()),
+            Some(SyntaxKind::NamedType)
+        );
+
+        let initializer = statement.initializer().expect("Expected an initializer");
+        assert_eq!(initializer.kind(), SyntaxKind::BinaryExpr);
+        assert_eq!(initializer.text(), "price * 2");
+        assert_eq!(initializer.text_range(), 21..30);
+    }
+
+    #[test]
+    fn trivia_stays_between_nodes() {
+        let result = parse_cst("let x = 1; // one\n\nlet y = 2;");
+        let root = result.syntax();
+
+        let kinds: Vec<_> = root
+            .children_with_tokens()
+            .map(|child| child.kind())
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                SyntaxKind::LetStmt,
+                SyntaxKind::Whitespace,
+                SyntaxKind::Comment,
+                SyntaxKind::Whitespace,
+                SyntaxKind::LetStmt,
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_block_is_tail() {
+        let result = parse_cst("let z = { { 1 } };");
+        let outer = find::<Block>(&result.syntax()).expect("Expected a block");
+
+        assert_eq!(outer.statements().count(), 0);
+        assert_eq!(
+            outer.tail().map(|tail| tail.text()),
+            Some("{ 1 }".to_string())
+        );
+    }
+
+    #[test]
+    fn error_nodes_cover_skipped_tokens() {
+        let result = parse_cst("let = 1;\nf(1, let, 3);");
+        assert_eq!(result.errors.len(), 2);
+
+        let root = result.syntax();
+        let statements: Vec<_> = root.children().collect();
+        assert_eq!(statements[0].kind(), SyntaxKind::Error);
+        assert_eq!(statements[0].text(), "let = 1;");
+
+        let arguments = find::<ArgList>(&statements[1]).expect("Expected arguments");
+        let kinds: Vec<_> = arguments.args().map(|arg| arg.kind()).collect();
+        assert_eq!(
+            kinds,
+            vec![SyntaxKind::Literal, SyntaxKind::Error, SyntaxKind::Literal]
+        );
+    }
+
+    #[test]
+    fn parents_and_offsets() {
+        let result = parse_cst("fn f() {\n    g(1);\n}");
+        let call = find::<CallExpr>(&result.syntax()).expect("Expected a call");
+
+        assert_eq!(call.syntax().text_range(), 13..17);
+        let parent = call.syntax().parent().expect("Expected a parent");
+        assert_eq!(parent.kind(), SyntaxKind::ExprStmt);
+        assert_eq!(
+            parent.parent().map(|node| node.kind()),
+            Some(SyntaxKind::Block)
+        );
+    }
+}
@@ -2,7 +2,7 @@
 mod delimiters {
     use carbide_lexer::lexer::CarbideLexer;
     use carbide_parser::{
-        nodes::{Expression, LiteralValue, Statement},
+        nodes::{Expression, LiteralValue, Pattern, Statement},
         parser::CarbideParser,
     };
 
@@ -23,9 +23,12 @@ mod delimiters {
         assert_eq!(
             result.ast,
             vec![Statement::Block(vec![Statement::LetDeclaration {
-                name: "my_var".into(),
+                attributes: Vec::new(),
+                mutable: false,
+                pattern: Pattern::binding("my_var", 6..12),
                 initializer: Some(Expression::Literal(LiteralValue::Int(0))),
-                type_annotation: None
+                type_annotation: None,
+                span: 6..12,
             }])]
         );
     }
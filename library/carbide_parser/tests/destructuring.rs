@@ -0,0 +1,188 @@
+#[cfg(test)]
+mod destructuring {
+    use carbide_errors::error::CarbideError;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Expression, FieldPattern, Parameter, Pattern, Statement, Type},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn let_pattern(src: &str) -> (Pattern, std::ops::Range<u64>) {
+        let (_, result) = parse_src(src);
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        match result.ast.into_iter().next() {
+            Some(Statement::LetDeclaration { pattern, span, .. }) => (pattern, span),
+            other => panic!("Expected a let declaration, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn tuple_let() {
+        assert_eq!(
+            let_pattern("let (x, y) = pair;"),
+            (
+                Pattern::Tuple(vec![
+                    Pattern::binding("x", 5..6),
+                    Pattern::binding("y", 8..9)
+                ]),
+                4..10
+            )
+        );
+    }
+
+    #[test]
+    fn array_let_with_rest() {
+        assert_eq!(
+            let_pattern("let [a, b, ..rest] = xs;"),
+            (
+                Pattern::Array(vec![
+                    Pattern::binding("a", 5..6),
+                    Pattern::binding("b", 8..9),
+                    Pattern::Rest {
+                        name: Some("rest".to_string()),
+                        span: 13..17,
+                    },
+                ]),
+                4..18
+            )
+        );
+        assert_eq!(
+            let_pattern("let [first, .., _] = xs;").0,
+            Pattern::Array(vec![
+                Pattern::binding("first", 5..10),
+                Pattern::Rest {
+                    name: None,
+                    span: 12..14,
+                },
+                Pattern::Wildcard,
+            ])
+        );
+        assert_eq!(let_pattern("let [] = xs;").0, Pattern::Array(vec![]));
+    }
+
+    #[test]
+    fn struct_let() {
+        assert_eq!(
+            let_pattern("let Point { x, y: (a, _), .. } = p;").0,
+            Pattern::Struct {
+                name: "Point".to_string(),
+                fields: vec![
+                    FieldPattern {
+                        name: "x".to_string(),
+                        pattern: Pattern::binding("x", 12..13),
+                    },
+                    FieldPattern {
+                        name: "y".to_string(),
+                        pattern: Pattern::Tuple(vec![
+                            Pattern::binding("a", 19..20),
+                            Pattern::Wildcard,
+                        ]),
+                    },
+                ],
+                rest: true,
+            }
+        );
+    }
+
+    #[test]
+    fn destructuring_parameters() {
+        let (_, result) = parse_src("fn f(mut (a, b): (int, int), Point { x, .. }: Point) {}");
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        let Statement::FunctionDeclaration { parameters, .. } = &result.ast[0] else {
+            panic!("Expected a function declaration, found {:?}", result.ast[0]);
+        };
+        assert_eq!(
+            parameters,
+            &[
+                Parameter {
+                    mutable: true,
+                    pattern: Pattern::Tuple(vec![
+                        Pattern::binding("a", 10..11),
+                        Pattern::binding("b", 13..14),
+                    ]),
+                    type_annotation: Some(Type::Tuple(vec![
+                        Type::named("int"),
+                        Type::named("int")
+                    ])),
+                    span: 9..15,
+                },
+                Parameter {
+                    mutable: false,
+                    pattern: Pattern::Struct {
+                        name: "Point".to_string(),
+                        fields: vec![FieldPattern {
+                            name: "x".to_string(),
+                            pattern: Pattern::binding("x", 37..38),
+                        }],
+                        rest: true,
+                    },
+                    type_annotation: Some(Type::named("Point")),
+                    span: 29..44,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn destructuring_lambda_parameters() {
+        let (_, result) = parse_src("let f = |[x, ..], (y, _)| => x + y;");
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        let Statement::LetDeclaration {
+            initializer: Some(Expression::Lambda { parameters, .. }),
+            ..
+        } = &result.ast[0]
+        else {
+            panic!("Expected a lambda, found {:?}", result.ast[0]);
+        };
+        let patterns: Vec<_> = parameters.iter().map(|p| &p.pattern).collect();
+        assert_eq!(
+            patterns,
+            [
+                &Pattern::Array(vec![
+                    Pattern::binding("x", 10..11),
+                    Pattern::Rest {
+                        name: None,
+                        span: 13..15,
+                    },
+                ]),
+                &Pattern::Tuple(vec![Pattern::binding("y", 19..20), Pattern::Wildcard]),
+            ]
+        );
+    }
+
+    #[test]
+    fn bindings_in_order() {
+        let (pattern, _) = let_pattern("let (a, [b, ..rest], P { x, y: Some(z) }) = v;");
+        assert_eq!(pattern.bindings(), ["a", "b", "rest", "x", "z"]);
+    }
+
+    #[test]
+    fn multiple_rest_patterns() {
+        let (_, result) = parse_src("let [a, .., b, ..rest] = xs;");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].code().to_string(), "E1060");
+        assert_eq!(
+            result.errors[0].to_string(),
+            "Array pattern has more than one `..` at [1:16]"
+        );
+    }
+
+    #[test]
+    fn rest_outside_array() {
+        for src in ["let (a, ..) = t;", "let .. = t;", "fn f(..) {}"] {
+            let (_, result) = parse_src(src);
+            assert_eq!(result.errors.len(), 1, "{src}");
+            assert_eq!(result.errors[0].code().to_string(), "E1002", "{src}");
+        }
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod eof {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{errors::CarbideParserError, parser::CarbideParser};
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    #[test]
+    fn function_without_body() {
+        let (_, result) = parse_src("fn f()");
+        assert!(matches!(
+            result.errors.as_slice(),
+            [error] if matches!(
+                &**error,
+                CarbideParserError::UnexpectedEOF { location, .. } if location.offset == 6
+            )
+        ));
+    }
+
+    #[test]
+    fn function_with_return_type_without_body() {
+        let (_, result) = parse_src("fn f() -> int");
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::UnexpectedEOF { .. }
+        ));
+    }
+
+    #[test]
+    fn empty_source() {
+        let (_, result) = parse_src("");
+        assert!(result.is_ok());
+        assert!(result.ast.is_empty());
+    }
+
+    #[test]
+    fn truncated_statements() {
+        for src in [
+            "fn",
+            "fn f(",
+            "fn f(a:",
+            "let",
+            "let x",
+            "let x =",
+            "let x: ",
+            "const X: int =",
+            "return",
+            "f(",
+            "f(1,",
+            "a.",
+            "a[",
+            "[1,",
+            "(1,",
+            "{",
+            "{ let x = 1;",
+            "pub",
+            "import",
+            "import a.",
+            "import a.{",
+            "struct",
+            "struct S {",
+            "struct S { x:",
+            "enum E {",
+            "enum E { A(",
+            "match a",
+            "match a {",
+            "match a { _ =>",
+            "if",
+            "if a",
+            "if a {} else",
+            "while",
+            "while a",
+            "for (",
+            "for (;;",
+            "|x| =>",
+            "S { x:",
+        ] {
+            let (_, result) = parse_src(src);
+            assert!(result.has_errors(), "Expected errors for '{src}'");
+        }
+    }
+
+    #[test]
+    fn token_summary_is_owned() {
+        let error = {
+            let src = String::from("fn 1() {}");
+            let (_, result) = parse_src(&src);
+            result.errors.into_iter().next()
+        };
+
+        // The error outlives the source it was parsed from
+        assert!(matches!(
+            error.as_deref(),
+            Some(CarbideParserError::UnexpectedToken { found, .. })
+                if found.text == "1" && found.span == (3..4) && found.kind == "IntLiteral(1)"
+        ));
+    }
+}
@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod expected {
+    use carbide_errors::reporter::ErrorReporter;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        errors::{CarbideParserError, Expected, ExpectedSet},
+        parser::{CarbideParser, ParseResult},
+    };
+
+    fn parse_src(src: &str) -> ParseResult {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        CarbideParser::new(result.tokens).parse()
+    }
+
+    /// Parse `src`, expecting a single error that lists what was expected
+    fn expected(src: &str) -> ExpectedSet {
+        let result = parse_src(src);
+        let [error] = result.errors.as_slice() else {
+            panic!("{src}: expected one error, found {:?}", result.errors);
+        };
+        error
+            .expected()
+            .unwrap_or_else(|| panic!("{src}: expected a set, found {error:?}"))
+            .clone()
+    }
+
+    #[test]
+    fn call_arguments() {
+        let set = expected("f(a;");
+        assert_eq!(set.to_string(), "one of `,`, `)`, or an operator");
+        assert_eq!(
+            set.iter().copied().collect::<Vec<_>>(),
+            [
+                Expected::Token(","),
+                Expected::Token(")"),
+                Expected::Kind("an operator"),
+            ]
+        );
+        assert_eq!(set.tokens().collect::<Vec<_>>(), [",", ")"]);
+    }
+
+    #[test]
+    fn messages() {
+        for (src, message) in [
+            (
+                "let x = 1 2;",
+                "Expected `;` or an operator, but found `2` (IntLiteral(2)) at 10..11",
+            ),
+            (
+                "let x: int 5;",
+                "Expected `=` or `;`, but found `5` (IntLiteral(5)) at 11..12",
+            ),
+            (
+                "fn f(a: int b: int) {}",
+                "Expected `,` or `)`, but found `b` (Identifier(\"b\")) at 12..13",
+            ),
+            (
+                "let = 3;",
+                "Expected a pattern, but found `=` (BinaryOperator(Eq)) at 4..5",
+            ),
+            (
+                "a.1;",
+                "Expected a property name, but found `1` (IntLiteral(1)) at 2..3",
+            ),
+        ] {
+            let result = parse_src(src);
+            assert_eq!(result.errors.len(), 1, "{src}: {:?}", result.errors);
+            assert_eq!(result.errors[0].to_string(), message);
+        }
+    }
+
+    #[test]
+    fn separators_and_closers() {
+        for (src, tokens) in [
+            ("let x = [1 2];", vec![",", "]"]),
+            ("let p = Point { x: 1 y: 2 };", vec![",", "}"]),
+            ("let (a b) = t;", vec![",", ")"]),
+            ("match x { 1 => 2 3 => 4 }", vec![",", "}"]),
+            (
+                "pub let x = 1;",
+                vec!["fn", "struct", "enum", "const", "trait"],
+            ),
+        ] {
+            assert_eq!(expected(src).tokens().collect::<Vec<_>>(), tokens, "{src}");
+        }
+    }
+
+    #[test]
+    fn end_of_input() {
+        let result = parse_src("f(a");
+        assert!(matches!(
+            result.errors.as_slice(),
+            [error] if matches!(
+                &**error,
+                CarbideParserError::UnexpectedEOF { location, expected }
+                    if location.offset == 3 && expected.tokens().eq([",", ")"])
+            )
+        ));
+
+        assert_eq!(
+            expected("#[inline").tokens().collect::<Vec<_>>(),
+            ["(", "]"]
+        );
+        assert!(expected("let x = ").contains(&Expected::Kind("an expression")));
+    }
+
+    #[test]
+    fn set_is_deduplicated_and_ordered() {
+        let set: ExpectedSet = [
+            Expected::Kind("an operator"),
+            Expected::Token(")"),
+            Expected::Token(","),
+            Expected::Token(")"),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(set.len(), 3);
+        assert_eq!(set.to_string(), "one of `)`, `,`, or an operator");
+        assert_eq!(ExpectedSet::new().to_string(), "more input");
+    }
+
+    #[test]
+    fn only_for_unexpected_input() {
+        let result = parse_src("let x = 1\nlet y = 2;");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.errors[0].expected(), None);
+    }
+
+    #[test]
+    fn report() {
+        let src = "f(a;";
+        let result = parse_src(src);
+
+        let mut reporter = ErrorReporter::new();
+        reporter.add_source("test.cb", src);
+        let report = reporter
+            .format_error("test.cb", &*result.errors[0])
+            .expect("Expected formatting to succeed");
+        assert!(report.contains("E1002"), "{report}");
+        assert!(
+            report.contains("Expected one of `,`, `)`, or an operator, found `;`"),
+            "{report}"
+        );
+    }
+}
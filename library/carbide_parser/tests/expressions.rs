@@ -0,0 +1,284 @@
+#[cfg(test)]
+mod expressions {
+    use carbide_lexer::{
+        lexer::CarbideLexer,
+        operators::{BinaryOperators, UnaryOperators},
+    };
+    use carbide_parser::{
+        errors::CarbideParserError,
+        nodes::{Expression, LiteralValue, Statement, StringPart},
+        parser::CarbideParser,
+    };
+
+    fn parse_expr(src: &str) -> Expression {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        assert!(
+            result.is_ok(),
+            "Parser failed for '{src}': {:?}",
+            result.errors
+        );
+
+        match result.ast.into_iter().next() {
+            Some(Statement::Expression(expr)) => expr,
+            other => panic!("Expected an expression statement, found {other:?}"),
+        }
+    }
+
+    #[allow(clippy::unnecessary_box_returns)]
+    fn ident(name: &str, span: std::ops::Range<u64>) -> Box<Expression> {
+        Box::new(Expression::identifier(name, span))
+    }
+
+    #[allow(clippy::unnecessary_box_returns)]
+    fn int(value: i64) -> Box<Expression> {
+        Box::new(Expression::Literal(LiteralValue::Int(value)))
+    }
+
+    #[allow(clippy::unnecessary_box_returns)]
+    fn binary(
+        left: Box<Expression>,
+        operator: BinaryOperators,
+        right: Box<Expression>,
+    ) -> Box<Expression> {
+        Box::new(Expression::BinaryOp {
+            left,
+            operator,
+            right,
+        })
+    }
+
+    #[test]
+    fn factor_binds_tighter_than_term() {
+        assert_eq!(
+            parse_expr("1 + 2 * 3;"),
+            *binary(
+                int(1),
+                BinaryOperators::Plus,
+                binary(int(2), BinaryOperators::Star, int(3))
+            )
+        );
+    }
+
+    #[test]
+    fn term_is_left_associative() {
+        assert_eq!(
+            parse_expr("1 - 2 - 3;"),
+            *binary(
+                binary(int(1), BinaryOperators::Minus, int(2)),
+                BinaryOperators::Minus,
+                int(3)
+            )
+        );
+    }
+
+    #[test]
+    fn power_is_right_associative() {
+        assert_eq!(
+            parse_expr("2 ** 3 ** 2;"),
+            *binary(
+                int(2),
+                BinaryOperators::StarStar,
+                binary(int(3), BinaryOperators::StarStar, int(2))
+            )
+        );
+    }
+
+    #[test]
+    fn power_binds_tighter_than_factor() {
+        assert_eq!(
+            parse_expr("2 * 3 ** 2 % 5;"),
+            *binary(
+                binary(
+                    int(2),
+                    BinaryOperators::Star,
+                    binary(int(3), BinaryOperators::StarStar, int(2))
+                ),
+                BinaryOperators::Percent,
+                int(5)
+            )
+        );
+    }
+
+    #[test]
+    fn assignment_is_right_associative() {
+        assert_eq!(
+            parse_expr("a = b = 1;"),
+            Expression::Assignment {
+                target: ident("a", 0..1),
+                value: Box::new(Expression::Assignment {
+                    target: ident("b", 4..5),
+                    value: int(1),
+                    span: 4..9,
+                }),
+                span: 0..9,
+            }
+        );
+    }
+
+    #[test]
+    fn logical_and_comparison() {
+        assert_eq!(
+            parse_expr("a < 1 && b >= 2 || c;"),
+            *binary(
+                binary(
+                    binary(ident("a", 0..1), BinaryOperators::Lt, int(1)),
+                    BinaryOperators::AndAnd,
+                    binary(ident("b", 9..10), BinaryOperators::GtEq, int(2))
+                ),
+                BinaryOperators::OrOr,
+                ident("c", 19..20)
+            )
+        );
+    }
+
+    #[test]
+    fn comparison_binds_tighter_than_equality() {
+        assert_eq!(
+            parse_expr("a <= b == c > d;"),
+            *binary(
+                binary(ident("a", 0..1), BinaryOperators::LtEq, ident("b", 5..6)),
+                BinaryOperators::EqEq,
+                binary(ident("c", 10..11), BinaryOperators::Gt, ident("d", 14..15))
+            )
+        );
+    }
+
+    #[test]
+    fn prefix_binds_tighter_than_binary() {
+        assert_eq!(
+            parse_expr("!a == b;"),
+            *binary(
+                Box::new(Expression::UnaryOp {
+                    operator: UnaryOperators::Not,
+                    operand: ident("a", 1..2),
+                }),
+                BinaryOperators::EqEq,
+                ident("b", 6..7)
+            )
+        );
+    }
+
+    #[test]
+    fn postfix_binds_tighter_than_prefix() {
+        assert_eq!(
+            parse_expr("!f();"),
+            Expression::UnaryOp {
+                operator: UnaryOperators::Not,
+                operand: Box::new(Expression::Call {
+                    callee: ident("f", 1..2),
+                    arguments: vec![],
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn chained_postfix() {
+        assert_eq!(
+            parse_expr("a.b[0](x);"),
+            Expression::Call {
+                callee: Box::new(Expression::Index {
+                    target: Box::new(Expression::MemberAccess {
+                        target: ident("a", 0..1),
+                        member: "b".to_string(),
+                        span: 2..3,
+                    }),
+                    index: int(0),
+                }),
+                arguments: vec![*ident("x", 7..8)],
+            }
+        );
+    }
+
+    #[test]
+    fn grouping_overrides_precedence() {
+        assert_eq!(
+            parse_expr("(1 + 2) * 3;"),
+            *binary(
+                Box::new(Expression::Grouped(binary(
+                    int(1),
+                    BinaryOperators::Plus,
+                    int(2)
+                ))),
+                BinaryOperators::Star,
+                int(3)
+            )
+        );
+    }
+
+    #[test]
+    fn dangling_operator() {
+        let mut lexer = CarbideLexer::from_src("1 * ;");
+        let mut parser = CarbideParser::new(lexer.lex().tokens);
+        assert!(!parser.parse().is_ok());
+    }
+
+    #[test]
+    fn interpolated_identifier_span() {
+        assert_eq!(
+            parse_expr("\"a {x}\";"),
+            Expression::InterpolatedString {
+                parts: vec![
+                    StringPart::Text("a ".to_string()),
+                    StringPart::Expression(ident("x", 4..5)),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn interpolated_error_span() {
+        let mut lexer = CarbideLexer::from_src("\"{break}\";");
+        let mut parser = CarbideParser::new(lexer.lex().tokens);
+        let result = parser.parse();
+        assert!(matches!(
+            result.errors.as_slice(),
+            [error] if matches!(
+                error.as_ref(),
+                CarbideParserError::UnexpectedToken { found, .. } if found.span == (2..7)
+            )
+        ));
+    }
+
+    #[test]
+    fn interpolation_errors_are_reported() {
+        let mut lexer = CarbideLexer::from_src("\"{1 = 2}\";");
+        let mut parser = CarbideParser::new(lexer.lex().tokens);
+        let result = parser.parse();
+        assert!(matches!(
+            result.errors.as_slice(),
+            [error] if matches!(
+                error.as_ref(),
+                CarbideParserError::InvalidAssignmentTarget(span) if *span == (2..3)
+            )
+        ));
+    }
+
+    #[test]
+    fn tokens_left_in_interpolation() {
+        let mut lexer = CarbideLexer::from_src("\"{a b c}\"; x;");
+        let mut parser = CarbideParser::new(lexer.lex().tokens);
+        let result = parser.parse();
+        assert!(matches!(
+            result.errors.as_slice(),
+            [error] if matches!(
+                error.as_ref(),
+                CarbideParserError::UnexpectedToken { found, .. } if found.text == "b"
+            )
+        ));
+        assert_eq!(
+            result.ast,
+            vec![
+                Statement::Expression(Expression::InterpolatedString {
+                    parts: vec![StringPart::Expression(Box::new(Expression::Error(2..7)))],
+                }),
+                Statement::Expression(*ident("x", 11..12)),
+            ]
+        );
+    }
+}
@@ -2,7 +2,7 @@
 mod functions {
     use carbide_lexer::lexer::CarbideLexer;
     use carbide_parser::{
-        nodes::{Parameter, Statement, Type},
+        nodes::{Block, Parameter, Pattern, Statement, Type, Visibility},
         parser::CarbideParser,
     };
 
@@ -23,10 +23,13 @@ mod functions {
         assert_eq!(
             result.ast,
             vec![Statement::FunctionDeclaration {
+                attributes: Vec::new(),
+                visibility: Visibility::Private,
                 name: "my_func".to_string(),
+                type_parameters: Vec::new(),
                 return_type: None,
                 parameters: vec![],
-                body: vec![]
+                body: Block::default()
             }]
         );
     }
@@ -38,19 +41,26 @@ mod functions {
         assert_eq!(
             result.ast,
             vec![Statement::FunctionDeclaration {
+                attributes: Vec::new(),
+                visibility: Visibility::Private,
                 name: "add".to_string(),
+                type_parameters: Vec::new(),
                 return_type: None,
                 parameters: vec![
                     Parameter {
-                        name: "a".to_string(),
+                        mutable: false,
+                        pattern: Pattern::binding("a", 7..8),
                         type_annotation: Some(Type::named("int")),
+                        span: 7..8,
                     },
                     Parameter {
-                        name: "b".to_string(),
-                        type_annotation: Some(Type::named("int"))
+                        mutable: false,
+                        pattern: Pattern::binding("b", 15..16),
+                        type_annotation: Some(Type::named("int")),
+                        span: 15..16,
                     }
                 ],
-                body: vec![]
+                body: Block::default()
             }]
         );
     }
@@ -62,20 +72,38 @@ mod functions {
         assert_eq!(
             result.ast,
             vec![Statement::FunctionDeclaration {
+                attributes: Vec::new(),
+                visibility: Visibility::Private,
                 name: "add".to_string(),
+                type_parameters: Vec::new(),
                 return_type: Some(Type::named("int")),
                 parameters: vec![
                     Parameter {
-                        name: "a".to_string(),
+                        mutable: false,
+                        pattern: Pattern::binding("a", 7..8),
                         type_annotation: Some(Type::named("int")),
+                        span: 7..8,
                     },
                     Parameter {
-                        name: "b".to_string(),
-                        type_annotation: Some(Type::named("int"))
+                        mutable: false,
+                        pattern: Pattern::binding("b", 15..16),
+                        type_annotation: Some(Type::named("int")),
+                        span: 15..16,
                     }
                 ],
-                body: vec![]
+                body: Block::default()
             }]
         );
     }
+
+    #[test]
+    fn mutable_parameter() {
+        let (_, result) = parse_src(r"fn push(mut list: [int], x: int) {}");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::FunctionDeclaration { parameters, .. }
+                if parameters[0].mutable && parameters[0].span == (12..16) && !parameters[1].mutable
+        ));
+    }
 }
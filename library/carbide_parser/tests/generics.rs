@@ -0,0 +1,158 @@
+#[cfg(test)]
+mod generics {
+    use carbide_errors::error::CarbideError;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{
+            Block, Expression, LiteralValue, Parameter, Pattern, Statement, Type, TypeParameter,
+            Visibility,
+        },
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn initializer(src: &str) -> Expression {
+        let (_, result) = parse_src(src);
+        assert!(result.is_ok(), "{:?}", result.errors);
+        match &result.ast[0] {
+            Statement::LetDeclaration {
+                initializer: Some(initializer),
+                ..
+            } => initializer.clone(),
+            other => panic!("Expected a let with an initializer, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn generic_function() {
+        let (_, result) = parse_src("fn first<T>(xs: [T]) -> T { return xs[0]; }");
+        assert!(result.is_ok(), "{:?}", result.errors);
+        assert_eq!(
+            result.ast,
+            vec![Statement::FunctionDeclaration {
+                attributes: Vec::new(),
+                visibility: Visibility::Private,
+                name: "first".to_string(),
+                type_parameters: vec![TypeParameter {
+                    name: "T".to_string(),
+                    bounds: vec![],
+                    span: 9..10,
+                }],
+                parameters: vec![Parameter {
+                    mutable: false,
+                    pattern: Pattern::binding("xs", 12..14),
+                    type_annotation: Some(Type::Array(Box::new(Type::named("T")))),
+                    span: 12..14,
+                }],
+                return_type: Some(Type::named("T")),
+                body: Block {
+                    statements: vec![Statement::Return(Some(Expression::Index {
+                        target: Box::new(Expression::identifier("xs", 35..37)),
+                        index: Box::new(Expression::Literal(LiteralValue::Int(0))),
+                    }))],
+                    tail: None,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn type_parameter_bounds() {
+        let (_, result) = parse_src("pub fn max<T: Ord + Show, U,>(a: T, b: U) {}");
+        assert!(result.is_ok(), "{:?}", result.errors);
+        let Statement::FunctionDeclaration {
+            type_parameters, ..
+        } = &result.ast[0]
+        else {
+            panic!("Expected a function declaration, found {:?}", result.ast[0]);
+        };
+        assert_eq!(
+            type_parameters,
+            &[
+                TypeParameter {
+                    name: "T".to_string(),
+                    bounds: vec!["Ord".to_string(), "Show".to_string()],
+                    span: 11..12,
+                },
+                TypeParameter {
+                    name: "U".to_string(),
+                    bounds: vec![],
+                    span: 26..27,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn turbofish_call() {
+        assert_eq!(
+            initializer("let x = first::<int>([1]);"),
+            Expression::Call {
+                callee: Box::new(Expression::Generic {
+                    name: "first".to_string(),
+                    arguments: vec![Type::named("int")],
+                    span: 8..20,
+                }),
+                arguments: vec![Expression::Array(vec![Expression::Literal(
+                    LiteralValue::Int(1)
+                )])],
+            }
+        );
+    }
+
+    #[test]
+    fn turbofish_with_path_and_nested_types() {
+        assert_eq!(
+            initializer("let f = util::pair::<[int], Box<string>>;"),
+            Expression::Generic {
+                name: "util::pair".to_string(),
+                arguments: vec![
+                    Type::Array(Box::new(Type::named("int"))),
+                    Type::Generic {
+                        name: "Box".to_string(),
+                        arguments: vec![Type::named("string")],
+                    },
+                ],
+                span: 8..40,
+            }
+        );
+    }
+
+    #[test]
+    fn comparison_is_not_a_turbofish() {
+        assert!(matches!(
+            initializer("let x = a < b;"),
+            Expression::BinaryOp { .. }
+        ));
+    }
+
+    #[test]
+    fn empty_type_parameters() {
+        let (_, result) = parse_src("fn f<>(x: int) {}");
+        assert!(result.is_ok(), "{:?}", result.errors);
+    }
+
+    #[test]
+    fn invalid_type_parameters() {
+        for src in [
+            "fn f<T(x: T) {}",
+            "fn f<T: >(x: T) {}",
+            "fn f<int>(x: int) {}",
+            "f::<int(1);",
+            "f::<1>(1);",
+        ] {
+            let (_, result) = parse_src(src);
+            assert_eq!(result.errors.len(), 1, "{src}");
+            assert_eq!(result.errors[0].code().to_string(), "E1002", "{src}");
+        }
+    }
+}
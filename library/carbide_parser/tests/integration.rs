@@ -2,7 +2,7 @@
 mod integration {
     use carbide_lexer::{lexer::CarbideLexer, operators::BinaryOperators};
     use carbide_parser::{
-        nodes::{Expression, LiteralValue, Parameter, Statement, Type},
+        nodes::{Block, Expression, LiteralValue, Parameter, Pattern, Statement, Type, Visibility},
         parser::CarbideParser,
     };
 
@@ -34,34 +34,47 @@ mod integration {
             result.ast,
             vec![
                 Statement::FunctionDeclaration {
+                    attributes: Vec::new(),
+                    visibility: Visibility::Private,
                     name: "add_int".to_string(),
+                    type_parameters: Vec::new(),
                     return_type: Some(Type::named("int")),
                     parameters: vec![
                         Parameter {
-                            name: "a".to_string(),
-                            type_annotation: Some(Type::named("int"))
+                            mutable: false,
+                            pattern: Pattern::binding("a", 24..25),
+                            type_annotation: Some(Type::named("int")),
+                            span: 24..25,
                         },
                         Parameter {
-                            name: "b".to_string(),
-                            type_annotation: Some(Type::named("int"))
+                            mutable: false,
+                            pattern: Pattern::binding("b", 32..33),
+                            type_annotation: Some(Type::named("int")),
+                            span: 32..33,
                         }
                     ],
-                    body: vec![Statement::Return(Some(Expression::BinaryOp {
-                        left: Box::new(Expression::Identifier("a".to_string())),
-                        operator: BinaryOperators::Plus,
-                        right: Box::new(Expression::Identifier("b".to_string())),
-                    })),],
+                    body: Block {
+                        statements: vec![Statement::Return(Some(Expression::BinaryOp {
+                            left: Box::new(Expression::identifier("a", 72..73)),
+                            operator: BinaryOperators::Plus,
+                            right: Box::new(Expression::identifier("b", 76..77)),
+                        }))],
+                        tail: None,
+                    },
                 },
                 Statement::LetDeclaration {
-                    name: "c".to_string(),
+                    attributes: Vec::new(),
+                    mutable: false,
+                    pattern: Pattern::binding("c", 110..111),
                     type_annotation: None,
                     initializer: Some(Expression::Call {
-                        callee: Box::new(Expression::Identifier("add_int".to_string())),
+                        callee: Box::new(Expression::identifier("add_int", 114..121)),
                         arguments: vec![
                             Expression::Literal(LiteralValue::Int(4)),
                             Expression::Literal(LiteralValue::Int(2))
                         ]
-                    })
+                    }),
+                    span: 110..111,
                 }
             ]
         );
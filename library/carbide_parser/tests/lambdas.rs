@@ -0,0 +1,184 @@
+#[cfg(test)]
+mod lambdas {
+    use carbide_lexer::{lexer::CarbideLexer, operators::BinaryOperators};
+    use carbide_parser::{
+        errors::CarbideParserError,
+        nodes::{Block, Expression, LambdaBody, LiteralValue, Parameter, Pattern, Statement, Type},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn parse_initializer(src: &str) -> Expression {
+        let (_, result) = parse_src(src);
+        assert!(
+            result.is_ok(),
+            "Parser failed for '{src}': {:?}",
+            result.errors
+        );
+
+        match result.ast.into_iter().next() {
+            Some(Statement::LetDeclaration {
+                initializer: Some(expr),
+                ..
+            }) => expr,
+            other => panic!("Expected an initialized let declaration, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn pipe_lambda() {
+        assert_eq!(
+            parse_initializer("let add = |a: int, b| => a + b;"),
+            Expression::Lambda {
+                parameters: vec![
+                    Parameter {
+                        mutable: false,
+                        pattern: Pattern::binding("a", 11..12),
+                        type_annotation: Some(Type::named("int")),
+                        span: 11..12,
+                    },
+                    Parameter {
+                        mutable: false,
+                        pattern: Pattern::binding("b", 19..20),
+                        type_annotation: None,
+                        span: 19..20,
+                    },
+                ],
+                return_type: None,
+                body: LambdaBody::Expression(Box::new(Expression::BinaryOp {
+                    left: Box::new(Expression::identifier("a", 25..26)),
+                    operator: BinaryOperators::Plus,
+                    right: Box::new(Expression::identifier("b", 29..30)),
+                })),
+            }
+        );
+    }
+
+    #[test]
+    fn no_parameters() {
+        assert_eq!(
+            parse_initializer("let one = || => 1;"),
+            Expression::Lambda {
+                parameters: vec![],
+                return_type: None,
+                body: LambdaBody::Expression(Box::new(Expression::Literal(LiteralValue::Int(1)))),
+            }
+        );
+    }
+
+    #[test]
+    fn fn_lambda_with_block() {
+        assert_eq!(
+            parse_initializer("let double = fn(x) -> int => { return x * 2; };"),
+            Expression::Lambda {
+                parameters: vec![Parameter {
+                    mutable: false,
+                    pattern: Pattern::binding("x", 16..17),
+                    type_annotation: None,
+                    span: 16..17,
+                }],
+                return_type: Some(Type::named("int")),
+                body: LambdaBody::Block(Block {
+                    statements: vec![Statement::Return(Some(Expression::BinaryOp {
+                        left: Box::new(Expression::identifier("x", 38..39)),
+                        operator: BinaryOperators::Star,
+                        right: Box::new(Expression::Literal(LiteralValue::Int(2))),
+                    }))],
+                    tail: None,
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn pipe_lambda_return_type() {
+        assert!(matches!(
+            parse_initializer("let f = |x: int| -> bool => x == 0;"),
+            Expression::Lambda {
+                return_type: Some(Type::Named(ref name)),
+                ..
+            } if name == "bool"
+        ));
+    }
+
+    #[test]
+    fn lambda_as_argument() {
+        let (_, result) = parse_src("map(xs, |x| => x * 2);");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::Expression(Expression::Call {
+                callee: Box::new(Expression::identifier("map", 0..3)),
+                arguments: vec![
+                    Expression::identifier("xs", 4..6),
+                    Expression::Lambda {
+                        parameters: vec![Parameter {
+                            mutable: false,
+                            pattern: Pattern::binding("x", 9..10),
+                            type_annotation: None,
+                            span: 9..10,
+                        }],
+                        return_type: None,
+                        body: LambdaBody::Expression(Box::new(Expression::BinaryOp {
+                            left: Box::new(Expression::identifier("x", 15..16)),
+                            operator: BinaryOperators::Star,
+                            right: Box::new(Expression::Literal(LiteralValue::Int(2))),
+                        })),
+                    },
+                ],
+            })]
+        );
+    }
+
+    #[test]
+    fn fn_lambda_statement() {
+        let (_, result) = parse_src("fn(x) => x;");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::Expression(Expression::Lambda { .. })
+        ));
+    }
+
+    #[test]
+    fn nested_lambda() {
+        assert!(matches!(
+            parse_initializer("let add = |a| => |b| => a + b;"),
+            Expression::Lambda {
+                body: LambdaBody::Expression(ref body),
+                ..
+            } if matches!(**body, Expression::Lambda { .. })
+        ));
+    }
+
+    #[test]
+    fn break_in_lambda_inside_loop() {
+        let (_, result) = parse_src("while a { let f = || => { break; }; }");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::BreakOutsideLoop(_)
+        ));
+    }
+
+    #[test]
+    fn missing_fat_arrow() {
+        let (_, result) = parse_src("let f = |x| x;");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn unclosed_parameters() {
+        let (_, result) = parse_src("let f = |x => x;");
+        assert!(!result.is_ok());
+    }
+}
@@ -0,0 +1,227 @@
+#[cfg(test)]
+mod limits {
+    use carbide_errors::error::CarbideError;
+    use carbide_errors::reporter::ErrorReporter;
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_lexer::tokens::SourceLocation;
+    use carbide_parser::{
+        errors::CarbideParserError,
+        limits::ParserLimits,
+        nodes::{Expression, Statement},
+        parse_expression,
+        parser::{CarbideParser, ParseResult},
+    };
+
+    fn parse_with(src: &str, limits: ParserLimits) -> ParseResult {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        CarbideParser::with_limits(result.tokens, limits).parse()
+    }
+
+    fn codes(result: &ParseResult) -> Vec<String> {
+        result
+            .errors
+            .iter()
+            .map(|error| error.code().to_string())
+            .collect()
+    }
+
+    /// Pathological sources nesting `n` levels deep
+    fn nested_sources(n: usize) -> Vec<String> {
+        vec![
+            format!("let x = {}1{};", "(".repeat(n), ")".repeat(n)),
+            format!("let x = {}1{};", "[".repeat(n), "]".repeat(n)),
+            format!("let x = {}1{};", "f(".repeat(n), ")".repeat(n)),
+            format!("let x = {}a;", "!".repeat(n)),
+            format!("let x = {}1;", "|a| => ".repeat(n)),
+            format!("let x = {}1{};", "if a { ".repeat(n), " }".repeat(n)),
+            format!("let x = if a {{}}{};", " else if a {}".repeat(n)),
+            format!("fn f() {}{}", "{".repeat(n), "}".repeat(n)),
+            format!("let x: {}int{};", "[".repeat(n), "]".repeat(n)),
+            format!("let {}a{} = 1;", "(".repeat(n), ",)".repeat(n)),
+            format!("let x = 1{};", " + 1".repeat(n)),
+            format!("let x = a{};", ".b()".repeat(n)),
+            format!("let x: int{};", "?".repeat(n)),
+        ]
+    }
+
+    #[test]
+    fn deep_nesting_is_an_error() {
+        for src in nested_sources(50_000) {
+            let result = parse_with(&src, ParserLimits::default());
+            assert_eq!(codes(&result), ["E1022"], "{}", &src[..40]);
+        }
+    }
+
+    #[test]
+    fn deep_nesting_in_cst_and_expressions() {
+        let src = format!("{}1{}", "(".repeat(50_000), ")".repeat(50_000));
+        let result = parse_expression(&src);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].code().to_string(), "E1022");
+
+        let src = format!("let x = {src};\nlet y = 2;");
+        let tokens = CarbideLexer::from_src(&src).lex().tokens;
+        let result = CarbideParser::new(tokens).parse_cst(&src);
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.syntax().text(), src);
+        assert!(matches!(
+            result.lower().as_slice(),
+            [Statement::Error(_), Statement::LetDeclaration { .. }]
+        ));
+    }
+
+    #[test]
+    fn nesting_within_the_limit() {
+        let limits = ParserLimits {
+            max_depth: 32,
+            ..ParserLimits::default()
+        };
+        for src in nested_sources(8) {
+            let result = parse_with(&src, limits);
+            assert!(result.is_ok(), "{src}: {:?}", result.errors);
+        }
+        for src in nested_sources(40) {
+            let result = parse_with(&src, limits);
+            assert_eq!(codes(&result), ["E1022"], "{src}");
+        }
+    }
+
+    #[test]
+    fn nesting_error_location() {
+        let limits = ParserLimits {
+            max_depth: 4,
+            ..ParserLimits::default()
+        };
+        let result = parse_with("let x = ((((((1))))));", limits);
+        assert_eq!(
+            *result.errors[0],
+            CarbideParserError::NestingTooDeep(SourceLocation {
+                line: 1,
+                column: 13,
+                offset: 12,
+            })
+        );
+    }
+
+    #[test]
+    fn nesting_inside_interpolation() {
+        let limits = ParserLimits {
+            max_depth: 4,
+            ..ParserLimits::default()
+        };
+        // The string counts as a level, so this stops one `(` sooner than without it
+        let result = parse_with("let x = \"{((((((1))))))}\";", limits);
+        assert_eq!(
+            *result.errors[0],
+            CarbideParserError::NestingTooDeep(SourceLocation {
+                line: 1,
+                column: 14,
+                offset: 13,
+            })
+        );
+    }
+
+    #[test]
+    fn too_many_parameters() {
+        let limits = ParserLimits {
+            max_parameters: 3,
+            ..ParserLimits::default()
+        };
+        let result = parse_with("fn f(a, b, c) {}\nfn g(a, b, c, d, e) {}", limits);
+        assert_eq!(codes(&result), ["E1020"]);
+        assert_eq!(
+            result.errors[0].to_string(),
+            "Too many parameters in function declaration at [2:15]"
+        );
+        // The declaration is still parsed
+        assert!(matches!(
+            &result.ast[1],
+            Statement::FunctionDeclaration { parameters, .. } if parameters.len() == 5
+        ));
+
+        let result = parse_with("let f = |a, b, c, d| => a;", limits);
+        assert_eq!(codes(&result), ["E1020"]);
+    }
+
+    #[test]
+    fn too_many_arguments() {
+        let limits = ParserLimits {
+            max_arguments: 2,
+            ..ParserLimits::default()
+        };
+        let result = parse_with("f(1, 2);\nf(1, 2, 3, 4);", limits);
+        assert_eq!(codes(&result), ["E1021"]);
+        assert_eq!(
+            result.errors[0].to_string(),
+            "Too many arguments in function call at [2:9]"
+        );
+        assert!(matches!(
+            &result.ast[1],
+            Statement::Expression(Expression::Call { arguments, .. }) if arguments.len() == 4
+        ));
+    }
+
+    #[test]
+    fn default_parameter_and_argument_limits() {
+        let parameters = |n: usize| {
+            (0..n)
+                .map(|i| format!("p{i}"))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        let result = parse_with(
+            &format!("fn f({}) {{}}", parameters(255)),
+            ParserLimits::default(),
+        );
+        assert!(result.is_ok(), "{:?}", result.errors);
+        let result = parse_with(
+            &format!("fn f({}) {{}}", parameters(256)),
+            ParserLimits::default(),
+        );
+        assert_eq!(codes(&result), ["E1020"]);
+
+        let result = parse_with(
+            &format!("f({});", ["1"; 256].join(", ")),
+            ParserLimits::default(),
+        );
+        assert_eq!(codes(&result), ["E1021"]);
+    }
+
+    #[test]
+    fn too_many_tokens() {
+        let limits = ParserLimits {
+            max_tokens: 5,
+            ..ParserLimits::default()
+        };
+        let result = parse_with("let x = 1;", limits);
+        assert!(result.is_ok(), "{:?}", result.errors);
+
+        let result = parse_with("let x = 1 + 2;", limits);
+        assert!(result.ast.is_empty());
+        assert_eq!(
+            result.errors[0].to_string(),
+            "Too many tokens in the input at [1:13]"
+        );
+
+        let tokens = CarbideLexer::from_src("1 + 2 + 3 + 4").lex().tokens;
+        let result = CarbideParser::with_limits(tokens, limits).parse_single_expression();
+        assert!(result.node.is_none());
+        assert_eq!(result.errors[0].code().to_string(), "E1023");
+    }
+
+    #[test]
+    fn report() {
+        let src = format!("let x = {}1{};", "(".repeat(200), ")".repeat(200));
+        let result = parse_with(&src, ParserLimits::default());
+
+        let mut reporter = ErrorReporter::new();
+        reporter.add_source("test.cb", &src);
+        let report = reporter
+            .format_error("test.cb", &*result.errors[0])
+            .expect("Expected formatting to succeed");
+        assert!(report.contains("E1022"), "{report}");
+    }
+}
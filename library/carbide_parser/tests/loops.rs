@@ -0,0 +1,155 @@
+#[cfg(test)]
+mod loops {
+    use carbide_lexer::{lexer::CarbideLexer, operators::BinaryOperators};
+    use carbide_parser::{
+        errors::CarbideParserError,
+        nodes::{Expression, LiteralValue, Pattern, Statement},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    #[test]
+    fn while_loop() {
+        let (_, result) = parse_src("while running { tick(); }");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::While {
+                condition: Expression::identifier("running", 6..13),
+                body: vec![Statement::Expression(Expression::Call {
+                    callee: Box::new(Expression::identifier("tick", 16..20)),
+                    arguments: vec![],
+                })],
+            }]
+        );
+    }
+
+    #[test]
+    fn for_loop() {
+        let (_, result) = parse_src("for (let i = 0; i != 10; i = i + 1) {}");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::For {
+                initializer: Some(Box::new(Statement::LetDeclaration {
+                    attributes: Vec::new(),
+                    mutable: false,
+                    pattern: Pattern::binding("i", 9..10),
+                    type_annotation: None,
+                    initializer: Some(Expression::Literal(LiteralValue::Int(0))),
+                    span: 9..10,
+                })),
+                condition: Some(Expression::BinaryOp {
+                    left: Box::new(Expression::identifier("i", 16..17)),
+                    operator: BinaryOperators::NotEq,
+                    right: Box::new(Expression::Literal(LiteralValue::Int(10))),
+                }),
+                increment: Some(Expression::Assignment {
+                    target: Box::new(Expression::identifier("i", 25..26)),
+                    value: Box::new(Expression::BinaryOp {
+                        left: Box::new(Expression::identifier("i", 29..30)),
+                        operator: BinaryOperators::Plus,
+                        right: Box::new(Expression::Literal(LiteralValue::Int(1))),
+                    }),
+                    span: 25..34,
+                }),
+                body: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn for_loop_empty_clauses() {
+        let (_, result) = parse_src("for (;;) { break; }");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::For {
+                initializer: None,
+                condition: None,
+                increment: None,
+                body: vec![Statement::Break],
+            }]
+        );
+    }
+
+    #[test]
+    fn for_loop_expression_initializer() {
+        let (_, result) = parse_src("for (i = 0; ; ) { continue; }");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::For {
+                initializer: Some(init),
+                condition: None,
+                increment: None,
+                ..
+            } if matches!(**init, Statement::Expression(Expression::Assignment { .. }))
+        ));
+    }
+
+    #[test]
+    fn break_and_continue_in_nested_blocks() {
+        let (_, result) = parse_src("while a { { if_like(); break; } continue; }");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn break_outside_loop() {
+        let (_, result) = parse_src("let x = 1;\nbreak;");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::BreakOutsideLoop(loc) if loc.line == 2 && loc.column == 1 && loc.offset == 11
+        ));
+        // The statement itself is still kept
+        assert_eq!(result.ast.len(), 2);
+    }
+
+    #[test]
+    fn continue_outside_loop() {
+        let (_, result) = parse_src("fn f() { continue; }");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::ContinueOutsideLoop(loc) if loc.offset == 9
+        ));
+    }
+
+    #[test]
+    fn break_inside_function_inside_loop() {
+        let (_, result) = parse_src("while a { fn f() { break; } }");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::BreakOutsideLoop(_)
+        ));
+    }
+
+    #[test]
+    fn break_after_loop() {
+        let (_, result) = parse_src("while a { break; } break;");
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn for_loop_missing_paren() {
+        let (_, result) = parse_src("for let i = 0; i; i) {}");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn while_missing_body() {
+        let (_, result) = parse_src("while a;");
+        assert!(!result.is_ok());
+    }
+}
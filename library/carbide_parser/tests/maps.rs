@@ -0,0 +1,117 @@
+#[cfg(test)]
+mod maps {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Expression, LiteralValue, MapEntry, Statement, Type},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn initializer(src: &str) -> Expression {
+        let (_, result) = parse_src(src);
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        match result.ast.into_iter().next() {
+            Some(Statement::LetDeclaration {
+                initializer: Some(initializer),
+                ..
+            }) => initializer,
+            other => panic!("Expected an initialized let, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn map_literal() {
+        assert_eq!(
+            initializer("let m = { \"a\": 1, key: value, };"),
+            Expression::Map(vec![
+                MapEntry {
+                    key: Expression::Literal(LiteralValue::String("a".to_string())),
+                    value: Expression::Literal(LiteralValue::Int(1)),
+                },
+                MapEntry {
+                    key: Expression::identifier("key", 18..21),
+                    value: Expression::identifier("value", 23..28),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn nested_maps() {
+        assert_eq!(
+            initializer("let m = { 1: {}, 2: [{}] };"),
+            Expression::Map(vec![
+                MapEntry {
+                    key: Expression::Literal(LiteralValue::Int(1)),
+                    value: Expression::Map(vec![]),
+                },
+                MapEntry {
+                    key: Expression::Literal(LiteralValue::Int(2)),
+                    value: Expression::Array(vec![Expression::Map(vec![])]),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn map_as_argument() {
+        let (_, result) = parse_src("f({ 1: 2 });");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn statement_brace_is_block() {
+        let (_, result) = parse_src("{}");
+        assert!(result.is_ok());
+        assert_eq!(result.ast, vec![Statement::Block(vec![])]);
+    }
+
+    #[test]
+    fn condition_brace_is_body() {
+        // The `{` after the condition is the loop body, not a map
+        let (_, result) = parse_src("while ready {}");
+        assert!(result.is_ok());
+        assert!(matches!(&result.ast[0], Statement::While { body, .. } if body.is_empty()));
+    }
+
+    #[test]
+    fn parenthesized_map_in_condition() {
+        let (_, result) = parse_src("while ({} == m) {}");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn map_type() {
+        let (_, result) = parse_src("let m: {string: [int]} = {};");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::LetDeclaration { type_annotation: Some(ty), .. }
+                if *ty == Type::Map {
+                    key: Box::new(Type::named("string")),
+                    value: Box::new(Type::Array(Box::new(Type::named("int")))),
+                }
+        ));
+    }
+
+    #[test]
+    fn missing_colon() {
+        let (_, result) = parse_src("let m = { 1 2 };");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn missing_map_type_value() {
+        let (_, result) = parse_src("let m: {string} = {};");
+        assert!(!result.is_ok());
+    }
+}
@@ -0,0 +1,190 @@
+#[cfg(test)]
+mod matches {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{
+            EnumVariant, Expression, FieldPattern, LiteralValue, MatchArm, Pattern, Statement,
+            StructField, Type, VariantPayload, Visibility,
+        },
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn arms(src: &str) -> Vec<MatchArm> {
+        let (_, result) = parse_src(src);
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        match result.ast.into_iter().next() {
+            Some(Statement::Expression(Expression::Match { arms, .. })) => arms,
+            other => panic!("Expected a match expression, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn enum_declaration() {
+        let (_, result) =
+            parse_src("enum Shape { Empty, Circle(float), Rect { w: float, h: float } }");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::EnumDeclaration {
+                visibility: Visibility::Private,
+                name: "Shape".to_string(),
+                variants: vec![
+                    EnumVariant {
+                        name: "Empty".to_string(),
+                        payload: VariantPayload::Unit,
+                    },
+                    EnumVariant {
+                        name: "Circle".to_string(),
+                        payload: VariantPayload::Tuple(vec![Type::named("float")]),
+                    },
+                    EnumVariant {
+                        name: "Rect".to_string(),
+                        payload: VariantPayload::Struct(vec![
+                            StructField {
+                                name: "w".to_string(),
+                                type_annotation: Type::named("float"),
+                            },
+                            StructField {
+                                name: "h".to_string(),
+                                type_annotation: Type::named("float"),
+                            },
+                        ]),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn enum_trailing_comma() {
+        let (_, result) = parse_src("enum Light { Red, Green, }");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn variant_constructor_call() {
+        let (_, result) = parse_src("Shape::Circle(1.0);");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::Expression(Expression::Call {
+                callee: Box::new(Expression::identifier("Shape::Circle", 0..13)),
+                arguments: vec![Expression::Literal(LiteralValue::Float(1.0))],
+            })]
+        );
+    }
+
+    #[test]
+    fn variant_patterns() {
+        let arms = arms(
+            "match shape { Shape::Empty => 0, Shape::Circle(r) => r, Shape::Rect { w, h: _, .. } => w }",
+        );
+        let patterns = arms.into_iter().map(|a| a.pattern).collect::<Vec<_>>();
+        assert_eq!(
+            patterns,
+            vec![
+                Pattern::Path("Shape::Empty".to_string()),
+                Pattern::TupleStruct {
+                    name: "Shape::Circle".to_string(),
+                    elements: vec![Pattern::binding("r", 47..48)],
+                },
+                Pattern::Struct {
+                    name: "Shape::Rect".to_string(),
+                    fields: vec![
+                        FieldPattern {
+                            name: "w".to_string(),
+                            pattern: Pattern::binding("w", 70..71),
+                        },
+                        FieldPattern {
+                            name: "h".to_string(),
+                            pattern: Pattern::Wildcard,
+                        },
+                    ],
+                    rest: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn literal_and_tuple_patterns() {
+        let arms = arms("match (a, b) { (1, true) => x, (_, \"s\") => y, other => z }");
+        let patterns = arms.into_iter().map(|a| a.pattern).collect::<Vec<_>>();
+        assert_eq!(
+            patterns,
+            vec![
+                Pattern::Tuple(vec![
+                    Pattern::Literal(LiteralValue::Int(1)),
+                    Pattern::Literal(LiteralValue::Bool(true)),
+                ]),
+                Pattern::Tuple(vec![
+                    Pattern::Wildcard,
+                    Pattern::Literal(LiteralValue::String("s".to_string())),
+                ]),
+                Pattern::binding("other", 46..51),
+            ]
+        );
+    }
+
+    #[test]
+    fn guard() {
+        let arms = arms("match n { x if x > 0 => x, _ => 0 }");
+        assert!(arms[0].guard.is_some());
+        assert!(arms[1].guard.is_none());
+    }
+
+    #[test]
+    fn arm_span_covers_pattern() {
+        let arms = arms("match n { Shape::Empty => 0 }");
+        assert_eq!(arms[0].span, 10..22);
+    }
+
+    #[test]
+    fn struct_literal_not_scrutinee() {
+        // `Point {` starts the arms, not a struct literal
+        let arms = arms("match Point { _ => 1 }");
+        assert_eq!(arms.len(), 1);
+    }
+
+    #[test]
+    fn match_statement_without_semicolon() {
+        let (_, result) = parse_src("match a { _ => 1 } let b = 2;");
+        assert!(result.is_ok());
+        assert_eq!(result.ast.len(), 2);
+    }
+
+    #[test]
+    fn tuple_expression() {
+        let (_, result) = parse_src("(1, 2); (1,); ();");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![
+                Statement::Expression(Expression::Tuple(vec![
+                    Expression::Literal(LiteralValue::Int(1)),
+                    Expression::Literal(LiteralValue::Int(2)),
+                ])),
+                Statement::Expression(Expression::Tuple(vec![Expression::Literal(
+                    LiteralValue::Int(1)
+                )])),
+                Statement::Expression(Expression::Tuple(vec![])),
+            ]
+        );
+    }
+
+    #[test]
+    fn missing_fat_arrow() {
+        let (_, result) = parse_src("match a { _ 1 }");
+        assert!(!result.is_ok());
+    }
+}
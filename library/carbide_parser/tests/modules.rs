@@ -0,0 +1,90 @@
+#[cfg(test)]
+mod modules {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Statement, Visibility},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    #[test]
+    fn import_module() {
+        let (_, result) = parse_src("import math;");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::Import {
+                path: vec!["math".to_string()],
+                items: None,
+                span: 0..11,
+            }]
+        );
+    }
+
+    #[test]
+    fn import_items() {
+        let (_, result) = parse_src("import util.strings.{trim, split};");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::Import {
+                path: vec!["util".to_string(), "strings".to_string()],
+                items: Some(vec!["trim".to_string(), "split".to_string()]),
+                span: 0..33,
+            }]
+        );
+    }
+
+    #[test]
+    fn pub_items() {
+        let (_, result) = parse_src("pub fn f() {} pub struct S {} pub enum E {} fn g() {}");
+        assert!(result.is_ok());
+
+        let visibilities = result
+            .ast
+            .iter()
+            .map(|s| match s {
+                Statement::FunctionDeclaration { visibility, .. }
+                | Statement::StructDeclaration { visibility, .. }
+                | Statement::EnumDeclaration { visibility, .. } => *visibility,
+                other => panic!("Expected an item, found {other:?}"),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            visibilities,
+            vec![
+                Visibility::Public,
+                Visibility::Public,
+                Visibility::Public,
+                Visibility::Private
+            ]
+        );
+    }
+
+    #[test]
+    fn pub_without_item() {
+        let (_, result) = parse_src("pub let x = 1;");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn import_missing_semicolon() {
+        let (_, result) = parse_src("import math");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn import_trailing_period() {
+        let (_, result) = parse_src("import util.;");
+        assert!(!result.is_ok());
+    }
+}
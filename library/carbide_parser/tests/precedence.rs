@@ -0,0 +1,27 @@
+#[cfg(test)]
+mod precedence {
+    use carbide_lexer::operators::{BinaryOperators, UnaryOperators};
+    use carbide_parser::precedence::{Fixity, Operator, Precedence, UnaryFixity};
+
+    #[test]
+    fn unary_precedence_follows_fixity() {
+        for operator in UnaryOperators::ALL {
+            let expected = match operator.fixity() {
+                Fixity::Prefix => Precedence::Prefix,
+                Fixity::Postfix => Precedence::Postfix,
+            };
+            assert_eq!(operator.precedence(), expected, "{operator:?}");
+        }
+        assert_eq!(UnaryOperators::Not.fixity(), Fixity::Prefix);
+    }
+
+    #[test]
+    fn postfix_binds_tightest() {
+        let postfix = Precedence::Postfix.binding_power();
+        for operator in BinaryOperators::ALL {
+            let (left, right) = operator.binding_power();
+            assert!(left.max(right) < postfix, "{operator:?}");
+        }
+        assert!(Precedence::Prefix.binding_power() < postfix);
+    }
+}
@@ -0,0 +1,1111 @@
+#[cfg(test)]
+mod print {
+    use carbide_lexer::{lexer::CarbideLexer, operators::BinaryOperators};
+    use carbide_parser::{
+        fold::{self, Folder},
+        nodes::{
+            Attribute, Block, EnumVariant, Expression, FieldInit, FieldPattern, LambdaBody,
+            LiteralValue, MapEntry, MatchArm, Method, Parameter, Pattern, Program, Statement,
+            StringPart, StructField, Type, TypeParameter, VariantPayload, Visibility,
+        },
+        parser::CarbideParser,
+        print::{PrintError, print_expression, print_program, print_type},
+    };
+    use proptest::{collection::vec, num, option, prelude::*, sample::select};
+
+    fn parse(src: &str) -> Result<Program, String> {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        if !result.is_ok() {
+            return Err(format!("Lexer failed for:\n{src}"));
+        }
+
+        let result = CarbideParser::new(result.tokens).parse();
+        if !result.is_ok() {
+            return Err(format!("Parser failed for:\n{src}\n{:?}", result.errors));
+        }
+        Ok(Program::new(result.ast))
+    }
+
+    fn parse_src(src: &str) -> Program {
+        parse(src).unwrap_or_else(|error| panic!("{error}"))
+    }
+
+    fn round_trip(src: &str) -> String {
+        print_program(&parse_src(src).statements).expect("Expected the program to print")
+    }
+
+    #[test]
+    fn prints_statements_on_their_own_lines() {
+        assert_eq!(
+            round_trip("pub fn f(mut a:int)->int?{let b=a;return b;} struct P{x:float}"),
+            "pub fn f(mut a: int) -> int? {\n    let b = a;\n    return b;\n}\n\
+             struct P {\n    x: float,\n}\n"
+        );
+    }
+
+    #[test]
+    fn prints_attributes_on_their_own_lines() {
+        assert_eq!(
+            round_trip("fn f(){#[deprecated(\"use g\",2)]let x=1;} #[inline]#[test] fn g(){}"),
+            "fn f() {\n    #[deprecated(\"use g\", 2)]\n    let x = 1;\n}\n\
+             #[inline]\n#[test]\nfn g() {}\n"
+        );
+    }
+
+    #[test]
+    fn prints_generics() {
+        assert_eq!(
+            round_trip("fn f<T:Ord+Show,U>(x:T)->T{return g::<[T],U>(x);}"),
+            "fn f<T: Ord + Show, U>(x: T) -> T {\n    return g::<[T], U>(x);\n}\n"
+        );
+    }
+
+    #[test]
+    fn prints_destructuring() {
+        assert_eq!(
+            round_trip("let mut(a,[b,..rest,c])=xs;fn f(P{x,..}:P,[..]:[int]){}"),
+            "let mut (a, [b, ..rest, c]) = xs;\nfn f(P { x, .. }: P, [..]: [int]) {}\n"
+        );
+    }
+
+    #[test]
+    fn keeps_parentheses_from_the_source() {
+        assert_eq!(round_trip("let x = (a + b) * c;"), "let x = (a + b) * c;\n");
+        assert_eq!(round_trip("let x = a + (b * c);"), "let x = a + (b * c);\n");
+    }
+
+    #[test]
+    fn adds_parentheses_for_precedence() {
+        let sum = Expression::BinaryOp {
+            left: Box::new(Expression::identifier("a", 0..0)),
+            operator: BinaryOperators::Plus,
+            right: Box::new(Expression::identifier("b", 0..0)),
+        };
+        let product = |left: Expression, right: Expression| Expression::BinaryOp {
+            left: Box::new(left),
+            operator: BinaryOperators::Star,
+            right: Box::new(right),
+        };
+        let c = Expression::identifier("c", 0..0);
+
+        assert_eq!(
+            print_expression(&product(sum.clone(), c.clone())).as_deref(),
+            Ok("(a + b) * c")
+        );
+        assert_eq!(
+            print_expression(&product(c, sum)).as_deref(),
+            Ok("c * (a + b)")
+        );
+    }
+
+    #[test]
+    fn adds_parentheses_where_the_grammar_needs_them() {
+        let block = Expression::Block(Block {
+            statements: Vec::new(),
+            tail: Some(Box::new(Expression::identifier("a", 0..0))),
+        });
+        let call = Expression::Call {
+            callee: Box::new(block),
+            arguments: Vec::new(),
+        };
+        let point = Expression::StructLiteral {
+            name: "P".to_string(),
+            fields: Vec::new(),
+            base: None,
+        };
+        let program = vec![
+            Statement::Expression(call),
+            Statement::While {
+                condition: point,
+                body: Vec::new(),
+            },
+        ];
+
+        assert_eq!(
+            print_program(&program).as_deref(),
+            Ok("({\n    a\n})();\nwhile (P {}) {}\n")
+        );
+    }
+
+    #[test]
+    fn rejects_unwritable_literals() {
+        for literal in [
+            LiteralValue::Int(-5),
+            LiteralValue::Int(i64::MIN),
+            LiteralValue::Float(-1.5),
+            LiteralValue::Float(-0.0),
+            LiteralValue::Float(f64::NAN),
+            LiteralValue::Float(f64::INFINITY),
+        ] {
+            let program = vec![Statement::Expression(Expression::Call {
+                callee: Box::new(Expression::identifier("f", 0..0)),
+                arguments: vec![Expression::Literal(literal.clone())],
+            })];
+            // Compared through `Debug`, since NaN isn't equal to itself
+            let Err(PrintError::UnwritableLiteral(found)) = print_program(&program) else {
+                panic!("Expected {literal:?} to be rejected");
+            };
+            assert_eq!(format!("{found:?}"), format!("{literal:?}"));
+        }
+    }
+
+    #[test]
+    fn prints_floats_in_full() {
+        let src = print_expression(&Expression::Literal(LiteralValue::Float(1e300)))
+            .expect("Expected the float to print");
+        assert_eq!(src, format!("1{}.0", "0".repeat(300)));
+        assert_eq!(
+            parse_src(&format!("{src};")).statements,
+            vec![Statement::Expression(Expression::Literal(
+                LiteralValue::Float(1e300)
+            ))]
+        );
+    }
+
+    #[test]
+    fn prints_types() {
+        let function = Type::Function {
+            parameters: vec![Type::named("int")],
+            return_type: Box::new(Type::Unit),
+        };
+        assert_eq!(
+            print_type(&Type::Optional(Box::new(function))),
+            "((int) -> ())?"
+        );
+        assert_eq!(print_type(&Type::Tuple(vec![Type::named("int")])), "(int,)");
+    }
+
+    const NAMES: &[&str] = &["a", "b", "foo", "bar_2", "x1"];
+    const PATHS: &[&str] = &["E::A", "m::n::c"];
+
+    fn name() -> BoxedStrategy<String> {
+        select(NAMES).prop_map(str::to_string).boxed()
+    }
+
+    fn path() -> BoxedStrategy<String> {
+        prop_oneof![name(), select(PATHS).prop_map(str::to_string)].boxed()
+    }
+
+    fn text() -> BoxedStrategy<String> {
+        "[a-z \"'\\\\}\n\t]{0,6}".boxed()
+    }
+
+    fn visibility() -> BoxedStrategy<Visibility> {
+        prop_oneof![Just(Visibility::Private), Just(Visibility::Public)].boxed()
+    }
+
+    /// Whether Carbide has a literal for `literal`
+    fn writable(literal: &LiteralValue) -> bool {
+        match literal {
+            LiteralValue::Int(value) => *value >= 0,
+            LiteralValue::Float(value) => value.is_finite() && value.is_sign_positive(),
+            LiteralValue::String(_) | LiteralValue::Bool(_) => true,
+        }
+    }
+
+    /// Any `i64`, though mostly writable ones so that most programs can still be printed
+    fn int() -> BoxedStrategy<i64> {
+        prop_oneof![3 => 0..=i64::MAX, 1 => any::<i64>()].boxed()
+    }
+
+    /// Any `f64`, including huge, tiny, negative, infinite and NaN values, though mostly
+    /// writable ones so that most programs can still be printed
+    fn float() -> BoxedStrategy<f64> {
+        prop_oneof![
+            2 => 0.0..1e12f64,
+            1 => num::f64::POSITIVE | num::f64::ZERO | num::f64::NORMAL | num::f64::SUBNORMAL,
+            1 => any::<f64>(),
+        ]
+        .boxed()
+    }
+
+    fn literal() -> BoxedStrategy<LiteralValue> {
+        prop_oneof![
+            int().prop_map(LiteralValue::Int),
+            float().prop_map(LiteralValue::Float),
+            text().prop_map(LiteralValue::String),
+            any::<bool>().prop_map(LiteralValue::Bool),
+        ]
+        .boxed()
+    }
+
+    fn attribute() -> BoxedStrategy<Attribute> {
+        (
+            select(&["inline", "test", "deprecated"][..]),
+            vec(literal(), 0..3),
+        )
+            .prop_map(|(name, arguments)| Attribute {
+                name: name.to_string(),
+                arguments,
+                span: 0..0,
+            })
+            .boxed()
+    }
+
+    fn ty() -> BoxedStrategy<Type> {
+        let leaf = prop_oneof![
+            select(&["int", "float", "string", "bool", "Point"][..]).prop_map(Type::named),
+            Just(Type::Unit),
+        ];
+        leaf.prop_recursive(3, 16, 3, |inner| {
+            prop_oneof![
+                (vec(inner.clone(), 0..3), inner.clone()).prop_map(|(parameters, ret)| {
+                    Type::Function {
+                        parameters,
+                        return_type: Box::new(ret),
+                    }
+                }),
+                inner.clone().prop_map(|ty| Type::Array(Box::new(ty))),
+                vec(inner.clone(), 0..3).prop_map(Type::Tuple),
+                (inner.clone(), inner.clone()).prop_map(|(key, value)| Type::Map {
+                    key: Box::new(key),
+                    value: Box::new(value),
+                }),
+                inner.clone().prop_map(|ty| Type::Optional(Box::new(ty))),
+                (select(&["List", "Pair"][..]), vec(inner, 1..3)).prop_map(|(name, arguments)| {
+                    Type::Generic {
+                        name: name.to_string(),
+                        arguments,
+                    }
+                }),
+            ]
+        })
+        .boxed()
+    }
+
+    fn pattern() -> BoxedStrategy<Pattern> {
+        let leaf = prop_oneof![
+            Just(Pattern::Wildcard),
+            literal().prop_map(Pattern::Literal),
+            name().prop_map(|name| Pattern::binding(name, 0..0)),
+            select(PATHS).prop_map(|path| Pattern::Path(path.to_string())),
+        ];
+        leaf.prop_recursive(3, 12, 3, |inner| {
+            prop_oneof![
+                vec(inner.clone(), 0..3).prop_map(Pattern::Tuple),
+                (
+                    vec(inner.clone(), 0..3),
+                    option::of((0..3usize, option::of(name())))
+                )
+                    .prop_map(|(mut elements, rest)| {
+                        if let Some((index, name)) = rest {
+                            elements.insert(
+                                index.min(elements.len()),
+                                Pattern::Rest { name, span: 0..0 },
+                            );
+                        }
+                        Pattern::Array(elements)
+                    }),
+                (path(), vec(inner.clone(), 0..3))
+                    .prop_map(|(name, elements)| Pattern::TupleStruct { name, elements }),
+                (
+                    path(),
+                    vec(
+                        (name(), inner).prop_map(|(name, pattern)| FieldPattern { name, pattern }),
+                        0..3
+                    ),
+                    any::<bool>()
+                )
+                    .prop_map(|(name, fields, rest)| Pattern::Struct {
+                        name,
+                        fields,
+                        rest
+                    }),
+            ]
+        })
+        .boxed()
+    }
+
+    fn parameter() -> BoxedStrategy<Parameter> {
+        (any::<bool>(), pattern(), option::of(ty()))
+            .prop_map(|(mutable, pattern, type_annotation)| Parameter {
+                mutable,
+                pattern,
+                type_annotation,
+                span: 0..0,
+            })
+            .boxed()
+    }
+
+    fn type_parameter() -> BoxedStrategy<TypeParameter> {
+        (
+            select(&["T", "U", "Item"][..]),
+            vec(select(&["Ord", "Show"][..]), 0..3),
+        )
+            .prop_map(|(name, bounds)| TypeParameter {
+                name: name.to_string(),
+                bounds: bounds.into_iter().map(str::to_string).collect(),
+                span: 0..0,
+            })
+            .boxed()
+    }
+
+    fn struct_fields() -> BoxedStrategy<Vec<StructField>> {
+        vec(
+            (name(), ty()).prop_map(|(name, type_annotation)| StructField {
+                name,
+                type_annotation,
+            }),
+            0..3,
+        )
+        .boxed()
+    }
+
+    fn binary_operator() -> BoxedStrategy<BinaryOperators> {
+        let operators: Vec<_> = BinaryOperators::ALL
+            .iter()
+            .copied()
+            .filter(|operator| *operator != BinaryOperators::Eq)
+            .collect();
+        select(operators).boxed()
+    }
+
+    /// Expressions that fit inside string interpolation, which can't hold strings or braces
+    fn interpolated() -> BoxedStrategy<Expression> {
+        let leaf = prop_oneof![
+            path().prop_map(|name| Expression::identifier(name, 0..0)),
+            int().prop_map(|n| Expression::Literal(LiteralValue::Int(n))),
+            any::<bool>().prop_map(|b| Expression::Literal(LiteralValue::Bool(b))),
+        ];
+        leaf.prop_recursive(2, 8, 2, |inner| {
+            prop_oneof![
+                (inner.clone(), binary_operator(), inner.clone()).prop_map(
+                    |(left, operator, right)| Expression::BinaryOp {
+                        left: Box::new(left),
+                        operator,
+                        right: Box::new(right),
+                    }
+                ),
+                (inner.clone(), vec(inner.clone(), 0..2)).prop_map(|(callee, arguments)| {
+                    Expression::Call {
+                        callee: Box::new(callee),
+                        arguments,
+                    }
+                }),
+                (inner.clone(), name()).prop_map(|(target, member)| Expression::MemberAccess {
+                    target: Box::new(target),
+                    member,
+                    span: 0..0,
+                }),
+                inner.prop_map(|inner| Expression::Grouped(Box::new(inner))),
+            ]
+        })
+        .boxed()
+    }
+
+    /// Statements without nested statements, built from `expression`
+    fn simple_statement(expression: BoxedStrategy<Expression>) -> BoxedStrategy<Statement> {
+        prop_oneof![
+            (
+                vec(attribute(), 0..2),
+                any::<bool>(),
+                pattern(),
+                option::of(ty()),
+                option::of(expression.clone())
+            )
+                .prop_map(
+                    |(attributes, mutable, pattern, type_annotation, initializer)| {
+                        Statement::LetDeclaration {
+                            attributes,
+                            mutable,
+                            pattern,
+                            type_annotation,
+                            initializer,
+                            span: 0..0,
+                        }
+                    }
+                ),
+            (visibility(), name(), ty(), expression.clone()).prop_map(
+                |(visibility, name, type_annotation, value)| Statement::ConstDeclaration {
+                    visibility,
+                    name,
+                    type_annotation,
+                    value,
+                    span: 0..0,
+                }
+            ),
+            expression.clone().prop_map(Statement::Expression),
+            option::of(expression).prop_map(Statement::Return),
+            Just(Statement::Break),
+            Just(Statement::Continue),
+        ]
+        .boxed()
+    }
+
+    fn statement(expression: BoxedStrategy<Expression>) -> BoxedStrategy<Statement> {
+        let simple = simple_statement(expression.clone());
+        let body = vec(simple.clone(), 0..3);
+        let function_body = (body.clone(), option::of(expression.clone()))
+            .prop_map(|(statements, tail)| Block {
+                statements,
+                tail: tail.map(Box::new),
+            })
+            .boxed();
+        prop_oneof![
+            4 => simple.clone(),
+            1 => (
+                vec(attribute(), 0..2),
+                visibility(),
+                name(),
+                vec(type_parameter(), 0..3),
+                vec(parameter(), 0..3),
+                option::of(ty()),
+                function_body.clone()
+            )
+                .prop_map(
+                    |(attributes, visibility, name, type_parameters, parameters, return_type, body)| {
+                    Statement::FunctionDeclaration {
+                        attributes,
+                        visibility,
+                        name,
+                        type_parameters,
+                        parameters,
+                        return_type,
+                        body,
+                    }
+                }),
+            1 => (visibility(), name(), struct_fields()).prop_map(|(visibility, name, fields)| {
+                Statement::StructDeclaration {
+                    visibility,
+                    name,
+                    fields,
+                }
+            }),
+            1 => (visibility(), name(), vec(enum_variant(), 0..3)).prop_map(
+                |(visibility, name, variants)| Statement::EnumDeclaration {
+                    visibility,
+                    name,
+                    variants,
+                }
+            ),
+            1 => (visibility(), name(), vec(method(function_body.clone(), false), 0..3)).prop_map(
+                |(visibility, name, methods)| Statement::TraitDeclaration {
+                    visibility,
+                    name,
+                    methods,
+                    span: 0..0,
+                }
+            ),
+            1 => (option::of(name()), name(), vec(method(function_body, true), 0..3)).prop_map(
+                |(trait_name, target, methods)| Statement::ImplDeclaration {
+                    trait_name,
+                    target,
+                    methods,
+                    span: 0..0,
+                }
+            ),
+            1 => (vec(name(), 1..3), option::of(vec(name(), 0..3)))
+                .prop_map(|(path, items)| Statement::Import {
+                    path,
+                    items,
+                    span: 0..0,
+                }),
+            1 => (expression.clone(), body.clone())
+                .prop_map(|(condition, body)| Statement::While { condition, body }),
+            1 => (
+                option::of(simple),
+                option::of(expression.clone()),
+                option::of(expression),
+                body.clone()
+            )
+                .prop_map(|(initializer, condition, increment, body)| Statement::For {
+                    initializer: initializer.map(Box::new),
+                    condition,
+                    increment,
+                    body,
+                }),
+            1 => body.prop_map(Statement::Block),
+        ]
+        .boxed()
+    }
+
+    /// Methods of a trait, which can leave out their bodies, or of an impl
+    fn method(body: BoxedStrategy<Block>, body_required: bool) -> BoxedStrategy<Method> {
+        let body = if body_required {
+            body.prop_map(Some).boxed()
+        } else {
+            option::of(body).boxed()
+        };
+        (name(), vec(parameter(), 0..3), option::of(ty()), body)
+            .prop_map(|(name, parameters, return_type, body)| Method {
+                name,
+                parameters,
+                return_type,
+                body,
+                span: 0..0,
+            })
+            .boxed()
+    }
+
+    fn enum_variant() -> BoxedStrategy<EnumVariant> {
+        let payload = prop_oneof![
+            Just(VariantPayload::Unit),
+            vec(ty(), 0..3).prop_map(VariantPayload::Tuple),
+            struct_fields().prop_map(VariantPayload::Struct),
+        ];
+        (name(), payload)
+            .prop_map(|(name, payload)| EnumVariant { name, payload })
+            .boxed()
+    }
+
+    fn block(expression: BoxedStrategy<Expression>) -> BoxedStrategy<Block> {
+        (
+            vec(statement(expression.clone()), 0..3),
+            option::of(expression),
+        )
+            .prop_map(|(statements, tail)| Block {
+                statements,
+                tail: tail.map(Box::new),
+            })
+            .boxed()
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn expression() -> BoxedStrategy<Expression> {
+        let leaf = prop_oneof![
+            literal().prop_map(Expression::Literal),
+            path().prop_map(|name| Expression::identifier(name, 0..0)),
+            (path(), vec(ty(), 1..3)).prop_map(|(name, arguments)| Expression::Generic {
+                name,
+                arguments,
+                span: 0..0,
+            }),
+        ];
+        leaf.prop_recursive(4, 48, 4, |inner| {
+            let boxed = inner.clone().prop_map(Box::new);
+            let place = prop_oneof![
+                path().prop_map(|name| Expression::identifier(name, 0..0)),
+                (boxed.clone(), name()).prop_map(|(target, member)| Expression::MemberAccess {
+                    target,
+                    member,
+                    span: 0..0,
+                }),
+                (boxed.clone(), boxed.clone())
+                    .prop_map(|(target, index)| Expression::Index { target, index }),
+            ];
+            let string_part = prop_oneof![
+                "[a-z \"\\\\}]{1,4}".prop_map(StringPart::Text),
+                interpolated().prop_map(|expression| StringPart::Expression(Box::new(expression))),
+            ];
+            let arm = (pattern(), option::of(inner.clone()), inner.clone()).prop_map(
+                |(pattern, guard, body)| MatchArm {
+                    pattern,
+                    guard,
+                    body,
+                    span: 0..0,
+                },
+            );
+            let field = (name(), inner.clone()).prop_map(|(name, value)| FieldInit { name, value });
+            let lambda_body = prop_oneof![
+                boxed.clone().prop_map(LambdaBody::Expression),
+                block(inner.clone()).prop_map(LambdaBody::Block),
+            ];
+            // Anything else in an `else` gets wrapped in a block
+            let else_branch = inner.clone().prop_map(|expression| match expression {
+                Expression::If { .. } | Expression::Block(_) => expression,
+                other => Expression::Block(Block {
+                    statements: Vec::new(),
+                    tail: Some(Box::new(other)),
+                }),
+            });
+
+            prop_oneof![
+                (boxed.clone(), binary_operator(), boxed.clone()).prop_map(
+                    |(left, operator, right)| Expression::BinaryOp {
+                        left,
+                        operator,
+                        right,
+                    }
+                ),
+                boxed.clone().prop_map(|operand| Expression::UnaryOp {
+                    operator: carbide_lexer::operators::UnaryOperators::Not,
+                    operand,
+                }),
+                (place, boxed.clone()).prop_map(|(target, value)| Expression::Assignment {
+                    target: Box::new(target),
+                    value,
+                    span: 0..0,
+                }),
+                (boxed.clone(), vec(inner.clone(), 0..3))
+                    .prop_map(|(callee, arguments)| Expression::Call { callee, arguments }),
+                (boxed.clone(), boxed.clone())
+                    .prop_map(|(target, index)| Expression::Index { target, index }),
+                (boxed.clone(), name()).prop_map(|(target, member)| Expression::MemberAccess {
+                    target,
+                    member,
+                    span: 0..0,
+                }),
+                boxed.clone().prop_map(Expression::Grouped),
+                vec(inner.clone(), 0..3).prop_map(Expression::Array),
+                vec(inner.clone(), 0..3).prop_map(Expression::Tuple),
+                vec(
+                    (inner.clone(), inner.clone()).prop_map(|(key, value)| MapEntry { key, value }),
+                    0..3
+                )
+                .prop_map(Expression::Map),
+                vec(string_part, 1..4).prop_map(|parts| Expression::InterpolatedString { parts }),
+                (boxed.clone(), vec(arm, 0..3)).prop_map(|(scrutinee, arms)| Expression::Match {
+                    scrutinee,
+                    arms,
+                    span: 0..0,
+                }),
+                (path(), vec(field, 0..3), option::of(boxed.clone())).prop_map(
+                    |(name, fields, base)| Expression::StructLiteral { name, fields, base }
+                ),
+                (vec(parameter(), 0..3), option::of(ty()), lambda_body).prop_map(
+                    |(parameters, return_type, body)| Expression::Lambda {
+                        parameters,
+                        return_type,
+                        body,
+                    }
+                ),
+                block(inner.clone()).prop_map(Expression::Block),
+                (boxed, block(inner), option::of(else_branch)).prop_map(
+                    |(condition, then_branch, else_branch)| Expression::If {
+                        condition,
+                        then_branch,
+                        else_branch: else_branch.map(Box::new),
+                    }
+                ),
+            ]
+        })
+        .boxed()
+    }
+
+    fn program() -> BoxedStrategy<Program> {
+        vec(statement(expression()), 0..4)
+            .prop_map(|statements| Canonical::default().fold_program(Program::new(statements)))
+            .boxed()
+    }
+
+    /// Rewrites generated ASTs into the forms the parser builds, and drops the statements
+    /// that aren't allowed where they ended up
+    #[derive(Default)]
+    struct Canonical {
+        functions: usize,
+        loops: usize,
+    }
+
+    impl Canonical {
+        fn in_function<T>(&mut self, f: impl FnOnce(&mut Self) -> T) -> T {
+            let loops = std::mem::take(&mut self.loops);
+            self.functions += 1;
+            let result = f(self);
+            self.functions -= 1;
+            self.loops = loops;
+            result
+        }
+
+        fn in_loop(&mut self, body: Vec<Statement>) -> Vec<Statement> {
+            self.loops += 1;
+            let body = body
+                .into_iter()
+                .map(|statement| self.fold_statement(statement))
+                .collect();
+            self.loops -= 1;
+            body
+        }
+
+        /// A block tail that's a block without a tail of its own is parsed as a statement
+        fn block(&mut self, block: Block) -> Block {
+            let mut block = fold::walk_block(self, block);
+            if let Some(tail) = block.tail.take() {
+                match *tail {
+                    Expression::Block(Block {
+                        statements,
+                        tail: None,
+                    }) => block.statements.push(Statement::Block(statements)),
+                    tail => block.tail = Some(Box::new(tail)),
+                }
+            }
+            block
+        }
+
+        fn placeholder() -> Statement {
+            Statement::Expression(Expression::identifier("a", 0..0))
+        }
+    }
+
+    impl Folder for Canonical {
+        fn fold_program(&mut self, program: Program) -> Program {
+            // Constants are only allowed at the top level
+            let statements = program
+                .statements
+                .into_iter()
+                .map(|statement| match statement {
+                    Statement::ConstDeclaration { .. } => fold::walk_statement(self, statement),
+                    statement => self.fold_statement(statement),
+                })
+                .collect();
+            Program::new(statements)
+        }
+
+        fn fold_statement(&mut self, statement: Statement) -> Statement {
+            let statement = match statement {
+                Statement::Return(_) if self.functions == 0 => return Self::placeholder(),
+                Statement::Break | Statement::Continue if self.loops == 0 => {
+                    return Self::placeholder();
+                }
+                Statement::ConstDeclaration {
+                    name,
+                    type_annotation,
+                    value,
+                    span,
+                    ..
+                } => {
+                    return self.fold_statement(Statement::LetDeclaration {
+                        attributes: Vec::new(),
+                        mutable: false,
+                        pattern: Pattern::binding(name, span.clone()),
+                        type_annotation: Some(type_annotation),
+                        initializer: Some(value),
+                        span,
+                    });
+                }
+                Statement::FunctionDeclaration { .. } => {
+                    return self.in_function(|this| fold::walk_statement(this, statement));
+                }
+                Statement::While { condition, body } => {
+                    return Statement::While {
+                        condition: self.fold_expression(condition),
+                        body: self.in_loop(body),
+                    };
+                }
+                Statement::For {
+                    initializer,
+                    condition,
+                    increment,
+                    body,
+                } => {
+                    // Initializers are only `let` or non-block expression statements
+                    let initializer = initializer.and_then(|initializer| match *initializer {
+                        // Attributes can't go on a `for` initializer
+                        Statement::LetDeclaration {
+                            mutable,
+                            pattern,
+                            type_annotation,
+                            initializer,
+                            span,
+                            ..
+                        } => Some(Box::new(Statement::LetDeclaration {
+                            attributes: Vec::new(),
+                            mutable,
+                            pattern,
+                            type_annotation,
+                            initializer,
+                            span,
+                        })),
+                        Statement::Expression(expression)
+                            if !expression.is_block_like()
+                                && !matches!(expression, Expression::Map(_)) =>
+                        {
+                            Some(Box::new(Statement::Expression(expression)))
+                        }
+                        _ => None,
+                    });
+                    return Statement::For {
+                        initializer: initializer
+                            .map(|initializer| Box::new(self.fold_statement(*initializer))),
+                        condition: condition.map(|condition| self.fold_expression(condition)),
+                        increment: increment.map(|increment| self.fold_expression(increment)),
+                        body: self.in_loop(body),
+                    };
+                }
+                statement => fold::walk_statement(self, statement),
+            };
+
+            match statement {
+                Statement::Expression(Expression::Block(Block {
+                    statements,
+                    tail: None,
+                })) => Statement::Block(statements),
+                statement => statement,
+            }
+        }
+
+        fn fold_expression(&mut self, expression: Expression) -> Expression {
+            match expression {
+                // `{}` is an empty map
+                Expression::Block(block) => match self.block(block) {
+                    Block {
+                        statements,
+                        tail: None,
+                    } if statements.is_empty() => Expression::Map(Vec::new()),
+                    block => Expression::Block(block),
+                },
+                Expression::If {
+                    condition,
+                    then_branch,
+                    else_branch,
+                } => Expression::If {
+                    condition: Box::new(self.fold_expression(*condition)),
+                    then_branch: self.block(then_branch),
+                    else_branch: else_branch.map(|branch| {
+                        Box::new(match *branch {
+                            Expression::Block(block) => Expression::Block(self.block(block)),
+                            branch => self.fold_expression(branch),
+                        })
+                    }),
+                },
+                Expression::Lambda { .. } => {
+                    self.in_function(|this| fold::walk_expression(this, expression))
+                }
+                expression => match fold::walk_expression(self, expression) {
+                    Expression::InterpolatedString { parts } => interpolated_string(parts),
+                    expression => expression,
+                },
+            }
+        }
+
+        fn fold_block(&mut self, block: Block) -> Block {
+            self.block(block)
+        }
+
+        fn fold_method(&mut self, method: Method) -> Method {
+            self.in_function(|this| fold::walk_method(this, method))
+        }
+
+        fn fold_type(&mut self, ty: Type) -> Type {
+            match fold::walk_type(self, ty) {
+                Type::Tuple(elements) if elements.is_empty() => Type::Unit,
+                ty => ty,
+            }
+        }
+    }
+
+    /// Merge adjacent text and drop empty text, like the lexer does
+    fn interpolated_string(parts: Vec<StringPart>) -> Expression {
+        let mut merged: Vec<StringPart> = Vec::new();
+        for part in parts {
+            match (merged.last_mut(), part) {
+                (_, StringPart::Text(text)) if text.is_empty() => {}
+                (Some(StringPart::Text(last)), StringPart::Text(text)) => last.push_str(&text),
+                (_, part) => merged.push(part),
+            }
+        }
+
+        if merged
+            .iter()
+            .any(|part| matches!(part, StringPart::Expression(_)))
+        {
+            Expression::InterpolatedString { parts: merged }
+        } else {
+            let text = merged
+                .into_iter()
+                .map(|part| match part {
+                    StringPart::Text(text) => text,
+                    StringPart::Expression(_) => unreachable!(),
+                })
+                .collect();
+            Expression::Literal(LiteralValue::String(text))
+        }
+    }
+
+    /// Zeroes spans, and removes parentheses unless `grouped` is set
+    struct Normalize {
+        grouped: bool,
+    }
+
+    impl Folder for Normalize {
+        fn fold_statement(&mut self, statement: Statement) -> Statement {
+            match fold::walk_statement(self, statement) {
+                Statement::LetDeclaration {
+                    attributes,
+                    mutable,
+                    pattern,
+                    type_annotation,
+                    initializer,
+                    ..
+                } => Statement::LetDeclaration {
+                    attributes,
+                    mutable,
+                    pattern,
+                    type_annotation,
+                    initializer,
+                    span: 0..0,
+                },
+                Statement::ConstDeclaration {
+                    visibility,
+                    name,
+                    type_annotation,
+                    value,
+                    ..
+                } => Statement::ConstDeclaration {
+                    visibility,
+                    name,
+                    type_annotation,
+                    value,
+                    span: 0..0,
+                },
+                Statement::TraitDeclaration {
+                    visibility,
+                    name,
+                    methods,
+                    ..
+                } => Statement::TraitDeclaration {
+                    visibility,
+                    name,
+                    methods,
+                    span: 0..0,
+                },
+                Statement::ImplDeclaration {
+                    trait_name,
+                    target,
+                    methods,
+                    ..
+                } => Statement::ImplDeclaration {
+                    trait_name,
+                    target,
+                    methods,
+                    span: 0..0,
+                },
+                Statement::Import { path, items, .. } => Statement::Import {
+                    path,
+                    items,
+                    span: 0..0,
+                },
+                statement => statement,
+            }
+        }
+
+        fn fold_expression(&mut self, expression: Expression) -> Expression {
+            match fold::walk_expression(self, expression) {
+                Expression::Grouped(inner) if !self.grouped => *inner,
+                Expression::Identifier { name, .. } => Expression::identifier(name, 0..0),
+                Expression::Generic {
+                    name, arguments, ..
+                } => Expression::Generic {
+                    name,
+                    arguments,
+                    span: 0..0,
+                },
+                Expression::Assignment { target, value, .. } => Expression::Assignment {
+                    target,
+                    value,
+                    span: 0..0,
+                },
+                Expression::Match {
+                    scrutinee, arms, ..
+                } => Expression::Match {
+                    scrutinee,
+                    arms,
+                    span: 0..0,
+                },
+                Expression::MemberAccess { target, member, .. } => Expression::MemberAccess {
+                    target,
+                    member,
+                    span: 0..0,
+                },
+                expression => expression,
+            }
+        }
+
+        fn fold_pattern(&mut self, pattern: Pattern) -> Pattern {
+            match fold::walk_pattern(self, pattern) {
+                Pattern::Binding { name, .. } => Pattern::binding(name, 0..0),
+                Pattern::Rest { name, .. } => Pattern::Rest { name, span: 0..0 },
+                pattern => pattern,
+            }
+        }
+
+        fn fold_type_parameter(&mut self, type_parameter: TypeParameter) -> TypeParameter {
+            TypeParameter {
+                span: 0..0,
+                ..type_parameter
+            }
+        }
+
+        fn fold_attribute(&mut self, attribute: Attribute) -> Attribute {
+            Attribute {
+                span: 0..0,
+                ..fold::walk_attribute(self, attribute)
+            }
+        }
+
+        fn fold_match_arm(&mut self, arm: MatchArm) -> MatchArm {
+            MatchArm {
+                span: 0..0,
+                ..fold::walk_match_arm(self, arm)
+            }
+        }
+
+        fn fold_method(&mut self, method: Method) -> Method {
+            Method {
+                span: 0..0,
+                ..fold::walk_method(self, method)
+            }
+        }
+
+        fn fold_parameter(&mut self, parameter: Parameter) -> Parameter {
+            Parameter {
+                span: 0..0,
+                ..fold::walk_parameter(self, parameter)
+            }
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(512))]
+
+        /// Printing any AST parses back to the same tree, apart from spans and the
+        /// parentheses the printer had to add
+        #[test]
+        fn printing_keeps_the_structure(program in program()) {
+            let src = match print_program(&program.statements) {
+                Ok(src) => src,
+                Err(PrintError::UnwritableLiteral(literal)) => {
+                    prop_assert!(!writable(&literal), "{:?}", literal);
+                    return Ok(());
+                }
+            };
+            let parsed = parse(&src).map_err(TestCaseError::fail)?;
+            let mut normalize = Normalize { grouped: false };
+            prop_assert_eq!(
+                normalize.fold_program(parsed),
+                normalize.fold_program(program),
+                "{}",
+                src
+            );
+        }
+
+        /// `parse(print(ast)) == ast` for ASTs the parser builds, so the printer adds no
+        /// parentheses of its own
+        #[test]
+        fn parse_print_round_trips(program in program()) {
+            // Programs with unwritable literals are covered by `printing_keeps_the_structure`
+            let Ok(src) = print_program(&program.statements) else {
+                return Ok(());
+            };
+            let ast = parse(&src).map_err(TestCaseError::fail)?;
+            let src = print_program(&ast.statements)
+                .map_err(|error| TestCaseError::fail(error.to_string()))?;
+            let reparsed = parse(&src).map_err(TestCaseError::fail)?;
+            let mut normalize = Normalize { grouped: true };
+            prop_assert_eq!(
+                normalize.fold_program(reparsed),
+                normalize.fold_program(ast),
+                "{}",
+                src
+            );
+        }
+
+        /// Every literal either prints as source that parses back to exactly the same value,
+        /// or is rejected because Carbide has no way to write it
+        #[test]
+        fn literals_print_exactly_or_are_rejected(literal in literal()) {
+            let expression = Expression::Literal(literal.clone());
+            match print_expression(&expression) {
+                Ok(src) => {
+                    prop_assert!(writable(&literal), "{:?} printed as {}", literal, src);
+                    let parsed = parse(&format!("{src};")).map_err(TestCaseError::fail)?;
+                    prop_assert_eq!(parsed.statements, vec![Statement::Expression(expression)]);
+                }
+                Err(PrintError::UnwritableLiteral(found)) => {
+                    prop_assert!(!writable(&literal), "{:?}", literal);
+                    prop_assert_eq!(format!("{found:?}"), format!("{literal:?}"));
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,128 @@
+#[cfg(test)]
+mod recovery {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Expression, LiteralValue, Pattern, Statement},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn function_body(statement: &Statement) -> &[Statement] {
+        match statement {
+            Statement::FunctionDeclaration { body, .. } => &body.statements,
+            other => panic!("Expected a function declaration, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn keeps_function_with_bad_statement() {
+        let (_, result) = parse_src("fn f() { let = 1; g(); }\nfn h() {}");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.ast.len(), 2);
+
+        let body = function_body(&result.ast[0]);
+        assert!(matches!(
+            body,
+            [Statement::Error(span), Statement::Expression(Expression::Call { .. })]
+                if *span == (9..17)
+        ));
+    }
+
+    #[test]
+    fn multiple_independent_errors() {
+        let (_, result) =
+            parse_src("fn f() { let = 1; let y = ; ok(); }\nlet z = 1 +;\nlet w = 2;");
+        assert_eq!(result.errors.len(), 3);
+
+        let body = function_body(&result.ast[0]);
+        assert!(matches!(
+            body,
+            [
+                Statement::Error(_),
+                Statement::Error(_),
+                Statement::Expression(_)
+            ]
+        ));
+        assert!(matches!(result.ast[1], Statement::Error(_)));
+        assert!(matches!(
+            &result.ast[2],
+            Statement::LetDeclaration { pattern: Pattern::Binding { name, .. }, .. } if name == "w"
+        ));
+    }
+
+    #[test]
+    fn synchronize_skips_nested_braces() {
+        // The `let` inside the braces doesn't restart parsing in the middle of them
+        let (_, result) = parse_src("struct S { x: int, let y = 1; }\nlet z = 2;");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(result.ast.len(), 2);
+        assert!(matches!(result.ast[0], Statement::Error(_)));
+        assert!(
+            matches!(&result.ast[1], Statement::LetDeclaration { pattern: Pattern::Binding { name, .. }, .. } if name == "z")
+        );
+    }
+
+    #[test]
+    fn recovers_in_nested_blocks() {
+        let (_, result) = parse_src("fn f() { while a { x = ; } y(); }");
+        assert_eq!(result.errors.len(), 1);
+
+        let body = function_body(&result.ast[0]);
+        assert!(matches!(
+            body,
+            [Statement::While { body, .. }, Statement::Expression(_)]
+                if matches!(body.as_slice(), [Statement::Error(_)])
+        ));
+    }
+
+    #[test]
+    fn recovers_in_arguments() {
+        let (_, result) = parse_src("f(1, let, 3);");
+        assert_eq!(result.errors.len(), 1);
+        assert_eq!(
+            result.ast,
+            vec![Statement::Expression(Expression::Call {
+                callee: Box::new(Expression::identifier("f", 0..1)),
+                arguments: vec![
+                    Expression::Literal(LiteralValue::Int(1)),
+                    Expression::Error(5..8),
+                    Expression::Literal(LiteralValue::Int(3)),
+                ],
+            })]
+        );
+    }
+
+    #[test]
+    fn recovers_in_nested_arguments() {
+        let (_, result) = parse_src("f(g(1 +), [2]);");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            &result.ast[0],
+            Statement::Expression(Expression::Call { arguments, .. })
+                if matches!(
+                    arguments.as_slice(),
+                    [Expression::Call { arguments, .. }, Expression::Array(_)]
+                        if matches!(arguments.as_slice(), [Expression::Error(_)])
+                )
+        ));
+    }
+
+    #[test]
+    fn stray_closing_brace() {
+        let (_, result) = parse_src("}\nlet x = 1;");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            &result.ast[..],
+            [Statement::Error(_), Statement::LetDeclaration { .. }]
+        ));
+    }
+}
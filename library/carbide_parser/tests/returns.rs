@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod returns {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        errors::CarbideParserError,
+        nodes::{Expression, LambdaBody, LiteralValue, Statement},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    #[test]
+    fn bare_return() {
+        let (_, result) = parse_src("fn f() { return; }");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::FunctionDeclaration { body, .. } if body.statements == [Statement::Return(None)]
+        ));
+    }
+
+    #[test]
+    fn return_value() {
+        let (_, result) = parse_src("fn f() { while a { return 1; } }");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::FunctionDeclaration { body, .. }
+                if matches!(&body.statements[0], Statement::While { body, .. }
+                    if body == &vec![Statement::Return(Some(Expression::Literal(LiteralValue::Int(1))))])
+        ));
+    }
+
+    #[test]
+    fn return_in_lambda() {
+        let (_, result) = parse_src("let f = || => { return; };");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::LetDeclaration {
+                initializer: Some(Expression::Lambda {
+                    body: LambdaBody::Block(body),
+                    ..
+                }),
+                ..
+            } if body.statements == [Statement::Return(None)]
+        ));
+    }
+
+    #[test]
+    fn return_at_top_level() {
+        let (_, result) = parse_src("let x = 1;\nreturn 1;");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::ReturnOutsideFunction(loc) if loc.line == 2 && loc.column == 1 && loc.offset == 11
+        ));
+        // The statement itself is still kept
+        assert_eq!(result.ast.len(), 2);
+    }
+
+    #[test]
+    fn bare_return_at_top_level() {
+        let (_, result) = parse_src("return;");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::ReturnOutsideFunction(_)
+        ));
+    }
+
+    #[test]
+    fn return_in_nested_top_level_blocks() {
+        let (_, result) = parse_src("{ while a { for (;;) { return 1; } } }");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::ReturnOutsideFunction(loc) if loc.offset == 23
+        ));
+    }
+
+    #[test]
+    fn return_after_function() {
+        let (_, result) = parse_src("fn f() { return; } return;");
+        assert_eq!(result.errors.len(), 1);
+    }
+
+    #[test]
+    fn return_missing_semicolon() {
+        let (_, result) = parse_src("fn f() { return 1 }");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn bare_return_before_closing_brace() {
+        for src in [
+            "fn f() { return }",
+            "fn f() {\n    return\n}",
+            "fn f() { if a { return } }",
+        ] {
+            let (_, result) = parse_src(src);
+            assert!(result.is_ok(), "{src}: {:?}", result.errors);
+        }
+
+        let (_, result) = parse_src("fn f() { return }");
+        assert!(matches!(
+            &result.ast[0],
+            Statement::FunctionDeclaration { body, .. } if body.statements == [Statement::Return(None)]
+        ));
+    }
+}
@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod semicolons {
+    use carbide_errors::{
+        error::CarbideError,
+        reporter::ErrorReporter,
+        suggestion::{Applicability, Suggestion},
+    };
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{errors::CarbideParserError, nodes::Statement, parser::CarbideParser};
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    /// Parse `src`, expecting a single missing `;`, and apply its fix
+    fn fix(src: &str) -> String {
+        let (_, result) = parse_src(src);
+        let [error] = result.errors.as_slice() else {
+            panic!("Expected one error, found {:?}", result.errors);
+        };
+        assert!(matches!(**error, CarbideParserError::MissingSemicolon(_)));
+
+        let [suggestion] = error.suggestions().try_into().unwrap_or_else(|s| {
+            panic!("Expected one suggestion, found {s:?}");
+        });
+        assert_eq!(suggestion.applicability, Applicability::MachineApplicable);
+        suggestion
+            .apply(src)
+            .expect("Expected the suggestion to apply")
+    }
+
+    #[test]
+    fn let_statement() {
+        assert_eq!(fix("let x = 1\nlet y = 2;"), "let x = 1;\nlet y = 2;");
+    }
+
+    #[test]
+    fn return_statement() {
+        assert_eq!(
+            fix("fn f() {\n    return 1\n    g();\n}"),
+            "fn f() {\n    return 1;\n    g();\n}"
+        );
+    }
+
+    #[test]
+    fn expression_statement() {
+        assert_eq!(fix("f(a)  \ng(b);"), "f(a);  \ng(b);");
+    }
+
+    #[test]
+    fn end_of_file() {
+        assert_eq!(fix("let x = 1"), "let x = 1;");
+    }
+
+    #[test]
+    fn points_past_previous_token() {
+        let (_, result) = parse_src("let x = 1\nlet y = 2;");
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::MissingSemicolon(loc) if loc.offset == 9
+        ));
+    }
+
+    #[test]
+    fn statement_kept_on_new_line() {
+        let (_, result) = parse_src("let x = 1\nlet y = 2;");
+        assert!(matches!(
+            result.ast.as_slice(),
+            [
+                Statement::LetDeclaration { .. },
+                Statement::LetDeclaration { .. }
+            ]
+        ));
+    }
+
+    #[test]
+    fn same_line_is_an_error_statement() {
+        let (_, result) = parse_src("let x = 1 2;\nlet y = 2;");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            result.ast.as_slice(),
+            [Statement::Error(_), Statement::LetDeclaration { .. }]
+        ));
+    }
+
+    #[test]
+    fn report() {
+        let src = "let x = 1\nlet y = 2;";
+        let (_, result) = parse_src(src);
+
+        let mut reporter = ErrorReporter::new();
+        reporter.add_source("test.cb", src);
+        let report = reporter
+            .format_error("test.cb", &*result.errors[0])
+            .expect("Expected formatting to succeed");
+        assert!(report.contains("E1003"), "{report}");
+    }
+
+    #[test]
+    fn insert_suggestion() {
+        let suggestion = Suggestion::insert("Add a `;`", 3, ";", Applicability::MaybeIncorrect);
+        assert_eq!(suggestion.apply("abcd"), Some("abc;d".to_string()));
+        assert_eq!(suggestion.apply("ab"), None);
+    }
+}
@@ -0,0 +1,84 @@
+#[cfg(test)]
+mod source {
+    use carbide_errors::error::CarbideError;
+    use carbide_lexer::operators::BinaryOperators;
+    use carbide_parser::{
+        Diagnostic,
+        nodes::{Expression, LiteralValue, Statement, Type},
+        parse_expression, parse_program, parse_type,
+    };
+
+    #[test]
+    fn parses_a_program() {
+        let result = parse_program("let x = 1; fn f() {}");
+        assert!(result.is_ok(), "{:?}", result.diagnostics);
+        assert_eq!(result.node.statements.len(), 2);
+    }
+
+    #[test]
+    fn combines_lexer_and_parser_errors() {
+        let result = parse_program("let s = \"open;\nlet = 1;");
+        assert!(result.has_errors());
+        assert!(matches!(result.diagnostics[0], Diagnostic::Lexer(_)));
+        assert!(
+            result.diagnostics[1..]
+                .iter()
+                .all(|d| matches!(d, Diagnostic::Parser(_)))
+        );
+        assert!(result.diagnostics.len() > 1);
+    }
+
+    #[test]
+    fn keeps_statements_around_errors() {
+        let result = parse_program("let a = 1; let = 2; let b = 3;");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert!(matches!(result.node.statements[1], Statement::Error(_)));
+        assert_eq!(result.node.statements.len(), 3);
+    }
+
+    #[test]
+    fn parses_an_expression() {
+        let result = parse_expression("a + 1");
+        assert!(result.is_ok(), "{:?}", result.diagnostics);
+        assert_eq!(
+            result.node,
+            Expression::BinaryOp {
+                left: Box::new(Expression::identifier("a", 0..1)),
+                operator: BinaryOperators::Plus,
+                right: Box::new(Expression::Literal(LiteralValue::Int(1))),
+            }
+        );
+    }
+
+    #[test]
+    fn rejects_tokens_after_an_expression() {
+        let result = parse_expression("a + 1 b");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].code().to_string(), "E1002");
+        assert_eq!(result.node, Expression::Error(0..7));
+    }
+
+    #[test]
+    fn reports_errors_inside_an_expression() {
+        let result = parse_expression("|| => { break; }");
+        assert!(matches!(result.node, Expression::Lambda { .. }));
+        assert_eq!(result.diagnostics[0].code().to_string(), "E1040");
+    }
+
+    #[test]
+    fn parses_a_type() {
+        let result = parse_type("[(int) -> string?]");
+        assert!(result.is_ok(), "{:?}", result.diagnostics);
+        assert_eq!(
+            result.node,
+            Some(Type::Array(Box::new(Type::Function {
+                parameters: vec![Type::named("int")],
+                return_type: Box::new(Type::Optional(Box::new(Type::named("string")))),
+            })))
+        );
+
+        let result = parse_type("int int");
+        assert_eq!(result.node, None);
+        assert_eq!(result.diagnostics.len(), 1);
+    }
+}
@@ -0,0 +1,226 @@
+#[cfg(test)]
+mod structs {
+    use carbide_errors::{error::CarbideError, reporter::ErrorReporter};
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        errors::CarbideParserError,
+        nodes::{
+            Expression, FieldInit, LiteralValue, Pattern, Statement, StructField, Type, Visibility,
+        },
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    fn float(value: f64) -> Expression {
+        Expression::Literal(LiteralValue::Float(value))
+    }
+
+    #[test]
+    fn declaration() {
+        let (_, result) = parse_src("struct Point { x: float, y: float }");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::StructDeclaration {
+                visibility: Visibility::Private,
+                name: "Point".to_string(),
+                fields: vec![
+                    StructField {
+                        name: "x".to_string(),
+                        type_annotation: Type::named("float"),
+                    },
+                    StructField {
+                        name: "y".to_string(),
+                        type_annotation: Type::named("float"),
+                    },
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn declaration_trailing_comma() {
+        let (_, result) = parse_src("struct Wrapper { inner: [int], }");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn empty_declaration() {
+        let (_, result) = parse_src("struct Unit {}");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::StructDeclaration {
+                visibility: Visibility::Private,
+                name: "Unit".to_string(),
+                fields: vec![],
+            }]
+        );
+    }
+
+    #[test]
+    fn literal() {
+        let (_, result) = parse_src("let p = Point { x: 1.0, y: 2.0 };");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::LetDeclaration {
+                attributes: Vec::new(),
+                mutable: false,
+                pattern: Pattern::binding("p", 4..5),
+                type_annotation: None,
+                initializer: Some(Expression::StructLiteral {
+                    name: "Point".to_string(),
+                    fields: vec![
+                        FieldInit {
+                            name: "x".to_string(),
+                            value: float(1.0),
+                        },
+                        FieldInit {
+                            name: "y".to_string(),
+                            value: float(2.0),
+                        },
+                    ],
+                    base: None,
+                }),
+                span: 4..5,
+            }]
+        );
+    }
+
+    #[test]
+    fn shorthand_literal() {
+        let (_, result) = parse_src("Point { x, y: 2.0 };");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::Expression(Expression::StructLiteral {
+                name: "Point".to_string(),
+                fields: vec![
+                    FieldInit {
+                        name: "x".to_string(),
+                        value: Expression::identifier("x", 8..9),
+                    },
+                    FieldInit {
+                        name: "y".to_string(),
+                        value: float(2.0),
+                    },
+                ],
+                base: None,
+            })]
+        );
+    }
+
+    #[test]
+    fn field_update() {
+        let (_, result) = parse_src("Point { x: 1.0, ..origin };");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::Expression(Expression::StructLiteral {
+                name: "Point".to_string(),
+                fields: vec![FieldInit {
+                    name: "x".to_string(),
+                    value: float(1.0),
+                }],
+                base: Some(Box::new(Expression::identifier("origin", 18..24))),
+            })]
+        );
+    }
+
+    #[test]
+    fn field_update_must_be_last() {
+        let (_, result) = parse_src("Point { ..origin, x: 1.0 };");
+        assert!(!result.is_ok());
+        assert_eq!(
+            result
+                .errors
+                .iter()
+                .map(|error| &**error)
+                .collect::<Vec<_>>(),
+            [&CarbideParserError::MisplacedStructBase(8..16)]
+        );
+        assert_eq!(result.errors[0].code().to_string(), "E1070");
+
+        // The fields after the base are still kept
+        assert_eq!(
+            result.ast,
+            vec![Statement::Expression(Expression::StructLiteral {
+                name: "Point".to_string(),
+                fields: vec![FieldInit {
+                    name: "x".to_string(),
+                    value: float(1.0),
+                }],
+                base: Some(Box::new(Expression::identifier("origin", 10..16))),
+            })]
+        );
+    }
+
+    #[test]
+    fn misplaced_field_update_report() {
+        let src = "let p = Point { ..origin, x: 1.0 };";
+        let (_, result) = parse_src(src);
+
+        let mut reporter = ErrorReporter::new();
+        reporter.add_source("test.cb", src);
+        let report = reporter
+            .format_error("test.cb", &*result.errors[0])
+            .expect("Expected formatting to succeed");
+        assert!(report.contains("E1070"), "{report}");
+        assert!(
+            report.contains("Move the `..base` after the other fields"),
+            "{report}"
+        );
+    }
+
+    #[test]
+    fn member_access_on_literal() {
+        let (_, result) = parse_src("Point { x: 1.0 }.x;");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::Expression(Expression::MemberAccess { target, member, .. })
+                if member == "x" && matches!(**target, Expression::StructLiteral { .. })
+        ));
+    }
+
+    #[test]
+    fn condition_is_not_a_literal() {
+        let (_, result) = parse_src("while ready { step(); }");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::While {
+                condition: Expression::Identifier { name, .. },
+                body,
+            } if name == "ready" && body.len() == 1
+        ));
+    }
+
+    #[test]
+    fn parenthesized_literal_in_condition() {
+        let (_, result) = parse_src("while (Point { x }).x { step(); }");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn literal_in_loop_body() {
+        let (_, result) = parse_src("while ready { let p = Point { x }; }");
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn missing_field_type() {
+        let (_, result) = parse_src("struct Point { x, y }");
+        assert!(!result.is_ok());
+    }
+}
@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod traits {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        errors::Expected,
+        nodes::{Block, Expression, Method, Parameter, Pattern, Statement, Type, Visibility},
+        parser::{CarbideParser, ParseResult},
+        print::print_program,
+    };
+
+    fn parse_src(src: &str) -> ParseResult {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        CarbideParser::new(result.tokens).parse()
+    }
+
+    fn parse_ok(src: &str) -> Vec<Statement> {
+        let result = parse_src(src);
+        assert!(result.is_ok(), "{src}: {:?}", result.errors);
+        result.ast
+    }
+
+    fn self_parameter(span: std::ops::Range<u64>) -> Parameter {
+        Parameter {
+            mutable: false,
+            pattern: Pattern::binding("self", span.clone()),
+            type_annotation: None,
+            span,
+        }
+    }
+
+    #[test]
+    fn trait_declaration() {
+        assert_eq!(
+            parse_ok("pub trait Shape { fn area(self) -> float; fn sides(self) -> int { 0 } }"),
+            vec![Statement::TraitDeclaration {
+                visibility: Visibility::Public,
+                name: "Shape".to_string(),
+                methods: vec![
+                    Method {
+                        name: "area".to_string(),
+                        parameters: vec![self_parameter(26..30)],
+                        return_type: Some(Type::named("float")),
+                        body: None,
+                        span: 21..25,
+                    },
+                    Method {
+                        name: "sides".to_string(),
+                        parameters: vec![self_parameter(51..55)],
+                        return_type: Some(Type::named("int")),
+                        body: Some(Block {
+                            statements: vec![],
+                            tail: Some(Box::new(Expression::Literal(
+                                carbide_parser::nodes::LiteralValue::Int(0)
+                            ))),
+                        }),
+                        span: 45..50,
+                    },
+                ],
+                span: 10..15,
+            }]
+        );
+    }
+
+    #[test]
+    fn trait_impl() {
+        let ast = parse_ok("impl Shape for Circle {\n    fn area(self) -> float { 3.0 }\n}");
+        let [
+            Statement::ImplDeclaration {
+                trait_name,
+                target,
+                methods,
+                span,
+            },
+        ] = ast.as_slice()
+        else {
+            panic!("Expected one impl, found {ast:?}");
+        };
+        assert_eq!(trait_name.as_deref(), Some("Shape"));
+        assert_eq!(target, "Circle");
+        assert_eq!(span, &(0..21));
+        assert_eq!(methods.len(), 1);
+        assert!(methods[0].takes_self());
+        assert_eq!(methods[0].span, 31..35);
+    }
+
+    #[test]
+    fn inherent_impl() {
+        let ast =
+            parse_ok("impl Circle { fn new(r: float) -> Circle { Circle { r } } }\nimpl Empty {}");
+        assert!(matches!(
+            ast.as_slice(),
+            [
+                Statement::ImplDeclaration {
+                    trait_name: None,
+                    target,
+                    methods,
+                    span,
+                },
+                Statement::ImplDeclaration {
+                    trait_name: None,
+                    methods: empty,
+                    ..
+                },
+            ] if target == "Circle"
+                && span == &(0..11)
+                && methods.len() == 1
+                && !methods[0].takes_self()
+                && empty.is_empty()
+        ));
+    }
+
+    #[test]
+    fn nested_in_function() {
+        let ast = parse_ok("fn f() { trait T {} impl T for P {} }");
+        assert!(matches!(
+            ast.as_slice(),
+            [Statement::FunctionDeclaration { body, .. }]
+                if matches!(
+                    body.statements.as_slice(),
+                    [Statement::TraitDeclaration { .. }, Statement::ImplDeclaration { .. }]
+                )
+        ));
+    }
+
+    #[test]
+    fn method_call() {
+        let ast = parse_ok("c.area(2);");
+        assert_eq!(
+            ast,
+            vec![Statement::Expression(Expression::Call {
+                callee: Box::new(Expression::MemberAccess {
+                    target: Box::new(Expression::identifier("c", 0..1)),
+                    member: "area".to_string(),
+                    span: 2..6,
+                }),
+                arguments: vec![Expression::Literal(
+                    carbide_parser::nodes::LiteralValue::Int(2)
+                )],
+            })]
+        );
+    }
+
+    #[test]
+    fn impl_methods_need_bodies() {
+        let result = parse_src("impl Circle { fn area(self) -> float; }");
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert_eq!(
+            result.errors[0]
+                .expected()
+                .map(|set| set.iter().copied().collect::<Vec<_>>()),
+            Some(vec![Expected::Token("{")])
+        );
+    }
+
+    #[test]
+    fn errors() {
+        for (src, expected) in [
+            ("trait { }", vec![Expected::Kind("a trait name")]),
+            (
+                "impl Shape Circle {}",
+                vec![Expected::Token("for"), Expected::Token("{")],
+            ),
+            ("impl Shape for {}", vec![Expected::Kind("a type name")]),
+            (
+                "trait Shape { let x = 1; }",
+                vec![Expected::Token("}"), Expected::Token("fn")],
+            ),
+        ] {
+            let result = parse_src(src);
+            assert!(!result.errors.is_empty(), "{src}");
+            assert_eq!(
+                result.errors[0]
+                    .expected()
+                    .map(|set| set.iter().copied().collect::<Vec<_>>()),
+                Some(expected),
+                "{src}"
+            );
+        }
+    }
+
+    #[test]
+    fn recovers_after_a_bad_impl() {
+        let result = parse_src("impl { }\nlet x = 1;");
+        assert_eq!(result.errors.len(), 1, "{:?}", result.errors);
+        assert!(matches!(
+            result.ast.last(),
+            Some(Statement::LetDeclaration { .. })
+        ));
+    }
+
+    #[test]
+    fn prints() {
+        let src = "pub trait Shape {\n    fn area(self) -> float;\n}\nimpl Shape for Circle {\n    fn area(self) -> float {\n        return self.r;\n    }\n}\nimpl Circle {}\n";
+        let ast = parse_ok(src);
+        let printed = print_program(&ast).expect("Expected the program to print");
+        assert_eq!(parse_ok(&printed), ast);
+        assert!(printed.contains("impl Shape for Circle {"), "{printed}");
+        assert!(printed.contains("fn area(self) -> float;"), "{printed}");
+    }
+}
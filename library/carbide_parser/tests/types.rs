@@ -0,0 +1,191 @@
+#[cfg(test)]
+mod types {
+    use carbide_lexer::lexer::CarbideLexer;
+    use carbide_parser::{
+        nodes::{Statement, Type, Visibility},
+        parser::CarbideParser,
+    };
+
+    fn parse_src(src: &'_ str) -> (CarbideParser<'_>, carbide_parser::parser::ParseResult) {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let mut parser = CarbideParser::new(result.tokens);
+        let result = parser.parse();
+        (parser, result)
+    }
+
+    /// Parse the type annotation of `let x: <ty>;`
+    fn parse_annotation(ty: &str) -> Type {
+        let src = format!("let x: {ty};");
+        let (_, result) = parse_src(&src);
+        assert!(
+            result.is_ok(),
+            "Parser failed for '{src}': {:?}",
+            result.errors
+        );
+
+        match result.ast.into_iter().next() {
+            Some(Statement::LetDeclaration {
+                type_annotation: Some(ty),
+                ..
+            }) => ty,
+            other => panic!("Expected a typed let declaration, found {other:?}"),
+        }
+    }
+
+    #[test]
+    fn primitive() {
+        assert_eq!(parse_annotation("int"), Type::named("int"));
+        assert_eq!(parse_annotation("bool"), Type::named("bool"));
+    }
+
+    #[test]
+    fn user_defined() {
+        assert_eq!(parse_annotation("Point"), Type::named("Point"));
+    }
+
+    #[test]
+    fn unit() {
+        assert_eq!(parse_annotation("()"), Type::Unit);
+    }
+
+    #[test]
+    fn parenthesized() {
+        assert_eq!(parse_annotation("(int)"), Type::named("int"));
+    }
+
+    #[test]
+    fn tuple() {
+        assert_eq!(
+            parse_annotation("(int, float)"),
+            Type::Tuple(vec![Type::named("int"), Type::named("float")])
+        );
+        assert_eq!(
+            parse_annotation("(int,)"),
+            Type::Tuple(vec![Type::named("int")])
+        );
+    }
+
+    #[test]
+    fn function() {
+        assert_eq!(
+            parse_annotation("(int, string) -> bool"),
+            Type::Function {
+                parameters: vec![Type::named("int"), Type::named("string")],
+                return_type: Box::new(Type::named("bool")),
+            }
+        );
+    }
+
+    #[test]
+    fn function_without_parameters() {
+        assert_eq!(
+            parse_annotation("() -> ()"),
+            Type::Function {
+                parameters: vec![],
+                return_type: Box::new(Type::Unit),
+            }
+        );
+    }
+
+    #[test]
+    fn curried_function() {
+        assert_eq!(
+            parse_annotation("(int) -> (int) -> int"),
+            Type::Function {
+                parameters: vec![Type::named("int")],
+                return_type: Box::new(Type::Function {
+                    parameters: vec![Type::named("int")],
+                    return_type: Box::new(Type::named("int")),
+                }),
+            }
+        );
+    }
+
+    #[test]
+    fn optional() {
+        assert_eq!(
+            parse_annotation("int?"),
+            Type::Optional(Box::new(Type::named("int")))
+        );
+        assert_eq!(
+            parse_annotation("[int?]?"),
+            Type::Optional(Box::new(Type::Array(Box::new(Type::Optional(Box::new(
+                Type::named("int")
+            ))))))
+        );
+    }
+
+    #[test]
+    fn optional_function() {
+        assert_eq!(
+            parse_annotation("(() -> int)?"),
+            Type::Optional(Box::new(Type::Function {
+                parameters: vec![],
+                return_type: Box::new(Type::named("int")),
+            }))
+        );
+    }
+
+    #[test]
+    fn generic() {
+        assert_eq!(
+            parse_annotation("Map<string, [int]>"),
+            Type::Generic {
+                name: "Map".to_string(),
+                arguments: vec![
+                    Type::named("string"),
+                    Type::Array(Box::new(Type::named("int")))
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn nested_generic() {
+        assert_eq!(
+            parse_annotation("List<Map<string, int>>"),
+            Type::Generic {
+                name: "List".to_string(),
+                arguments: vec![Type::Generic {
+                    name: "Map".to_string(),
+                    arguments: vec![Type::named("string"), Type::named("int")],
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn function_return_type() {
+        let (_, result) = parse_src("fn f() -> (int, int) {}");
+        assert!(result.is_ok());
+        assert!(matches!(
+            &result.ast[0],
+            Statement::FunctionDeclaration {
+                visibility: Visibility::Private,
+                return_type: Some(Type::Tuple(_)),
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn unclosed_generic() {
+        let (_, result) = parse_src("let x: Map<string, int;");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn unclosed_tuple() {
+        let (_, result) = parse_src("let x: (int, float;");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn primitive_is_not_an_expression() {
+        let (_, result) = parse_src("let x = int;");
+        assert!(!result.is_ok());
+    }
+}
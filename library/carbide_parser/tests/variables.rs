@@ -2,7 +2,8 @@
 mod variables {
     use carbide_lexer::lexer::CarbideLexer;
     use carbide_parser::{
-        nodes::{Expression, LiteralValue, Statement, Type},
+        errors::CarbideParserError,
+        nodes::{Expression, LiteralValue, Pattern, Statement, Type, Visibility},
         parser::CarbideParser,
     };
 
@@ -23,9 +24,12 @@ mod variables {
         assert_eq!(
             result.ast,
             vec![Statement::LetDeclaration {
-                name: "my_var".into(),
+                attributes: Vec::new(),
+                mutable: false,
+                pattern: Pattern::binding("my_var", 4..10),
                 type_annotation: None,
                 initializer: Some(Expression::Literal(LiteralValue::Int(0))),
+                span: 4..10,
             }]
         );
     }
@@ -37,9 +41,12 @@ mod variables {
         assert_eq!(
             result.ast,
             vec![Statement::LetDeclaration {
-                name: "my_var".into(),
+                attributes: Vec::new(),
+                mutable: false,
+                pattern: Pattern::binding("my_var", 4..10),
                 type_annotation: None,
                 initializer: None,
+                span: 4..10,
             }]
         );
     }
@@ -51,9 +58,12 @@ mod variables {
         assert_eq!(
             result.ast,
             vec![Statement::LetDeclaration {
-                name: "my_var".into(),
+                attributes: Vec::new(),
+                mutable: false,
+                pattern: Pattern::binding("my_var", 4..10),
                 type_annotation: Some(Type::named("int")),
                 initializer: Some(Expression::Literal(LiteralValue::Int(0))),
+                span: 4..10,
             }]
         );
     }
@@ -77,8 +87,25 @@ mod variables {
     }
 
     #[test]
-    fn invalid_block_initializer() {
+    fn empty_map_initializer() {
         let (_, result) = parse_src("let my_var = {};");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::LetDeclaration {
+                attributes: Vec::new(),
+                mutable: false,
+                pattern: Pattern::binding("my_var", 4..10),
+                type_annotation: None,
+                initializer: Some(Expression::Map(vec![])),
+                span: 4..10,
+            }]
+        );
+    }
+
+    #[test]
+    fn invalid_unclosed_map_initializer() {
+        let (_, result) = parse_src("let my_var = {;");
         assert!(!result.is_ok());
     }
 
@@ -224,4 +251,77 @@ mod variables {
         let (_, result) = parse_src("let z = 1 + 2 + 3 + 4 + 5 + 6 + 7 + 8 + 9;");
         assert!(result.is_ok(), "Should handle long left-associative chains");
     }
+
+    #[test]
+    fn mutable_declaration() {
+        let (_, result) = parse_src("let mut count = 0;");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::LetDeclaration {
+                attributes: Vec::new(),
+                mutable: true,
+                pattern: Pattern::binding("count", 8..13),
+                type_annotation: None,
+                initializer: Some(Expression::Literal(LiteralValue::Int(0))),
+                span: 8..13,
+            }]
+        );
+    }
+
+    #[test]
+    fn const_declaration() {
+        let (_, result) = parse_src("const LIMIT: int = 10;");
+        assert!(result.is_ok());
+        assert_eq!(
+            result.ast,
+            vec![Statement::ConstDeclaration {
+                visibility: Visibility::Private,
+                name: "LIMIT".into(),
+                type_annotation: Type::named("int"),
+                value: Expression::Literal(LiteralValue::Int(10)),
+                span: 6..11,
+            }]
+        );
+    }
+
+    #[test]
+    fn pub_const_declaration() {
+        let (_, result) = parse_src("pub const NAME: string = \"carbide\";");
+        assert!(result.is_ok());
+        assert!(matches!(
+            result.ast[0],
+            Statement::ConstDeclaration {
+                visibility: Visibility::Public,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn const_requires_type() {
+        let (_, result) = parse_src("const LIMIT = 10;");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn const_requires_initializer() {
+        let (_, result) = parse_src("const LIMIT: int;");
+        assert!(!result.is_ok());
+    }
+
+    #[test]
+    fn const_outside_top_level() {
+        let (_, result) = parse_src("fn f() {\n    const LIMIT: int = 10;\n}");
+        assert_eq!(result.errors.len(), 1);
+        assert!(matches!(
+            *result.errors[0],
+            CarbideParserError::ConstOutsideTopLevel(loc) if loc.line == 2 && loc.column == 5
+        ));
+        // The declaration itself is still kept
+        assert!(matches!(
+            &result.ast[0],
+            Statement::FunctionDeclaration { body, .. } if body.statements.len() == 1
+        ));
+    }
 }
@@ -0,0 +1,182 @@
+#[cfg(test)]
+mod visit {
+    use carbide_lexer::{lexer::CarbideLexer, operators::BinaryOperators};
+    use carbide_parser::{
+        fold::{self, Folder},
+        nodes::{Expression, LiteralValue, Pattern, Program, Type},
+        parser::CarbideParser,
+        print::print_program,
+        visit::{self, Visitor},
+        visit_mut::{self, VisitorMut},
+    };
+
+    fn parse_program(src: &str) -> Program {
+        let mut lexer = CarbideLexer::from_src(src);
+        let result = lexer.lex();
+        assert!(result.is_ok(), "Lexer failed for '{src}'");
+
+        let result = CarbideParser::new(result.tokens).parse();
+        assert!(result.is_ok(), "Parser failed: {:?}", result.errors);
+        Program::new(result.ast)
+    }
+
+    /// Collects every identifier, binding and named type in the order they're visited
+    #[derive(Default)]
+    struct Names<'ast> {
+        identifiers: Vec<&'ast str>,
+        bindings: Vec<&'ast str>,
+        types: Vec<&'ast str>,
+    }
+
+    impl<'ast> Visitor<'ast> for Names<'ast> {
+        fn visit_expression(&mut self, expression: &'ast Expression) {
+            if let Expression::Identifier { name, .. } = expression {
+                self.identifiers.push(name);
+            }
+            visit::walk_expression(self, expression);
+        }
+
+        fn visit_pattern(&mut self, pattern: &'ast Pattern) {
+            if let Pattern::Binding { name, .. } = pattern {
+                self.bindings.push(name);
+            }
+            visit::walk_pattern(self, pattern);
+        }
+
+        fn visit_type(&mut self, ty: &'ast Type) {
+            if let Type::Named(name) | Type::Generic { name, .. } = ty {
+                self.types.push(name);
+            }
+            visit::walk_type(self, ty);
+        }
+    }
+
+    #[test]
+    fn visitor_reaches_nested_nodes() {
+        let program = parse_program(
+            "fn f(a: [int], b: (string) -> bool?) {
+                let g = |c| => { a[c] };
+                for (let i = 0; i < len; i = i + 1) { h(\"{i}\"); }
+                let s = match p { Some(x) if x > lo => x, _ => if ok { d } else { e } };
+                let t = Point { x: px, ..base };
+            }
+            enum E { A(float), B { w: Map<string, int> } }",
+        );
+
+        let mut names = Names::default();
+        names.visit_program(&program);
+
+        assert_eq!(
+            names.identifiers,
+            vec![
+                "a", "c", "i", "len", "i", "i", "h", "i", "p", "x", "lo", "x", "ok", "d", "e",
+                "px", "base"
+            ]
+        );
+        assert_eq!(names.bindings, vec!["a", "b", "g", "c", "i", "s", "x", "t"]);
+        assert_eq!(
+            names.types,
+            vec!["int", "string", "bool", "float", "Map", "string", "int"]
+        );
+    }
+
+    #[test]
+    fn overrides_can_skip_children() {
+        /// Counts literals outside of lambdas
+        struct Literals(usize);
+
+        impl Visitor<'_> for Literals {
+            fn visit_expression(&mut self, expression: &Expression) {
+                if !matches!(expression, Expression::Lambda { .. }) {
+                    visit::walk_expression(self, expression);
+                }
+            }
+
+            fn visit_literal(&mut self, _literal: &LiteralValue) {
+                self.0 += 1;
+            }
+        }
+
+        let program = parse_program("let f = |x| => x + 1; let y = [2, 3]; g(|| => 4);");
+        let mut literals = Literals(0);
+        literals.visit_program(&program);
+        assert_eq!(literals.0, 2);
+    }
+
+    #[test]
+    fn visitor_mut_rewrites_in_place() {
+        struct Rename;
+
+        impl VisitorMut for Rename {
+            fn visit_expression_mut(&mut self, expression: &mut Expression) {
+                if let Expression::Identifier { name, .. } = expression
+                    && name == "old"
+                {
+                    *name = "new".to_string();
+                }
+                visit_mut::walk_expression_mut(self, expression);
+            }
+        }
+
+        let mut program = parse_program("let a = old + f(old); while old { old = { old }; }");
+        Rename.visit_program_mut(&mut program);
+        assert_eq!(
+            program,
+            parse_program("let a = new + f(new); while new { new = { new }; }")
+        );
+    }
+
+    #[test]
+    fn default_folder_is_identity() {
+        struct Identity;
+        impl Folder for Identity {}
+
+        for src in [
+            "pub fn f(mut a: int) -> int? { let b: [int] = [a, !a]; return b[0]; }",
+            "struct P { x: float } enum E { A(int), B { p: P }, C }",
+            "import a.b.{c, d}; const C: {string: int} = { \"k\": 1 };",
+            "let m = match v { E::A(n) => n, E::B { p: P { x }, .. } => 0, _ => 1 };",
+            "let l = |a, b: int| -> int => { a * b }; let s = \"{l(1, 2)}!\";",
+        ] {
+            let program = parse_program(src);
+            assert_eq!(Identity.fold_program(program.clone()), program, "{src}");
+        }
+    }
+
+    #[test]
+    fn folder_replaces_nodes() {
+        /// Folds additions of integer literals, innermost first
+        struct ConstantFolder;
+
+        impl Folder for ConstantFolder {
+            fn fold_expression(&mut self, expression: Expression) -> Expression {
+                match fold::walk_expression(self, expression) {
+                    Expression::BinaryOp {
+                        left,
+                        operator: BinaryOperators::Plus,
+                        right,
+                    } => match (*left, *right) {
+                        (
+                            Expression::Literal(LiteralValue::Int(a)),
+                            Expression::Literal(LiteralValue::Int(b)),
+                        ) => Expression::Literal(LiteralValue::Int(a + b)),
+                        (left, right) => Expression::BinaryOp {
+                            left: Box::new(left),
+                            operator: BinaryOperators::Plus,
+                            right: Box::new(right),
+                        },
+                    },
+                    other => other,
+                }
+            }
+        }
+
+        // Compared as source, since the folded nodes keep their original spans
+        let program =
+            ConstantFolder.fold_program(parse_program("let x = 1 + 2 + 3; f(x + 1, [4 + 5]);"));
+        assert_eq!(
+            print_program(&program.statements).as_deref(),
+            Ok("let x = 6;\nf(x + 1, [9]);\n")
+        );
+    }
+}